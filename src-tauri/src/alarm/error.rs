@@ -0,0 +1,67 @@
+// 告警错误定义
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::AlarmState;
+use crate::history::HistoryError;
+use crate::i18n::tr;
+
+/// 告警规则校验、判定或告警处理失败的原因
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum AlarmError {
+    /// 规则名称为空
+    EmptyRuleName,
+    /// 温度限值不是有限数值
+    InvalidLimit { value: f64 },
+    /// 湿度规则没有设置上下限
+    EmptyHumidityRange,
+    /// 湿度下限高于上限
+    InvalidHumidityRange { min: u8, max: u8 },
+    /// 传感器故障规则的测温点数量为0
+    InvalidSensorCount,
+    /// 告警不存在
+    AlarmNotFound { id: i64 },
+    /// 告警当前状态不允许该操作
+    InvalidState { id: i64, state: AlarmState },
+    /// 确认或清除告警时未填写操作员
+    EmptyOperator,
+    /// 告警数据库操作失败
+    Database { message: String },
+    /// 读取历史数据失败
+    History { error: HistoryError },
+}
+
+impl fmt::Display for AlarmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            AlarmError::EmptyRuleName => tr!("alarm.emptyRuleName"),
+            AlarmError::InvalidLimit { value } => tr!("alarm.invalidLimit", value = value),
+            AlarmError::EmptyHumidityRange => tr!("alarm.emptyHumidityRange"),
+            AlarmError::InvalidHumidityRange { min, max } => tr!("alarm.invalidHumidityRange", min = min, max = max),
+            AlarmError::InvalidSensorCount => tr!("alarm.invalidSensorCount"),
+            AlarmError::AlarmNotFound { id } => tr!("alarm.alarmNotFound", id = id),
+            AlarmError::InvalidState { id, state } => tr!("alarm.invalidState", id = id, state = state.as_str()),
+            AlarmError::EmptyOperator => tr!("alarm.emptyOperator"),
+            AlarmError::Database { message } => tr!("alarm.database", error = message),
+            AlarmError::History { error } => error.to_string(),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for AlarmError {}
+
+impl From<rusqlite::Error> for AlarmError {
+    fn from(error: rusqlite::Error) -> Self {
+        AlarmError::Database {
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<HistoryError> for AlarmError {
+    fn from(error: HistoryError) -> Self {
+        AlarmError::History { error }
+    }
+}
@@ -0,0 +1,242 @@
+// 告警模块，按规则判定每次保存的读数，产生告警并支持确认和清除
+use chrono::Local;
+use log::{error, info, warn};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+
+use crate::history::HistoryStore;
+use crate::i18n::tr;
+use crate::layout::Layouts;
+
+// 导出错误定义
+pub mod error;
+// 导出告警规则模块
+pub mod rule;
+// 导出表结构与升级模块
+pub mod schema;
+// 导出告警存储模块
+pub mod store;
+
+pub use error::AlarmError;
+pub use rule::AlarmRule;
+pub use store::{Alarm, AlarmQuery, AlarmState, AlarmStore};
+
+/// 告警事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum AlarmEvent {
+    /// 产生新告警
+    Raised { alarm: Alarm },
+    /// 告警已确认
+    Acknowledged { alarm: Alarm },
+    /// 告警已清除
+    Cleared { alarm: Alarm },
+}
+
+type Listener = Box<dyn Fn(&AlarmEvent) + Send + Sync>;
+
+struct Shared {
+    store: AlarmStore,
+    history: HistoryStore,
+    layouts: Layouts,
+    rules: Mutex<Vec<AlarmRule>>,
+    listener: Listener,
+}
+
+/// 告警引擎，保存在Tauri托管状态中，可克隆后在后台任务中使用
+#[derive(Clone)]
+pub struct AlarmEngine {
+    shared: Arc<Shared>,
+}
+
+impl AlarmEngine {
+    /// 创建告警引擎并读取已保存的规则，产生、确认和清除告警时调用 `listener`
+    ///
+    /// # 参数
+    ///
+    /// * `store` - 告警数据库
+    /// * `history` - 历史数据库，判定时从中读取读数和同一分机的上一次读数
+    /// * `layouts` - 测温点布局，按层判定时使用
+    /// * `listener` - 告警事件回调
+    pub fn with_listener<F>(store: AlarmStore, history: HistoryStore, layouts: Layouts, listener: F) -> Self
+    where
+        F: Fn(&AlarmEvent) + Send + Sync + 'static,
+    {
+        let rules = store.load_rules().unwrap_or_else(|e| {
+            error!("{}", tr!("alarm.loadFailed", error = e));
+            Vec::new()
+        });
+        info!("{}", tr!("alarm.loaded", count = rules.len()));
+        AlarmEngine {
+            shared: Arc::new(Shared {
+                store,
+                history,
+                layouts,
+                rules: Mutex::new(rules),
+                listener: Box::new(listener),
+            }),
+        }
+    }
+
+    /// 列出全部规则
+    pub fn rules(&self) -> Vec<AlarmRule> {
+        lock(&self.shared.rules).clone()
+    }
+
+    /// 新建或更新规则，`id` 为空或不存在时新建
+    ///
+    /// # 返回值
+    ///
+    /// 保存后的规则（新建时带生成的标识）
+    pub fn save_rule(&self, mut rule: AlarmRule) -> Result<AlarmRule, AlarmError> {
+        rule.validate()?;
+        let mut rules = lock(&self.shared.rules);
+        if rule.id.trim().is_empty() {
+            rule.id = generate_rule_id(&rules);
+        }
+        self.shared.store.save_rule(&rule)?;
+        match rules.iter_mut().find(|existing| existing.id == rule.id) {
+            Some(existing) => *existing = rule.clone(),
+            None => rules.push(rule.clone()),
+        }
+        info!("{}", tr!("alarm.ruleSaved", name = rule.name, id = rule.id));
+        Ok(rule)
+    }
+
+    /// 删除规则，已产生的告警保留
+    ///
+    /// # 返回值
+    ///
+    /// 规则存在时返回true
+    pub fn delete_rule(&self, id: &str) -> Result<bool, AlarmError> {
+        let mut rules = lock(&self.shared.rules);
+        if !self.shared.store.delete_rule(id)? {
+            return Ok(false);
+        }
+        rules.retain(|rule| rule.id != id);
+        info!("{}", tr!("alarm.ruleDeleted", id = id));
+        Ok(true)
+    }
+
+    /// 用导入的规则替换全部规则，已产生的告警保留
+    ///
+    /// 标识为空的规则按新建处理并生成标识
+    pub fn replace_rules(&self, mut rules: Vec<AlarmRule>) -> Result<Vec<AlarmRule>, AlarmError> {
+        for rule in &rules {
+            rule.validate()?;
+        }
+        let mut current = lock(&self.shared.rules);
+        for index in 0..rules.len() {
+            if rules[index].id.trim().is_empty() {
+                rules[index].id = generate_rule_id(&rules);
+            }
+        }
+        self.shared.store.replace_rules(&rules)?;
+        *current = rules.clone();
+        info!("{}", tr!("alarm.rulesReplaced", count = rules.len()));
+        Ok(rules)
+    }
+
+    /// 按启用的规则判定一条已保存的历史记录
+    ///
+    /// # 参数
+    ///
+    /// * `reading_id` - 历史记录编号
+    ///
+    /// # 返回值
+    ///
+    /// 新产生的告警，已存在且未清除的告警只更新触发时间和次数
+    pub fn evaluate(&self, reading_id: i64) -> Result<Vec<Alarm>, AlarmError> {
+        let rules: Vec<AlarmRule> = lock(&self.shared.rules).clone();
+        let history = &self.shared.history;
+        let reading = history.reading(reading_id)?;
+        let rules: Vec<&AlarmRule> = rules.iter().filter(|rule| rule.applies_to(&reading.granary)).collect();
+        if rules.is_empty() {
+            return Ok(Vec::new());
+        }
+        let previous = history.previous(&reading)?;
+        let layout = self.shared.layouts.for_granary(&reading.granary);
+
+        let mut raised = Vec::new();
+        for rule in rules {
+            let violations = rule.evaluate(&reading, previous.as_ref(), &layout);
+            raised.extend(self.shared.store.apply(rule, &reading, &violations)?);
+        }
+        for alarm in &raised {
+            warn!(
+                "{}",
+                tr!(
+                    "alarm.triggered",
+                    rule = alarm.rule_name,
+                    granary = alarm.granary,
+                    address = alarm.sub_device_address,
+                    subject = format!("{:?}", alarm.subject),
+                    value = format!("{:?}", alarm.value),
+                    threshold = format!("{:?}", alarm.threshold)
+                )
+            );
+            (self.shared.listener)(&AlarmEvent::Raised { alarm: alarm.clone() });
+        }
+        Ok(raised)
+    }
+
+    /// 按条件查询告警，按编号从新到旧排列
+    pub fn alarms(&self, query: &AlarmQuery) -> Result<Vec<Alarm>, AlarmError> {
+        self.shared.store.query(query)
+    }
+
+    /// 确认告警
+    ///
+    /// # 参数
+    ///
+    /// * `id` - 告警编号
+    /// * `operator` - 操作员
+    pub fn acknowledge(&self, id: i64, operator: &str) -> Result<Alarm, AlarmError> {
+        let alarm = self.shared.store.acknowledge(id, check_operator(operator)?)?;
+        info!("{}", tr!("alarm.acknowledged", id = id, operator = operator.trim()));
+        (self.shared.listener)(&AlarmEvent::Acknowledged { alarm: alarm.clone() });
+        Ok(alarm)
+    }
+
+    /// 清除告警，清除后条件仍满足时下一次读数会产生新告警
+    ///
+    /// # 参数
+    ///
+    /// * `id` - 告警编号
+    /// * `operator` - 操作员
+    pub fn clear(&self, id: i64, operator: &str) -> Result<Alarm, AlarmError> {
+        let alarm = self.shared.store.clear(id, check_operator(operator)?)?;
+        info!("{}", tr!("alarm.cleared", id = id, operator = operator.trim()));
+        (self.shared.listener)(&AlarmEvent::Cleared { alarm: alarm.clone() });
+        Ok(alarm)
+    }
+}
+
+/// 操作员不能为空，返回去掉首尾空白后的名称
+fn check_operator(operator: &str) -> Result<&str, AlarmError> {
+    let operator = operator.trim();
+    if operator.is_empty() {
+        return Err(AlarmError::EmptyOperator);
+    }
+    Ok(operator)
+}
+
+/// 生成不与现有规则重复的标识
+fn generate_rule_id(rules: &[AlarmRule]) -> String {
+    let base = format!("rule-{}", Local::now().format("%Y%m%d%H%M%S"));
+    let mut id = base.clone();
+    let mut suffix = 1;
+    while rules.iter().any(|rule| rule.id == id) {
+        suffix += 1;
+        id = format!("{}-{}", base, suffix);
+    }
+    id
+}
+
+/// 获取锁，锁中毒时继续使用内部数据
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
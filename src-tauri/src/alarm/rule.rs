@@ -0,0 +1,438 @@
+// 告警规则定义与判定
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use super::AlarmError;
+use crate::history::HistoryReading;
+use crate::layout::GranaryLayout;
+use crate::protocol::SensorStatus;
+
+/// 告警级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    /// 提示
+    Info,
+    /// 警告
+    Warning,
+    /// 严重
+    Critical,
+}
+
+impl Severity {
+    /// 数据库中保存的名称，与序列化名称一致
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+
+    /// 解析数据库中保存的名称，无法识别时按警告处理
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "info" => Severity::Info,
+            "critical" => Severity::Critical,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+/// 湿度传感器位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HumidityLocation {
+    /// 仓内
+    Indoor,
+    /// 仓外
+    Outdoor,
+}
+
+/// 告警条件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum AlarmCondition {
+    /// 任一测温点温度高于上限（°C）
+    MaxTemperature { limit: f64 },
+    /// 任一层的平均温度高于上限（°C），按粮仓的测温点布局分层
+    LayerAverage { limit: f64 },
+    /// 测温点温度比同一分机上一次读数升高超过上限（°C）
+    RiseSincePrevious { limit: f64 },
+    /// 湿度超出范围（%RH），`min` 和 `max` 至少设置一个
+    Humidity {
+        location: HumidityLocation,
+        min: Option<u8>,
+        max: Option<u8>,
+    },
+    /// 编号1到 `expected_sensors` 的测温点断路、短路、超出量程、卡滞或缺少读数
+    SensorFault { expected_sensors: u16 },
+}
+
+/// 告警规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlarmRule {
+    /// 规则标识，新建规则时留空由后端生成
+    #[serde(default)]
+    pub id: String,
+    /// 规则名称
+    pub name: String,
+    /// 是否启用
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 触发时的告警级别
+    pub severity: Severity,
+    /// 只对该粮仓生效，为None时对全部粮仓生效
+    #[serde(default)]
+    pub granary: Option<String>,
+    /// 告警条件
+    pub condition: AlarmCondition,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 告警对象
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum AlarmSubject {
+    /// 单个测温点
+    Sensor { sensor_id: u16 },
+    /// 一层测温点，层号从1开始
+    Layer { layer: u16 },
+    /// 湿度传感器
+    Humidity { location: HumidityLocation },
+    /// 状态异常的测温点，异常类型变化时视为新的告警对象
+    Fault { sensor_id: u16, status: SensorStatus },
+}
+
+/// 一次读数中满足告警条件的对象
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// 告警对象
+    pub subject: AlarmSubject,
+    /// 触发告警的值，传感器故障时为异常测温点的温度（如有）
+    pub value: Option<f64>,
+    /// 越过的限值，传感器故障时为None
+    pub threshold: Option<f64>,
+}
+
+impl AlarmRule {
+    /// 校验规则参数
+    pub fn validate(&self) -> Result<(), AlarmError> {
+        if self.name.trim().is_empty() {
+            return Err(AlarmError::EmptyRuleName);
+        }
+        match &self.condition {
+            AlarmCondition::MaxTemperature { limit }
+            | AlarmCondition::LayerAverage { limit }
+            | AlarmCondition::RiseSincePrevious { limit } => check_limit(*limit),
+            AlarmCondition::Humidity { min, max, .. } => match (min, max) {
+                (None, None) => Err(AlarmError::EmptyHumidityRange),
+                (Some(min), Some(max)) if min > max => Err(AlarmError::InvalidHumidityRange { min: *min, max: *max }),
+                _ => Ok(()),
+            },
+            AlarmCondition::SensorFault { expected_sensors } => {
+                if *expected_sensors == 0 {
+                    return Err(AlarmError::InvalidSensorCount);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// 规则是否对该粮仓生效
+    pub fn applies_to(&self, granary: &str) -> bool {
+        self.enabled && self.granary.as_deref().is_none_or(|scope| scope == granary)
+    }
+
+    /// 判定一次读数中满足告警条件的对象
+    ///
+    /// # 参数
+    ///
+    /// * `reading` - 带测温点读数的历史记录
+    /// * `previous` - 同一粮仓同一分机的上一次读数，没有时为None
+    /// * `layout` - 粮仓的测温点布局，按层判定时使用
+    pub fn evaluate(
+        &self,
+        reading: &HistoryReading,
+        previous: Option<&HistoryReading>,
+        layout: &GranaryLayout,
+    ) -> Vec<Violation> {
+        let sensors = reading.sensors.as_deref().unwrap_or_default();
+        match &self.condition {
+            AlarmCondition::MaxTemperature { limit } => sensors
+                .iter()
+                .filter(|sensor| sensor.temperature > *limit)
+                .map(|sensor| Violation {
+                    subject: AlarmSubject::Sensor {
+                        sensor_id: sensor.sensor_id,
+                    },
+                    value: Some(sensor.temperature),
+                    threshold: Some(*limit),
+                })
+                .collect(),
+            AlarmCondition::LayerAverage { limit } => {
+                // 编号超出布局的测温点无法确定所在层，不参与判定
+                let map = layout.sensor_map();
+                let mut layers: BTreeMap<u16, Vec<f64>> = BTreeMap::new();
+                for sensor in sensors {
+                    if let Some(position) = map.get(sensor.sensor_id) {
+                        layers.entry(position.layer).or_default().push(sensor.temperature);
+                    }
+                }
+                layers
+                    .into_iter()
+                    .map(|(layer, values)| (layer, values.iter().sum::<f64>() / values.len() as f64))
+                    .filter(|(_, average)| average > limit)
+                    .map(|(layer, average)| Violation {
+                        subject: AlarmSubject::Layer { layer },
+                        value: Some(average),
+                        threshold: Some(*limit),
+                    })
+                    .collect()
+            }
+            AlarmCondition::RiseSincePrevious { limit } => {
+                let Some(previous) = previous else {
+                    return Vec::new();
+                };
+                let before: HashMap<u16, f64> = previous
+                    .sensors
+                    .iter()
+                    .flatten()
+                    .map(|sensor| (sensor.sensor_id, sensor.temperature))
+                    .collect();
+                sensors
+                    .iter()
+                    .filter_map(|sensor| {
+                        let rise = sensor.temperature - before.get(&sensor.sensor_id)?;
+                        (rise > *limit).then_some(Violation {
+                            subject: AlarmSubject::Sensor {
+                                sensor_id: sensor.sensor_id,
+                            },
+                            value: Some(rise),
+                            threshold: Some(*limit),
+                        })
+                    })
+                    .collect()
+            }
+            AlarmCondition::Humidity { location, min, max } => {
+                let humidity = match location {
+                    HumidityLocation::Indoor => reading.environment.indoor_humidity,
+                    HumidityLocation::Outdoor => reading.environment.outdoor_humidity,
+                };
+                let threshold = humidity.and_then(|value| {
+                    max.filter(|max| value > *max).or_else(|| min.filter(|min| value < *min))
+                });
+                match (humidity, threshold) {
+                    (Some(value), Some(threshold)) => vec![Violation {
+                        subject: AlarmSubject::Humidity { location: *location },
+                        value: Some(value.into()),
+                        threshold: Some(threshold.into()),
+                    }],
+                    _ => Vec::new(),
+                }
+            }
+            AlarmCondition::SensorFault { expected_sensors } => (1..=*expected_sensors)
+                .filter_map(|sensor_id| {
+                    let status = reading.sensor_status(sensor_id);
+                    let value = reading
+                        .faults
+                        .iter()
+                        .flatten()
+                        .find(|fault| fault.sensor_id == sensor_id)
+                        .and_then(|fault| fault.temperature);
+                    (status != SensorStatus::Valid).then_some(Violation {
+                        subject: AlarmSubject::Fault { sensor_id, status },
+                        value,
+                        threshold: None,
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 温度限值必须是有限数值
+fn check_limit(limit: f64) -> Result<(), AlarmError> {
+    if limit.is_finite() {
+        Ok(())
+    } else {
+        Err(AlarmError::InvalidLimit { value: limit })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::model::WiringOrder;
+    use crate::protocol::{EnvironmentData, SensorFault, SensorReading};
+
+    fn rule(condition: AlarmCondition) -> AlarmRule {
+        AlarmRule {
+            id: "rule-1".to_string(),
+            name: "测试规则".to_string(),
+            enabled: true,
+            severity: Severity::Warning,
+            granary: None,
+            condition,
+        }
+    }
+
+    fn reading(temperatures: &[(u16, f64)]) -> HistoryReading {
+        HistoryReading {
+            id: 1,
+            granary: "1号仓".to_string(),
+            sub_device_address: 1,
+            source: "127.0.0.1:2000".to_string(),
+            sampled_at: "2024-05-01 08:00:00".to_string(),
+            device_time_valid: true,
+            received_at: "2024-05-01 08:00:00".to_string(),
+            packet_count: 1,
+            sensor_count: temperatures.len(),
+            environment: EnvironmentData::default(),
+            sensors: Some(
+                temperatures
+                    .iter()
+                    .map(|&(sensor_id, temperature)| SensorReading {
+                        sensor_id,
+                        temperature,
+                        position: None,
+                    })
+                    .collect(),
+            ),
+            faults: Some(Vec::new()),
+            raw_frame: None,
+        }
+    }
+
+    /// 1行2列、每根2个测温点，按层编号：1、2号在第1层，3、4号在第2层
+    fn layout() -> GranaryLayout {
+        GranaryLayout {
+            cable_rows: 1,
+            cable_columns: 2,
+            points_per_cable: 2,
+            wiring: WiringOrder::ByLayer,
+            skipped: Vec::new(),
+        }
+    }
+
+    fn subjects(violations: &[Violation]) -> Vec<AlarmSubject> {
+        violations.iter().map(|violation| violation.subject.clone()).collect()
+    }
+
+    #[test]
+    fn flags_sensors_above_max_temperature() {
+        let rule = rule(AlarmCondition::MaxTemperature { limit: 30.0 });
+        let violations = rule.evaluate(&reading(&[(1, 29.5), (2, 30.0), (3, 31.2)]), None, &layout());
+
+        assert_eq!(
+            violations,
+            vec![Violation {
+                subject: AlarmSubject::Sensor { sensor_id: 3 },
+                value: Some(31.2),
+                threshold: Some(30.0),
+            }]
+        );
+    }
+
+    #[test]
+    fn averages_each_layer() {
+        let rule = rule(AlarmCondition::LayerAverage { limit: 25.0 });
+        // 第1层平均24，第2层平均26；5号超出布局，不参与判定
+        let current = reading(&[(1, 20.0), (2, 28.0), (3, 25.0), (4, 27.0), (5, 90.0)]);
+        let violations = rule.evaluate(&current, None, &layout());
+
+        assert_eq!(
+            violations,
+            vec![Violation {
+                subject: AlarmSubject::Layer { layer: 2 },
+                value: Some(26.0),
+                threshold: Some(25.0),
+            }]
+        );
+    }
+
+    #[test]
+    fn compares_rise_with_previous_reading() {
+        let rule = rule(AlarmCondition::RiseSincePrevious { limit: 2.0 });
+        let current = reading(&[(1, 22.0), (2, 25.0), (3, 30.0)]);
+        assert!(rule.evaluate(&current, None, &layout()).is_empty());
+
+        // 3号在上一次读数中没有数据，无法比较
+        let previous = reading(&[(1, 21.0), (2, 22.5)]);
+        let violations = rule.evaluate(&current, Some(&previous), &layout());
+        assert_eq!(
+            violations,
+            vec![Violation {
+                subject: AlarmSubject::Sensor { sensor_id: 2 },
+                value: Some(2.5),
+                threshold: Some(2.0),
+            }]
+        );
+    }
+
+    #[test]
+    fn checks_humidity_range() {
+        let rule = rule(AlarmCondition::Humidity {
+            location: HumidityLocation::Indoor,
+            min: Some(40),
+            max: Some(70),
+        });
+        let mut current = reading(&[]);
+        assert!(rule.evaluate(&current, None, &layout()).is_empty());
+
+        current.environment.outdoor_humidity = Some(90);
+        current.environment.indoor_humidity = Some(65);
+        assert!(rule.evaluate(&current, None, &layout()).is_empty());
+
+        current.environment.indoor_humidity = Some(75);
+        let violations = rule.evaluate(&current, None, &layout());
+        assert_eq!(
+            violations,
+            vec![Violation {
+                subject: AlarmSubject::Humidity {
+                    location: HumidityLocation::Indoor,
+                },
+                value: Some(75.0),
+                threshold: Some(70.0),
+            }]
+        );
+
+        current.environment.indoor_humidity = Some(35);
+        let violations = rule.evaluate(&current, None, &layout());
+        assert_eq!(violations[0].threshold, Some(40.0));
+    }
+
+    #[test]
+    fn reports_faulty_and_missing_sensors() {
+        let rule = rule(AlarmCondition::SensorFault { expected_sensors: 4 });
+        let mut current = reading(&[(1, 20.0), (3, 21.0)]);
+        current.faults = Some(vec![SensorFault {
+            sensor_id: 2,
+            status: SensorStatus::OutOfRange,
+            raw: Some(0x7FFF),
+            temperature: Some(120.5),
+            position: None,
+        }]);
+        let violations = rule.evaluate(&current, None, &layout());
+
+        assert_eq!(
+            subjects(&violations),
+            vec![
+                AlarmSubject::Fault {
+                    sensor_id: 2,
+                    status: SensorStatus::OutOfRange,
+                },
+                AlarmSubject::Fault {
+                    sensor_id: 4,
+                    status: SensorStatus::Missing,
+                },
+            ]
+        );
+        assert_eq!(violations[0].value, Some(120.5));
+        assert_eq!(violations[1].value, None);
+    }
+}
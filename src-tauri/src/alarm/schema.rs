@@ -0,0 +1,53 @@
+// 告警数据库表结构与升级
+use log::info;
+use rusqlite::Connection;
+
+use super::AlarmError;
+use crate::i18n::tr;
+
+/// 按版本排列的升级脚本，第N条把数据库从版本N升级到N+1
+const MIGRATIONS: &[&str] = &[
+    // 版本1：告警规则与告警记录
+    "CREATE TABLE alarm_rules (
+        id TEXT PRIMARY KEY,
+        definition TEXT NOT NULL
+    );
+    CREATE TABLE alarms (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        rule_id TEXT NOT NULL,
+        rule_name TEXT NOT NULL,
+        severity TEXT NOT NULL,
+        granary TEXT NOT NULL,
+        sub_device_address INTEGER NOT NULL,
+        subject TEXT NOT NULL,
+        value REAL,
+        threshold REAL,
+        reading_id INTEGER NOT NULL,
+        first_triggered_at TEXT NOT NULL,
+        last_triggered_at TEXT NOT NULL,
+        occurrences INTEGER NOT NULL,
+        state TEXT NOT NULL,
+        resolved_at TEXT,
+        acknowledged_by TEXT,
+        acknowledged_at TEXT,
+        cleared_by TEXT,
+        cleared_at TEXT
+    );
+    CREATE INDEX idx_alarms_open ON alarms (rule_id, granary, sub_device_address, state);
+    CREATE INDEX idx_alarms_triggered_at ON alarms (first_triggered_at);",
+];
+
+/// 把数据库升级到最新版本
+///
+/// 版本号保存在 `PRAGMA user_version` 中，每个版本的升级在单独的事务中执行
+pub fn migrate(connection: &mut Connection) -> Result<(), AlarmError> {
+    let current: usize = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    for (version, script) in MIGRATIONS.iter().enumerate().skip(current) {
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(script)?;
+        transaction.pragma_update(None, "user_version", version + 1)?;
+        transaction.commit()?;
+        info!("{}", tr!("alarm.schemaUpgraded", version = version + 1));
+    }
+    Ok(())
+}
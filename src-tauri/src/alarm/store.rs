@@ -0,0 +1,557 @@
+// 告警规则与告警记录的数据库存储
+use chrono::Local;
+use log::{info, warn};
+use rusqlite::types::{Type, Value};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+use super::rule::{AlarmRule, AlarmSubject, Severity, Violation};
+use super::{schema, AlarmError};
+use crate::history::{HistoryReading, TIME_FORMAT};
+use crate::i18n::tr;
+
+/// 查询未指定数量时最多返回的告警数
+const DEFAULT_QUERY_LIMIT: usize = 500;
+
+/// 告警状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AlarmState {
+    /// 未处理
+    Active,
+    /// 已确认，尚未清除
+    Acknowledged,
+    /// 已清除
+    Cleared,
+}
+
+impl AlarmState {
+    /// 数据库中保存的名称，与序列化名称一致
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlarmState::Active => "active",
+            AlarmState::Acknowledged => "acknowledged",
+            AlarmState::Cleared => "cleared",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "acknowledged" => AlarmState::Acknowledged,
+            "cleared" => AlarmState::Cleared,
+            _ => AlarmState::Active,
+        }
+    }
+}
+
+/// 告警记录
+///
+/// 同一规则对同一对象的告警在清除前只保留一条，再次触发时更新最近触发时间和次数
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Alarm {
+    /// 告警编号
+    pub id: i64,
+    /// 触发的规则
+    pub rule_id: String,
+    /// 触发时的规则名称
+    pub rule_name: String,
+    /// 告警级别
+    pub severity: Severity,
+    /// 粮仓
+    pub granary: String,
+    /// 分机地址
+    pub sub_device_address: u8,
+    /// 告警对象
+    pub subject: AlarmSubject,
+    /// 最近一次触发时的值，传感器故障时为异常测温点的温度（如有）
+    pub value: Option<f64>,
+    /// 越过的限值，传感器故障时为null
+    pub threshold: Option<f64>,
+    /// 最近一次触发告警的历史记录编号
+    pub reading_id: i64,
+    /// 首次触发的采样时间
+    pub first_triggered_at: String,
+    /// 最近一次触发的采样时间
+    pub last_triggered_at: String,
+    /// 触发次数
+    pub occurrences: u32,
+    /// 告警状态
+    pub state: AlarmState,
+    /// 之后的读数不再满足告警条件的采样时间，再次触发时重置为null
+    pub resolved_at: Option<String>,
+    /// 确认告警的操作员
+    pub acknowledged_by: Option<String>,
+    /// 确认时间
+    pub acknowledged_at: Option<String>,
+    /// 清除告警的操作员
+    pub cleared_by: Option<String>,
+    /// 清除时间
+    pub cleared_at: Option<String>,
+}
+
+/// 告警查询条件，未设置的条件不参与筛选
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlarmQuery {
+    /// 粮仓
+    pub granary: Option<String>,
+    /// 分机地址
+    pub sub_device_address: Option<u8>,
+    /// 告警状态
+    pub state: Option<AlarmState>,
+    /// 只返回未清除的告警
+    #[serde(default)]
+    pub open_only: bool,
+    /// 最多返回的告警数，默认500
+    pub limit: Option<usize>,
+}
+
+/// 告警数据库
+pub struct AlarmStore {
+    connection: Mutex<Connection>,
+}
+
+impl AlarmStore {
+    /// 打开数据库文件，不存在时创建，并升级到最新表结构
+    pub fn open(path: &Path) -> Result<Self, AlarmError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| AlarmError::Database {
+                message: e.to_string(),
+            })?;
+        }
+        let connection = Connection::open(path)?;
+        info!("{}", tr!("alarm.databaseOpened", path = path.display()));
+        Self::with_connection(connection)
+    }
+
+    /// 打开内存数据库，数据在应用退出后丢失，用于数据库文件无法打开时
+    pub fn open_in_memory() -> Result<Self, AlarmError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(mut connection: Connection) -> Result<Self, AlarmError> {
+        schema::migrate(&mut connection)?;
+        Ok(AlarmStore {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// 读取全部规则，按创建顺序排列，跳过无法解析的规则
+    pub fn load_rules(&self) -> Result<Vec<AlarmRule>, AlarmError> {
+        let connection = self.lock();
+        let mut statement = connection.prepare("SELECT id, definition FROM alarm_rules ORDER BY rowid")?;
+        let rows = statement
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        let rules = rows
+            .into_iter()
+            .filter_map(|(id, definition)| match serde_json::from_str(&definition) {
+                Ok(rule) => Some(rule),
+                Err(e) => {
+                    warn!("{}", tr!("alarm.ruleSkipped", id = id, error = e));
+                    None
+                }
+            })
+            .collect();
+        Ok(rules)
+    }
+
+    /// 新建或更新规则，更新时保持原有顺序
+    pub fn save_rule(&self, rule: &AlarmRule) -> Result<(), AlarmError> {
+        let definition = serde_json::to_string(rule).map_err(|e| AlarmError::Database {
+            message: e.to_string(),
+        })?;
+        self.lock().execute(
+            "INSERT INTO alarm_rules (id, definition) VALUES (?1, ?2)
+             ON CONFLICT (id) DO UPDATE SET definition = excluded.definition",
+            params![rule.id, definition],
+        )?;
+        Ok(())
+    }
+
+    /// 用给定的规则替换全部规则，按给定顺序保存，已产生的告警保留
+    pub fn replace_rules(&self, rules: &[AlarmRule]) -> Result<(), AlarmError> {
+        let mut connection = self.lock();
+        let transaction = connection.transaction()?;
+        transaction.execute("DELETE FROM alarm_rules", [])?;
+        for rule in rules {
+            let definition = serde_json::to_string(rule).map_err(|e| AlarmError::Database {
+                message: e.to_string(),
+            })?;
+            transaction.execute(
+                "INSERT INTO alarm_rules (id, definition) VALUES (?1, ?2)",
+                params![rule.id, definition],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// 删除规则，已产生的告警保留
+    ///
+    /// # 返回值
+    ///
+    /// 规则存在时返回true
+    pub fn delete_rule(&self, id: &str) -> Result<bool, AlarmError> {
+        let deleted = self.lock().execute("DELETE FROM alarm_rules WHERE id = ?1", [id])?;
+        Ok(deleted > 0)
+    }
+
+    /// 按一条规则的判定结果更新告警
+    ///
+    /// 未清除的同一对象告警更新触发时间和次数，其余对象新建告警；
+    /// 本次不再满足条件的未清除告警记录恢复时间
+    ///
+    /// # 参数
+    ///
+    /// * `rule` - 判定使用的规则
+    /// * `reading` - 判定的历史记录
+    /// * `violations` - 满足告警条件的对象
+    ///
+    /// # 返回值
+    ///
+    /// 新建的告警
+    pub fn apply(
+        &self,
+        rule: &AlarmRule,
+        reading: &HistoryReading,
+        violations: &[Violation],
+    ) -> Result<Vec<Alarm>, AlarmError> {
+        let mut connection = self.lock();
+        let transaction = connection.transaction()?;
+        let open: Vec<(i64, String)> = {
+            let mut statement = transaction.prepare_cached(
+                "SELECT id, subject FROM alarms
+                 WHERE rule_id = ?1 AND granary = ?2 AND sub_device_address = ?3 AND state != 'cleared'",
+            )?;
+            let rows = statement
+                .query_map(params![rule.id, reading.granary, reading.sub_device_address], |row| {
+                    Ok((row.get(0)?, row.get(1)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+
+        let mut raised = Vec::new();
+        let mut triggered = Vec::new();
+        for violation in violations {
+            let subject = serde_json::to_string(&violation.subject).map_err(|e| AlarmError::Database {
+                message: e.to_string(),
+            })?;
+            match open.iter().find(|(_, existing)| *existing == subject) {
+                Some((id, _)) => {
+                    transaction.execute(
+                        "UPDATE alarms SET value = ?2, threshold = ?3, reading_id = ?4, last_triggered_at = ?5,
+                            occurrences = occurrences + 1, resolved_at = NULL
+                         WHERE id = ?1",
+                        params![id, violation.value, violation.threshold, reading.id, reading.sampled_at],
+                    )?;
+                    triggered.push(*id);
+                }
+                None => {
+                    transaction.execute(
+                        "INSERT INTO alarms (rule_id, rule_name, severity, granary, sub_device_address, subject,
+                            value, threshold, reading_id, first_triggered_at, last_triggered_at, occurrences, state)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?10, 1, ?11)",
+                        params![
+                            rule.id,
+                            rule.name,
+                            rule.severity.as_str(),
+                            reading.granary,
+                            reading.sub_device_address,
+                            subject,
+                            violation.value,
+                            violation.threshold,
+                            reading.id,
+                            reading.sampled_at,
+                            AlarmState::Active.as_str(),
+                        ],
+                    )?;
+                    raised.push(transaction.last_insert_rowid());
+                }
+            }
+        }
+        for (id, _) in open.iter().filter(|(id, _)| !triggered.contains(id)) {
+            transaction.execute(
+                "UPDATE alarms SET resolved_at = ?2 WHERE id = ?1 AND resolved_at IS NULL",
+                params![id, reading.sampled_at],
+            )?;
+        }
+        transaction.commit()?;
+
+        raised.into_iter().map(|id| load_alarm(&connection, id)).collect()
+    }
+
+    /// 按条件查询告警，按编号从新到旧排列
+    pub fn query(&self, query: &AlarmQuery) -> Result<Vec<Alarm>, AlarmError> {
+        let mut conditions = Vec::new();
+        let mut values: Vec<Value> = Vec::new();
+        if let Some(granary) = &query.granary {
+            conditions.push("granary = ?");
+            values.push(Value::Text(granary.clone()));
+        }
+        if let Some(address) = query.sub_device_address {
+            conditions.push("sub_device_address = ?");
+            values.push(Value::Integer(address.into()));
+        }
+        if let Some(state) = query.state {
+            conditions.push("state = ?");
+            values.push(Value::Text(state.as_str().to_string()));
+        }
+        if query.open_only {
+            conditions.push("state != 'cleared'");
+        }
+        let filter = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let limit = query.limit.unwrap_or(DEFAULT_QUERY_LIMIT);
+        values.push(Value::Integer(i64::try_from(limit).unwrap_or(i64::MAX)));
+
+        let connection = self.lock();
+        let sql = format!("SELECT {} FROM alarms {} ORDER BY id DESC LIMIT ?", ALARM_COLUMNS, filter);
+        let mut statement = connection.prepare(&sql)?;
+        let alarms = statement
+            .query_map(params_from_iter(values), alarm_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(alarms)
+    }
+
+    /// 确认告警，只有未处理的告警可以确认
+    pub fn acknowledge(&self, id: i64, operator: &str) -> Result<Alarm, AlarmError> {
+        let connection = self.lock();
+        let alarm = load_alarm(&connection, id)?;
+        if alarm.state != AlarmState::Active {
+            return Err(AlarmError::InvalidState { id, state: alarm.state });
+        }
+        connection.execute(
+            "UPDATE alarms SET state = ?2, acknowledged_by = ?3, acknowledged_at = ?4 WHERE id = ?1",
+            params![id, AlarmState::Acknowledged.as_str(), operator, now()],
+        )?;
+        load_alarm(&connection, id)
+    }
+
+    /// 清除告警，未处理和已确认的告警都可以清除
+    pub fn clear(&self, id: i64, operator: &str) -> Result<Alarm, AlarmError> {
+        let connection = self.lock();
+        let alarm = load_alarm(&connection, id)?;
+        if alarm.state == AlarmState::Cleared {
+            return Err(AlarmError::InvalidState { id, state: alarm.state });
+        }
+        connection.execute(
+            "UPDATE alarms SET state = ?2, cleared_by = ?3, cleared_at = ?4 WHERE id = ?1",
+            params![id, AlarmState::Cleared.as_str(), operator, now()],
+        )?;
+        load_alarm(&connection, id)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Connection> {
+        match self.connection.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// 查询告警时读取的列，顺序与 `alarm_from_row` 一致
+const ALARM_COLUMNS: &str = "id, rule_id, rule_name, severity, granary, sub_device_address, subject, value, \
+     threshold, reading_id, first_triggered_at, last_triggered_at, occurrences, state, resolved_at, \
+     acknowledged_by, acknowledged_at, cleared_by, cleared_at";
+
+fn alarm_from_row(row: &Row<'_>) -> rusqlite::Result<Alarm> {
+    let subject: String = row.get(6)?;
+    let subject = serde_json::from_str(&subject)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(6, Type::Text, Box::new(e)))?;
+    Ok(Alarm {
+        id: row.get(0)?,
+        rule_id: row.get(1)?,
+        rule_name: row.get(2)?,
+        severity: Severity::parse(&row.get::<_, String>(3)?),
+        granary: row.get(4)?,
+        sub_device_address: row.get(5)?,
+        subject,
+        value: row.get(7)?,
+        threshold: row.get(8)?,
+        reading_id: row.get(9)?,
+        first_triggered_at: row.get(10)?,
+        last_triggered_at: row.get(11)?,
+        occurrences: row.get(12)?,
+        state: AlarmState::parse(&row.get::<_, String>(13)?),
+        resolved_at: row.get(14)?,
+        acknowledged_by: row.get(15)?,
+        acknowledged_at: row.get(16)?,
+        cleared_by: row.get(17)?,
+        cleared_at: row.get(18)?,
+    })
+}
+
+/// 按编号读取一条告警
+fn load_alarm(connection: &Connection, id: i64) -> Result<Alarm, AlarmError> {
+    let sql = format!("SELECT {} FROM alarms WHERE id = ?1", ALARM_COLUMNS);
+    connection
+        .query_row(&sql, [id], alarm_from_row)
+        .optional()?
+        .ok_or(AlarmError::AlarmNotFound { id })
+}
+
+/// 当前时间，格式与历史数据一致
+fn now() -> String {
+    Local::now().format(TIME_FORMAT).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alarm::rule::AlarmCondition;
+    use crate::protocol::EnvironmentData;
+
+    fn rule() -> AlarmRule {
+        AlarmRule {
+            id: "rule-1".to_string(),
+            name: "高温".to_string(),
+            enabled: true,
+            severity: Severity::Critical,
+            granary: None,
+            condition: AlarmCondition::MaxTemperature { limit: 30.0 },
+        }
+    }
+
+    fn reading(id: i64, sampled_at: &str) -> HistoryReading {
+        HistoryReading {
+            id,
+            granary: "1号仓".to_string(),
+            sub_device_address: 1,
+            source: "127.0.0.1:2000".to_string(),
+            sampled_at: sampled_at.to_string(),
+            device_time_valid: true,
+            received_at: sampled_at.to_string(),
+            packet_count: 1,
+            sensor_count: 0,
+            environment: EnvironmentData::default(),
+            sensors: None,
+            faults: None,
+            raw_frame: None,
+        }
+    }
+
+    fn violation(sensor_id: u16, value: f64) -> Violation {
+        Violation {
+            subject: AlarmSubject::Sensor { sensor_id },
+            value: Some(value),
+            threshold: Some(30.0),
+        }
+    }
+
+    fn all_alarms(store: &AlarmStore) -> Vec<Alarm> {
+        store.query(&AlarmQuery::default()).unwrap()
+    }
+
+    #[test]
+    fn updates_open_alarm_instead_of_duplicating() {
+        let store = AlarmStore::open_in_memory().unwrap();
+        let raised = store
+            .apply(&rule(), &reading(1, "2024-05-01 08:00:00"), &[violation(3, 31.0)])
+            .unwrap();
+        assert_eq!(raised.len(), 1);
+        assert_eq!(raised[0].state, AlarmState::Active);
+
+        let raised = store
+            .apply(&rule(), &reading(2, "2024-05-01 09:00:00"), &[violation(3, 32.5)])
+            .unwrap();
+        assert!(raised.is_empty());
+
+        let alarms = all_alarms(&store);
+        assert_eq!(alarms.len(), 1);
+        let alarm = &alarms[0];
+        assert_eq!(alarm.occurrences, 2);
+        assert_eq!(alarm.value, Some(32.5));
+        assert_eq!(alarm.reading_id, 2);
+        assert_eq!(alarm.first_triggered_at, "2024-05-01 08:00:00");
+        assert_eq!(alarm.last_triggered_at, "2024-05-01 09:00:00");
+    }
+
+    #[test]
+    fn records_resolution_when_condition_clears() {
+        let store = AlarmStore::open_in_memory().unwrap();
+        store
+            .apply(&rule(), &reading(1, "2024-05-01 08:00:00"), &[violation(3, 31.0)])
+            .unwrap();
+        store.apply(&rule(), &reading(2, "2024-05-01 09:00:00"), &[]).unwrap();
+        // 之后仍不满足条件时保留第一次恢复的时间
+        store.apply(&rule(), &reading(3, "2024-05-01 10:00:00"), &[]).unwrap();
+        assert_eq!(all_alarms(&store)[0].resolved_at.as_deref(), Some("2024-05-01 09:00:00"));
+
+        // 清除前再次触发时沿用原告警并重置恢复时间
+        let raised = store
+            .apply(&rule(), &reading(4, "2024-05-01 11:00:00"), &[violation(3, 31.5)])
+            .unwrap();
+        assert!(raised.is_empty());
+        let alarms = all_alarms(&store);
+        assert_eq!(alarms.len(), 1);
+        assert_eq!(alarms[0].resolved_at, None);
+        assert_eq!(alarms[0].occurrences, 2);
+    }
+
+    #[test]
+    fn raises_new_alarm_after_clear() {
+        let store = AlarmStore::open_in_memory().unwrap();
+        let raised = store
+            .apply(&rule(), &reading(1, "2024-05-01 08:00:00"), &[violation(3, 31.0)])
+            .unwrap();
+        let cleared = store.clear(raised[0].id, "张三").unwrap();
+        assert_eq!(cleared.state, AlarmState::Cleared);
+        assert_eq!(cleared.cleared_by.as_deref(), Some("张三"));
+
+        let raised = store
+            .apply(&rule(), &reading(2, "2024-05-01 09:00:00"), &[violation(3, 31.0)])
+            .unwrap();
+        assert_eq!(raised.len(), 1);
+        assert_ne!(raised[0].id, cleared.id);
+        assert_eq!(raised[0].occurrences, 1);
+
+        let open = store
+            .query(&AlarmQuery {
+                open_only: true,
+                ..AlarmQuery::default()
+            })
+            .unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].id, raised[0].id);
+    }
+
+    #[test]
+    fn acknowledges_only_active_alarms() {
+        let store = AlarmStore::open_in_memory().unwrap();
+        let raised = store
+            .apply(&rule(), &reading(1, "2024-05-01 08:00:00"), &[violation(3, 31.0)])
+            .unwrap();
+        let id = raised[0].id;
+
+        let alarm = store.acknowledge(id, "张三").unwrap();
+        assert_eq!(alarm.state, AlarmState::Acknowledged);
+        assert_eq!(alarm.acknowledged_by.as_deref(), Some("张三"));
+        assert!(matches!(
+            store.acknowledge(id, "李四"),
+            Err(AlarmError::InvalidState {
+                state: AlarmState::Acknowledged,
+                ..
+            })
+        ));
+
+        store.clear(id, "张三").unwrap();
+        assert!(matches!(
+            store.acknowledge(id, "李四"),
+            Err(AlarmError::InvalidState {
+                state: AlarmState::Cleared,
+                ..
+            })
+        ));
+        assert!(matches!(store.clear(id, "李四"), Err(AlarmError::InvalidState { .. })));
+        assert!(matches!(store.acknowledge(id + 1, "李四"), Err(AlarmError::AlarmNotFound { .. })));
+    }
+}
@@ -0,0 +1,37 @@
+// 粮情分析错误定义
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::history::HistoryError;
+use crate::i18n::tr;
+
+/// 粮情分析失败的原因
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum AnalysisError {
+    /// 未指定要分析的粮仓
+    EmptyGranary,
+    /// 升温速率上限不是正数
+    InvalidRiseLimit { value: f64 },
+    /// 读取历史数据失败
+    History { error: HistoryError },
+}
+
+impl fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            AnalysisError::EmptyGranary => tr!("analysis.emptyGranary"),
+            AnalysisError::InvalidRiseLimit { value } => tr!("analysis.invalidRiseLimit", value = value),
+            AnalysisError::History { error } => error.to_string(),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for AnalysisError {}
+
+impl From<HistoryError> for AnalysisError {
+    fn from(error: HistoryError) -> Self {
+        AnalysisError::History { error }
+    }
+}
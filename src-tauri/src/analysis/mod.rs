@@ -0,0 +1,9 @@
+// 粮情分析模块，基于历史数据分析测温点的温度趋势
+
+// 导出错误定义
+pub mod error;
+// 导出趋势分析模块
+pub mod trend;
+
+pub use error::AnalysisError;
+pub use trend::{TrendQuery, TrendReport};
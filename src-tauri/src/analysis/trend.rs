@@ -0,0 +1,425 @@
+// 测温点温度趋势与升温速率分析
+use chrono::{Duration, Local, NaiveDateTime};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::AnalysisError;
+use crate::history::{HistoryQuery, HistoryReading, HistoryStore, TIME_FORMAT};
+use crate::i18n::tr;
+use crate::layout::{GranaryLayout, SensorPosition};
+
+/// 默认的升温速率上限（°C/天）
+pub const DEFAULT_RISE_LIMIT_PER_DAY: f64 = 1.0;
+/// 未指定起始时间时分析最近的天数
+const DEFAULT_TREND_DAYS: i64 = 7;
+/// 计算升温速率要求的最短时间跨度（小时），跨度过短时读数波动会被放大
+const MIN_TREND_SPAN_HOURS: i64 = 12;
+/// 单次分析最多读取的记录数
+const MAX_TREND_READINGS: usize = 10_000;
+
+/// 趋势分析条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendQuery {
+    /// 粮仓
+    pub granary: String,
+    /// 分机地址，未设置时分析粮仓下的全部分机
+    pub sub_device_address: Option<u8>,
+    /// 起始时间（含），默认为7天前
+    pub from: Option<String>,
+    /// 结束时间（不含），默认为不限
+    pub to: Option<String>,
+    /// 升温速率上限（°C/天），超过时标记该测温点，默认1.0
+    pub rise_limit_per_day: Option<f64>,
+}
+
+/// 单个测温点的温度趋势
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SensorTrend {
+    /// 分机地址
+    pub sub_device_address: u8,
+    /// 测温点编号
+    pub sensor_id: u16,
+    /// 按粮仓测温点布局确定的坐标，编号超出布局时为null
+    pub position: Option<SensorPosition>,
+    /// 参与分析的读数数量
+    pub sample_count: usize,
+    /// 最早的温度
+    pub first_temperature: f64,
+    /// 最近的温度
+    pub latest_temperature: f64,
+    /// 最近一次读数的采样时间
+    pub latest_sampled_at: String,
+    /// 最低温度
+    pub min_temperature: f64,
+    /// 最高温度
+    pub max_temperature: f64,
+    /// 升温速率（°C/天），按最小二乘拟合，时间跨度不足12小时时为null
+    pub rise_per_day: Option<f64>,
+    /// 日环比（°C），最近温度减去24小时前的温度，没有足够早的读数时为null
+    pub day_over_day: Option<f64>,
+    /// 升温速率是否超过上限
+    pub rising_too_fast: bool,
+}
+
+/// 一组测温点（一层或整仓）的趋势汇总
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupTrend {
+    /// 测温点数量
+    pub sensor_count: usize,
+    /// 最近温度的平均值
+    pub average_temperature: Option<f64>,
+    /// 最近温度的最高值
+    pub max_temperature: Option<f64>,
+    /// 升温速率的平均值（°C/天）
+    pub average_rise_per_day: Option<f64>,
+    /// 升温速率的最高值（°C/天）
+    pub max_rise_per_day: Option<f64>,
+    /// 日环比的平均值（°C）
+    pub average_day_over_day: Option<f64>,
+    /// 升温过快的测温点数量
+    pub flagged_count: usize,
+}
+
+/// 单层的趋势汇总
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayerTrend {
+    /// 层号，从1开始
+    pub layer: u16,
+    #[serde(flatten)]
+    pub trend: GroupTrend,
+}
+
+/// 趋势分析结果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendReport {
+    /// 粮仓
+    pub granary: String,
+    /// 参与分析的记录数量
+    pub reading_count: usize,
+    /// 最早的采样时间
+    pub first_sampled_at: Option<String>,
+    /// 最近的采样时间
+    pub last_sampled_at: Option<String>,
+    /// 使用的升温速率上限（°C/天）
+    pub rise_limit_per_day: f64,
+    /// 整仓汇总
+    pub overall: GroupTrend,
+    /// 按层汇总，不含编号超出布局的测温点
+    pub layers: Vec<LayerTrend>,
+    /// 各测温点趋势，按分机地址和测温点编号排列
+    pub sensors: Vec<SensorTrend>,
+    /// 升温过快的测温点，按升温速率从高到低排列
+    pub flagged: Vec<SensorTrend>,
+}
+
+/// 从历史数据库读取粮仓的读数并分析趋势
+///
+/// # 参数
+///
+/// * `history` - 历史数据库
+/// * `layout` - 粮仓的测温点布局，用于确定测温点所在的层
+/// * `query` - 分析条件
+pub fn analyze(
+    history: &HistoryStore,
+    layout: &GranaryLayout,
+    query: &TrendQuery,
+) -> Result<TrendReport, AnalysisError> {
+    let granary = query.granary.trim();
+    if granary.is_empty() {
+        return Err(AnalysisError::EmptyGranary);
+    }
+    let rise_limit = query.rise_limit_per_day.unwrap_or(DEFAULT_RISE_LIMIT_PER_DAY);
+    if !rise_limit.is_finite() || rise_limit <= 0.0 {
+        return Err(AnalysisError::InvalidRiseLimit { value: rise_limit });
+    }
+
+    let from = query.from.clone().unwrap_or_else(|| {
+        (Local::now().naive_local() - Duration::days(DEFAULT_TREND_DAYS))
+            .format(TIME_FORMAT)
+            .to_string()
+    });
+    let mut readings = history.query(&HistoryQuery {
+        granary: Some(granary.to_string()),
+        sub_device_address: query.sub_device_address,
+        from: Some(from),
+        to: query.to.clone(),
+        limit: Some(MAX_TREND_READINGS),
+        include_sensors: true,
+    })?;
+    if readings.len() == MAX_TREND_READINGS {
+        warn!("{}", tr!("analysis.truncated", granary = granary, limit = MAX_TREND_READINGS));
+    }
+    // 查询结果从新到旧排列，分析时按时间顺序处理
+    readings.reverse();
+
+    let report = build_report(granary, &readings, rise_limit, layout);
+    debug!(
+        "{}",
+        tr!(
+            "analysis.summary",
+            granary = granary,
+            readings = report.reading_count,
+            sensors = report.sensors.len(),
+            flagged = report.flagged.len()
+        )
+    );
+    Ok(report)
+}
+
+/// 按时间顺序排列的读数生成趋势分析结果
+///
+/// # 参数
+///
+/// * `granary` - 粮仓
+/// * `readings` - 带测温点读数的历史记录，按采样时间从旧到新排列
+/// * `rise_limit` - 升温速率上限（°C/天）
+/// * `layout` - 粮仓的测温点布局
+pub fn build_report(
+    granary: &str,
+    readings: &[HistoryReading],
+    rise_limit: f64,
+    layout: &GranaryLayout,
+) -> TrendReport {
+    // 按分机和测温点归集时间序列，同一测温点在不同分机上编号可能相同
+    let mut series: BTreeMap<(u8, u16), Vec<(NaiveDateTime, f64)>> = BTreeMap::new();
+    for reading in readings {
+        let Ok(time) = NaiveDateTime::parse_from_str(&reading.sampled_at, TIME_FORMAT) else {
+            warn!(
+                "{}",
+                tr!("analysis.invalidSampleTime", id = reading.id, time = reading.sampled_at)
+            );
+            continue;
+        };
+        for sensor in reading.sensors.iter().flatten() {
+            series
+                .entry((reading.sub_device_address, sensor.sensor_id))
+                .or_default()
+                .push((time, sensor.temperature));
+        }
+    }
+
+    let map = layout.sensor_map();
+    let sensors: Vec<SensorTrend> = series
+        .into_iter()
+        .filter_map(|((address, sensor_id), samples)| {
+            sensor_trend(address, sensor_id, map.get(sensor_id), &samples, rise_limit)
+        })
+        .collect();
+
+    let mut layers: BTreeMap<u16, Vec<&SensorTrend>> = BTreeMap::new();
+    for sensor in &sensors {
+        if let Some(position) = sensor.position {
+            layers.entry(position.layer).or_default().push(sensor);
+        }
+    }
+    let layers = layers
+        .into_iter()
+        .map(|(layer, members)| LayerTrend {
+            layer,
+            trend: group_trend(&members),
+        })
+        .collect();
+
+    let mut flagged: Vec<SensorTrend> = sensors.iter().filter(|sensor| sensor.rising_too_fast).cloned().collect();
+    flagged.sort_by(|a, b| b.rise_per_day.unwrap_or_default().total_cmp(&a.rise_per_day.unwrap_or_default()));
+
+    TrendReport {
+        granary: granary.to_string(),
+        reading_count: readings.len(),
+        first_sampled_at: readings.first().map(|reading| reading.sampled_at.clone()),
+        last_sampled_at: readings.last().map(|reading| reading.sampled_at.clone()),
+        rise_limit_per_day: rise_limit,
+        overall: group_trend(&sensors.iter().collect::<Vec<_>>()),
+        layers,
+        sensors,
+        flagged,
+    }
+}
+
+/// 计算单个测温点的趋势，`samples` 按时间顺序排列
+fn sensor_trend(
+    sub_device_address: u8,
+    sensor_id: u16,
+    position: Option<SensorPosition>,
+    samples: &[(NaiveDateTime, f64)],
+    rise_limit: f64,
+) -> Option<SensorTrend> {
+    let &(first_time, first_temperature) = samples.first()?;
+    let &(latest_time, latest_temperature) = samples.last()?;
+    let temperatures = samples.iter().map(|&(_, temperature)| temperature);
+    let min_temperature = temperatures.clone().fold(f64::INFINITY, f64::min);
+    let max_temperature = temperatures.fold(f64::NEG_INFINITY, f64::max);
+
+    let rise_per_day = if latest_time - first_time >= Duration::hours(MIN_TREND_SPAN_HOURS) {
+        slope_per_day(samples)
+    } else {
+        None
+    };
+    let day_before = latest_time - Duration::days(1);
+    let day_over_day = samples
+        .iter()
+        .rev()
+        .find(|&&(time, _)| time <= day_before)
+        .map(|&(_, temperature)| latest_temperature - temperature);
+
+    Some(SensorTrend {
+        sub_device_address,
+        sensor_id,
+        position,
+        sample_count: samples.len(),
+        first_temperature,
+        latest_temperature,
+        latest_sampled_at: latest_time.format(TIME_FORMAT).to_string(),
+        min_temperature,
+        max_temperature,
+        rise_per_day,
+        day_over_day,
+        rising_too_fast: rise_per_day.is_some_and(|rise| rise > rise_limit),
+    })
+}
+
+/// 按最小二乘法拟合温度随时间的变化，返回每天的变化量
+fn slope_per_day(samples: &[(NaiveDateTime, f64)]) -> Option<f64> {
+    let (origin, _) = *samples.first()?;
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|&(time, temperature)| ((time - origin).num_seconds() as f64 / 86_400.0, temperature))
+        .collect();
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|&(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|&(_, y)| y).sum::<f64>() / count;
+    let covariance: f64 = points.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = points.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum();
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/// 汇总一组测温点的趋势
+fn group_trend(sensors: &[&SensorTrend]) -> GroupTrend {
+    let rises: Vec<f64> = sensors.iter().filter_map(|sensor| sensor.rise_per_day).collect();
+    let deltas: Vec<f64> = sensors.iter().filter_map(|sensor| sensor.day_over_day).collect();
+    let latest: Vec<f64> = sensors.iter().map(|sensor| sensor.latest_temperature).collect();
+    GroupTrend {
+        sensor_count: sensors.len(),
+        average_temperature: average(&latest),
+        max_temperature: latest.iter().copied().reduce(f64::max),
+        average_rise_per_day: average(&rises),
+        max_rise_per_day: rises.iter().copied().reduce(f64::max),
+        average_day_over_day: average(&deltas),
+        flagged_count: sensors.iter().filter(|sensor| sensor.rising_too_fast).count(),
+    }
+}
+
+fn average(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::model::WiringOrder;
+    use crate::protocol::{EnvironmentData, SensorReading};
+
+    fn time(hours: i64) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2024-05-01 08:00:00", TIME_FORMAT).unwrap() + Duration::hours(hours)
+    }
+
+    fn reading(id: i64, hours: i64, temperatures: &[(u16, f64)]) -> HistoryReading {
+        HistoryReading {
+            id,
+            granary: "1号仓".to_string(),
+            sub_device_address: 1,
+            source: "127.0.0.1:2000".to_string(),
+            sampled_at: time(hours).format(TIME_FORMAT).to_string(),
+            device_time_valid: true,
+            received_at: time(hours).format(TIME_FORMAT).to_string(),
+            packet_count: 1,
+            sensor_count: temperatures.len(),
+            environment: EnvironmentData::default(),
+            sensors: Some(
+                temperatures
+                    .iter()
+                    .map(|&(sensor_id, temperature)| SensorReading {
+                        sensor_id,
+                        temperature,
+                        position: None,
+                    })
+                    .collect(),
+            ),
+            faults: Some(Vec::new()),
+            raw_frame: None,
+        }
+    }
+
+    #[test]
+    fn fits_least_squares_slope() {
+        let samples = [(time(0), 10.0), (time(24), 11.0), (time(48), 13.0)];
+        assert_eq!(slope_per_day(&samples), Some(1.5));
+        // 只有一个时间点时无法拟合
+        assert_eq!(slope_per_day(&[(time(0), 10.0), (time(0), 12.0)]), None);
+    }
+
+    #[test]
+    fn needs_minimum_span_for_rise() {
+        let short = [(time(0), 10.0), (time(MIN_TREND_SPAN_HOURS - 1), 12.0)];
+        let trend = sensor_trend(1, 1, None, &short, 1.0).unwrap();
+        assert_eq!(trend.rise_per_day, None);
+        assert!(!trend.rising_too_fast);
+
+        let long = [(time(0), 10.0), (time(MIN_TREND_SPAN_HOURS), 12.0)];
+        let trend = sensor_trend(1, 1, None, &long, 1.0).unwrap();
+        assert_eq!(trend.rise_per_day, Some(4.0));
+        assert!(trend.rising_too_fast);
+    }
+
+    #[test]
+    fn compares_with_last_sample_a_day_before() {
+        let samples = [(time(0), 10.0), (time(20), 11.0), (time(24), 11.5), (time(30), 12.0), (time(48), 15.0)];
+        let trend = sensor_trend(1, 1, None, &samples, 1.0).unwrap();
+        assert_eq!(trend.day_over_day, Some(3.5));
+        assert_eq!((trend.min_temperature, trend.max_temperature), (10.0, 15.0));
+
+        let recent = [(time(0), 10.0), (time(23), 11.0)];
+        assert_eq!(sensor_trend(1, 1, None, &recent, 1.0).unwrap().day_over_day, None);
+    }
+
+    #[test]
+    fn groups_by_layer_and_orders_flagged_sensors() {
+        // 1行1列2层的布局，测温点3超出布局
+        let layout = GranaryLayout {
+            cable_rows: 1,
+            cable_columns: 1,
+            points_per_cable: 2,
+            wiring: WiringOrder::ByLayer,
+            skipped: Vec::new(),
+        };
+        let readings = [
+            reading(1, 0, &[(1, 10.0), (2, 10.0), (3, 10.0)]),
+            reading(2, 24, &[(1, 10.5), (2, 13.0), (3, 14.0)]),
+            reading(3, 48, &[(1, 11.0), (2, 16.0), (3, 18.0)]),
+        ];
+
+        let report = build_report("1号仓", &readings, 1.0, &layout);
+
+        assert_eq!(report.reading_count, 3);
+        assert_eq!(report.first_sampled_at.as_deref(), Some("2024-05-01 08:00:00"));
+        assert_eq!(report.sensors.len(), 3);
+        assert_eq!(report.overall.sensor_count, 3);
+        assert_eq!(report.overall.flagged_count, 2);
+        let flagged: Vec<u16> = report.flagged.iter().map(|sensor| sensor.sensor_id).collect();
+        // 超出布局的测温点不计入各层，但仍按升温速率从高到低列出
+        assert_eq!(flagged, vec![3, 2]);
+
+        assert_eq!(report.layers.len(), 2);
+        assert_eq!(report.layers[0].layer, 1);
+        assert_eq!(report.layers[0].trend.sensor_count, 1);
+        assert_eq!(report.layers[0].trend.max_rise_per_day, Some(0.5));
+        assert_eq!(report.layers[1].trend.sensor_count, 1);
+        assert_eq!(report.layers[1].trend.average_temperature, Some(16.0));
+    }
+}
@@ -0,0 +1,185 @@
+// 配置包与当前配置的差异比较
+use serde::Serialize;
+use serde_json::Value;
+
+use super::BundleContent;
+
+/// 差异所在的分组
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BundleSection {
+    /// 网络通信默认参数
+    Network,
+    /// 界面显示设置
+    Display,
+    /// 测温点布局，`default` 为默认布局，其余为单独设置布局的粮仓
+    Layouts,
+    /// 库点
+    Depots,
+    /// 仓房
+    Warehouses,
+    /// 采集器
+    Collectors,
+    /// 告警规则
+    AlarmRules,
+    /// 定时轮询任务
+    PollJobs,
+}
+
+/// 条目的变化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    /// 导入后新增
+    Added,
+    /// 导入后内容变化
+    Changed,
+    /// 导入后删除
+    Removed,
+}
+
+/// 导入配置包时一个条目的变化
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleChange {
+    /// 所在分组
+    pub section: BundleSection,
+    /// 条目标识
+    pub key: String,
+    /// 条目名称，没有名称的条目为None
+    pub name: Option<String>,
+    /// 变化
+    pub change: ChangeKind,
+    /// 内容变化的字段，只用于 `Changed`
+    pub fields: Vec<String>,
+}
+
+/// 比较当前配置和配置包，列出导入后新增、变化和删除的条目
+///
+/// # 参数
+///
+/// * `current` - 当前配置
+/// * `incoming` - 配置包中的配置
+///
+/// # 返回值
+///
+/// 按分组排列的变化，同一分组中新增和变化的条目按配置包中的顺序，删除的条目按当前配置中的顺序
+pub fn compare(current: &BundleContent, incoming: &BundleContent) -> Vec<BundleChange> {
+    let mut changes = Vec::new();
+    compare_entries(
+        BundleSection::Network,
+        vec![entry("network", &current.config.network)],
+        vec![entry("network", &incoming.config.network)],
+        &mut changes,
+    );
+    compare_entries(
+        BundleSection::Display,
+        vec![entry("display", &current.config.display)],
+        vec![entry("display", &incoming.config.display)],
+        &mut changes,
+    );
+    compare_entries(BundleSection::Layouts, layouts(current), layouts(incoming), &mut changes);
+
+    let (current_inventory, incoming_inventory) = (&current.config.inventory, &incoming.config.inventory);
+    compare_entries(
+        BundleSection::Depots,
+        entries(&current_inventory.depots, |depot| &depot.id),
+        entries(&incoming_inventory.depots, |depot| &depot.id),
+        &mut changes,
+    );
+    compare_entries(
+        BundleSection::Warehouses,
+        entries(&current_inventory.warehouses, |warehouse| &warehouse.id),
+        entries(&incoming_inventory.warehouses, |warehouse| &warehouse.id),
+        &mut changes,
+    );
+    compare_entries(
+        BundleSection::Collectors,
+        entries(&current_inventory.collectors, |collector| &collector.id),
+        entries(&incoming_inventory.collectors, |collector| &collector.id),
+        &mut changes,
+    );
+    compare_entries(
+        BundleSection::AlarmRules,
+        entries(&current.alarm_rules, |rule| &rule.id),
+        entries(&incoming.alarm_rules, |rule| &rule.id),
+        &mut changes,
+    );
+    compare_entries(
+        BundleSection::PollJobs,
+        entries(&current.poll_jobs, |job| &job.id),
+        entries(&incoming.poll_jobs, |job| &job.id),
+        &mut changes,
+    );
+    changes
+}
+
+/// 默认布局和单独设置布局的粮仓
+fn layouts(content: &BundleContent) -> Vec<(String, Value)> {
+    let layouts = &content.config.layouts;
+    let mut entries = vec![entry("default", &layouts.default)];
+    entries.extend(layouts.granaries.iter().map(|(granary, layout)| entry(granary, layout)));
+    entries
+}
+
+/// 按标识转换为可比较的内容
+fn entries<T: Serialize>(items: &[T], key: impl Fn(&T) -> &String) -> Vec<(String, Value)> {
+    items.iter().map(|item| entry(key(item), item)).collect()
+}
+
+fn entry<T: Serialize>(key: &str, item: &T) -> (String, Value) {
+    (key.to_string(), serde_json::to_value(item).unwrap_or(Value::Null))
+}
+
+/// 比较同一分组中的条目
+fn compare_entries(
+    section: BundleSection,
+    current: Vec<(String, Value)>,
+    incoming: Vec<(String, Value)>,
+    changes: &mut Vec<BundleChange>,
+) {
+    for (key, value) in &incoming {
+        let (change, fields) = match current.iter().find(|(existing, _)| existing == key) {
+            None => (ChangeKind::Added, Vec::new()),
+            Some((_, existing)) if existing != value => (ChangeKind::Changed, changed_fields(existing, value)),
+            Some(_) => continue,
+        };
+        changes.push(BundleChange {
+            section,
+            key: key.clone(),
+            name: name(value),
+            change,
+            fields,
+        });
+    }
+    for (key, value) in &current {
+        if !incoming.iter().any(|(other, _)| other == key) {
+            changes.push(BundleChange {
+                section,
+                key: key.clone(),
+                name: name(value),
+                change: ChangeKind::Removed,
+                fields: Vec::new(),
+            });
+        }
+    }
+}
+
+/// 两个对象中值不同的字段，按字段名排列
+fn changed_fields(before: &Value, after: &Value) -> Vec<String> {
+    let (Value::Object(before), Value::Object(after)) = (before, after) else {
+        return Vec::new();
+    };
+    let mut fields: Vec<String> = before
+        .keys()
+        .chain(after.keys().filter(|key| !before.contains_key(*key)))
+        .filter(|key| before.get(*key) != after.get(*key))
+        .cloned()
+        .collect();
+    fields.sort();
+    fields
+}
+
+fn name(value: &Value) -> Option<String> {
+    value.get("name").and_then(Value::as_str).map(str::to_string)
+}
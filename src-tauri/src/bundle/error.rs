@@ -0,0 +1,68 @@
+// 配置包错误定义
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::alarm::AlarmError;
+use crate::config::ConfigError;
+use crate::i18n::tr;
+use crate::scheduler::SchedulerError;
+
+/// 导出或导入配置包失败的原因
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum BundleError {
+    /// 文件不是配置包或内容字段类型错误
+    InvalidFile { message: String },
+    /// 文件格式标识不是配置包
+    UnknownFormat { format: String },
+    /// 校验和与内容不符，文件已损坏或被修改
+    ChecksumMismatch { expected: String, actual: String },
+    /// 同一分组中有重复的标识
+    DuplicateId { id: String },
+    /// 应用配置无效
+    Config { error: ConfigError },
+    /// 告警规则无效或保存失败
+    Alarm { error: AlarmError },
+    /// 定时轮询任务无效或保存失败
+    Scheduler { error: SchedulerError },
+    /// 读写配置包文件失败
+    Storage { path: String, message: String },
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            BundleError::InvalidFile { message } => tr!("bundle.invalidFile", message = message),
+            BundleError::UnknownFormat { format } => tr!("bundle.unknownFormat", format = format),
+            BundleError::ChecksumMismatch { expected, actual } => {
+                tr!("bundle.checksumMismatch", expected = expected, actual = actual)
+            }
+            BundleError::DuplicateId { id } => tr!("bundle.duplicateId", id = id),
+            BundleError::Config { error } => error.to_string(),
+            BundleError::Alarm { error } => error.to_string(),
+            BundleError::Scheduler { error } => error.to_string(),
+            BundleError::Storage { path, message } => tr!("bundle.storage", path = path, error = message),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+impl From<ConfigError> for BundleError {
+    fn from(error: ConfigError) -> Self {
+        BundleError::Config { error }
+    }
+}
+
+impl From<AlarmError> for BundleError {
+    fn from(error: AlarmError) -> Self {
+        BundleError::Alarm { error }
+    }
+}
+
+impl From<SchedulerError> for BundleError {
+    fn from(error: SchedulerError) -> Self {
+        BundleError::Scheduler { error }
+    }
+}
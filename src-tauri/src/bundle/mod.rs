@@ -0,0 +1,327 @@
+// 配置包模块，把应用配置、告警规则和定时轮询任务导出到一个带校验和的文件，在其他工作站导入前可预览差异
+use chrono::Local;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::alarm::{AlarmEngine, AlarmRule};
+use crate::config::{AppConfig, ConfigStore};
+use crate::i18n::tr;
+use crate::scheduler::{PollJob, Scheduler};
+
+// 导出差异比较模块
+pub mod diff;
+// 导出错误定义
+pub mod error;
+
+pub use diff::BundleChange;
+pub use error::BundleError;
+
+/// 配置包文件的格式标识
+const BUNDLE_FORMAT: &str = "grain-resolve-config";
+/// 校验和算法前缀
+const CHECKSUM_PREFIX: &str = "sha256:";
+
+/// 配置包中的内容
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleContent {
+    /// 应用配置，包括网络参数、测温点布局、界面显示设置和站点台账
+    pub config: AppConfig,
+    /// 告警规则
+    pub alarm_rules: Vec<AlarmRule>,
+    /// 定时轮询任务
+    pub poll_jobs: Vec<PollJob>,
+}
+
+/// 配置包文件
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BundleFile {
+    /// 格式标识
+    format: String,
+    /// 导出时间（RFC 3339）
+    exported_at: String,
+    /// 内容的校验和，按键名排序后的紧凑JSON计算SHA-256
+    checksum: String,
+    /// 内容，应用配置可以是旧版本，导入时升级
+    content: Value,
+}
+
+/// 配置包内容中各部分的原始格式
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawContent {
+    config: Value,
+    #[serde(default)]
+    alarm_rules: Vec<AlarmRule>,
+    #[serde(default)]
+    poll_jobs: Vec<PollJob>,
+}
+
+/// 配置包的导出时间和校验和
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleInfo {
+    /// 导出时间（RFC 3339）
+    pub exported_at: String,
+    /// 校验和
+    pub checksum: String,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    #[serde(flatten)]
+    pub info: BundleInfo,
+    /// 导入后新增、变化和删除的条目
+    pub changes: Vec<BundleChange>,
+    /// 是否已导入，只预览时为false
+    pub applied: bool,
+}
+
+/// 当前的应用配置、告警规则和定时轮询任务
+pub fn current(config: &ConfigStore, alarms: &AlarmEngine, scheduler: &Scheduler) -> BundleContent {
+    BundleContent {
+        config: config.get(),
+        alarm_rules: alarms.rules(),
+        poll_jobs: scheduler.jobs().into_iter().map(|status| status.job).collect(),
+    }
+}
+
+/// 把当前配置导出到配置包文件
+///
+/// # 参数
+///
+/// * `path` - 配置包文件路径，已存在时覆盖
+/// * `content` - 导出的内容
+pub fn export(path: &Path, content: &BundleContent) -> Result<BundleInfo, BundleError> {
+    let value = serde_json::to_value(content).map_err(|e| storage_error(path, e))?;
+    let info = BundleInfo {
+        exported_at: Local::now().to_rfc3339(),
+        checksum: checksum(&value),
+    };
+    let file = BundleFile {
+        format: BUNDLE_FORMAT.to_string(),
+        exported_at: info.exported_at.clone(),
+        checksum: info.checksum.clone(),
+        content: value,
+    };
+    let text = serde_json::to_string_pretty(&file).map_err(|e| storage_error(path, e))?;
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, text).map_err(|e| storage_error(&temp, e))?;
+    fs::rename(&temp, path).map_err(|e| storage_error(path, e))?;
+    info!("{}", tr!("bundle.written", path = path.display(), checksum = info.checksum));
+    Ok(info)
+}
+
+/// 读取配置包文件，校验格式和校验和，应用配置升级到当前版本，并校验告警规则和定时轮询任务
+pub fn read(path: &Path) -> Result<(BundleInfo, BundleContent), BundleError> {
+    let text = fs::read_to_string(path).map_err(|e| storage_error(path, e))?;
+    let file: BundleFile = serde_json::from_str(&text).map_err(invalid_file)?;
+    if file.format != BUNDLE_FORMAT {
+        return Err(BundleError::UnknownFormat { format: file.format });
+    }
+    let actual = checksum(&file.content);
+    if actual != file.checksum {
+        return Err(BundleError::ChecksumMismatch {
+            expected: file.checksum,
+            actual,
+        });
+    }
+
+    let raw: RawContent = serde_json::from_value(file.content).map_err(invalid_file)?;
+    let content = BundleContent {
+        config: ConfigStore::validate(raw.config)?,
+        alarm_rules: raw.alarm_rules,
+        poll_jobs: raw.poll_jobs,
+    };
+    check_unique(content.alarm_rules.iter().map(|rule| &rule.id))?;
+    check_unique(content.poll_jobs.iter().map(|job| &job.id))?;
+    for rule in &content.alarm_rules {
+        rule.validate()?;
+    }
+    for job in &content.poll_jobs {
+        job.validate()?;
+    }
+    Ok((
+        BundleInfo {
+            exported_at: file.exported_at,
+            checksum: file.checksum,
+        },
+        content,
+    ))
+}
+
+/// 导入配置包，替换全部应用配置、告警规则和定时轮询任务
+///
+/// 导入前已校验全部内容，依次替换应用配置、告警规则和定时轮询任务，任一部分保存失败时把已替换的部分恢复为导入前的内容
+///
+/// # 参数
+///
+/// * `path` - 配置包文件路径
+/// * `dry_run` - 为true时只比较差异，不导入
+pub fn import(
+    path: &Path,
+    config: &ConfigStore,
+    alarms: &AlarmEngine,
+    scheduler: &Scheduler,
+    dry_run: bool,
+) -> Result<ImportReport, BundleError> {
+    let (info, content) = read(path)?;
+    let previous = current(config, alarms, scheduler);
+    let changes = diff::compare(&previous, &content);
+    if !dry_run {
+        apply(config, alarms, scheduler, content, previous)?;
+        info!("{}", tr!("bundle.applied", path = path.display(), count = changes.len()));
+    }
+    Ok(ImportReport {
+        info,
+        changes,
+        applied: !dry_run,
+    })
+}
+
+/// 依次替换应用配置、告警规则和定时轮询任务，失败时恢复已替换的部分
+fn apply(
+    config: &ConfigStore,
+    alarms: &AlarmEngine,
+    scheduler: &Scheduler,
+    content: BundleContent,
+    previous: BundleContent,
+) -> Result<(), BundleError> {
+    set_config(config, &content.config)?;
+    if let Err(e) = alarms.replace_rules(content.alarm_rules) {
+        warn!("{}", tr!("bundle.rollingBack", error = e));
+        restore(set_config(config, &previous.config));
+        return Err(e.into());
+    }
+    if let Err(e) = scheduler.replace_jobs(content.poll_jobs) {
+        warn!("{}", tr!("bundle.rollingBack", error = e));
+        restore(set_config(config, &previous.config));
+        restore(alarms.replace_rules(previous.alarm_rules).map_err(BundleError::from));
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// 保存应用配置
+fn set_config(config: &ConfigStore, content: &AppConfig) -> Result<(), BundleError> {
+    let document = serde_json::to_value(content).map_err(invalid_file)?;
+    config.set(document)?;
+    Ok(())
+}
+
+/// 恢复导入前的内容失败时只记录日志
+fn restore<T>(result: Result<T, BundleError>) {
+    if let Err(e) = result {
+        error!("{}", tr!("bundle.rollbackFailed", error = e));
+    }
+}
+
+/// 计算内容的校验和，对象按键名排序后序列化，与JSON库是否保留键的顺序无关
+fn checksum(content: &Value) -> String {
+    let bytes = serde_json::to_vec(&canonical(content)).unwrap_or_default();
+    let digest = Sha256::digest(&bytes);
+    let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}{}", CHECKSUM_PREFIX, hex)
+}
+
+/// 对象的键按名称排序
+fn canonical(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            Value::Object(entries.into_iter().map(|(key, value)| (key.clone(), canonical(value))).collect())
+        }
+        Value::Array(items) => Value::Array(items.iter().map(canonical).collect()),
+        other => other.clone(),
+    }
+}
+
+/// 检查同一分组中的非空标识不重复，空标识在导入时生成
+fn check_unique<'a>(ids: impl Iterator<Item = &'a String>) -> Result<(), BundleError> {
+    let mut seen = HashSet::new();
+    for id in ids.filter(|id| !id.trim().is_empty()) {
+        if !seen.insert(id) {
+            return Err(BundleError::DuplicateId { id: id.clone() });
+        }
+    }
+    Ok(())
+}
+
+fn invalid_file(error: serde_json::Error) -> BundleError {
+    BundleError::InvalidFile {
+        message: error.to_string(),
+    }
+}
+
+/// 生成存储错误
+fn storage_error(path: &Path, error: impl std::fmt::Display) -> BundleError {
+    BundleError::Storage {
+        path: path.display().to_string(),
+        message: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alarm::AlarmStore;
+    use crate::history::HistoryStore;
+    use crate::inventory::Inventory;
+    use crate::layout::Layouts;
+    use crate::network::DeviceServer;
+    use serde_json::json;
+
+    #[test]
+    fn restores_previous_content_when_import_fails() {
+        let dir = std::env::temp_dir().join(format!("bundle-rollback-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let config = ConfigStore::with_listener(&dir.join("config.json"), |_| {});
+        let layouts = Layouts::new(config.clone());
+        let history = HistoryStore::open_in_memory().unwrap();
+        let alarms =
+            AlarmEngine::with_listener(AlarmStore::open_in_memory().unwrap(), history.clone(), layouts.clone(), |_| {});
+        let scheduler_dir = dir.join("scheduler");
+        let scheduler = Scheduler::with_listener(
+            &scheduler_dir,
+            DeviceServer::new(),
+            history,
+            alarms.clone(),
+            layouts,
+            Inventory::new(config.clone()),
+            |_| {},
+        );
+        let rule = json!({"id": "old", "name": "旧规则", "severity": "info",
+                          "condition": {"kind": "maxTemperature", "limit": 40.0}});
+        alarms.save_rule(serde_json::from_value(rule).unwrap()).unwrap();
+
+        let mut content = current(&config, &alarms, &scheduler);
+        content.config.network.timeout_ms = 3000;
+        content.alarm_rules.clear();
+        let target = json!({"link": {"kind": "tcp", "ip": "10.0.0.1", "port": 2000}, "subDeviceAddress": 1});
+        let job = json!({"name": "早班", "schedule": {"kind": "interval", "seconds": 600}, "targets": [target]});
+        content.poll_jobs.push(serde_json::from_value(job).unwrap());
+        let file = dir.join("bundle.json");
+        export(&file, &content).unwrap();
+        // 定时轮询任务的目录被同名文件占用，保存任务失败
+        fs::write(&scheduler_dir, "").unwrap();
+
+        let result = import(&file, &config, &alarms, &scheduler, false);
+
+        assert!(matches!(result, Err(BundleError::Scheduler { .. })));
+        assert_eq!(config.get().network.timeout_ms, 10_000);
+        assert_eq!(alarms.rules().len(), 1);
+        assert_eq!(alarms.rules()[0].id, "old");
+        assert!(scheduler.jobs().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
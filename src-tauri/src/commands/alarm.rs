@@ -0,0 +1,74 @@
+// 告警规则与告警处理命令
+
+use crate::alarm::{Alarm, AlarmEngine, AlarmError, AlarmQuery, AlarmRule};
+use crate::i18n::tr;
+use log::{debug, error};
+use tauri::State;
+
+/// 告警事件名称
+pub const ALARM_EVENT: &str = "alarm";
+
+/// 列出告警规则
+#[tauri::command]
+pub fn list_alarm_rules(alarms: State<'_, AlarmEngine>) -> Vec<AlarmRule> {
+    debug!("{}", tr!("cmd.called", command = "list_alarm_rules"));
+    alarms.rules()
+}
+
+/// 新建或更新告警规则
+///
+/// `id` 为空时新建规则并返回生成的标识，保存后从下一次读数开始生效
+#[tauri::command]
+pub fn save_alarm_rule(alarms: State<'_, AlarmEngine>, rule: AlarmRule) -> Result<AlarmRule, AlarmError> {
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.saveAlarmRule",
+            id = format!("{:?}", rule.id),
+            name = rule.name,
+            condition = format!("{:?}", rule.condition)
+        )
+    );
+
+    alarms.save_rule(rule).inspect_err(|e| {
+        error!("{}", tr!("alarm.saveFailed", error = e));
+    })
+}
+
+/// 删除告警规则，已产生的告警保留
+///
+/// 返回规则是否存在
+#[tauri::command]
+pub fn delete_alarm_rule(alarms: State<'_, AlarmEngine>, id: &str) -> Result<bool, AlarmError> {
+    debug!("{}", tr!("cmd.call.deleteAlarmRule", id = id));
+    alarms.delete_rule(id).inspect_err(|e| {
+        error!("{}", tr!("alarm.deleteFailed", error = e));
+    })
+}
+
+/// 按粮仓、分机和状态查询告警，按编号从新到旧排列
+#[tauri::command]
+pub fn query_alarms(alarms: State<'_, AlarmEngine>, query: AlarmQuery) -> Result<Vec<Alarm>, AlarmError> {
+    debug!("{}", tr!("cmd.call.queryAlarms", query = format!("{:?}", query)));
+    alarms.alarms(&query)
+}
+
+/// 确认告警，记录操作员和确认时间
+#[tauri::command]
+pub fn acknowledge_alarm(alarms: State<'_, AlarmEngine>, id: i64, operator: &str) -> Result<Alarm, AlarmError> {
+    debug!("{}", tr!("cmd.call.acknowledgeAlarm", id = id, operator = operator));
+
+    alarms.acknowledge(id, operator).inspect_err(|e| {
+        error!("{}", tr!("alarm.handleFailed", id = id, error = e));
+    })
+}
+
+/// 清除告警，记录操作员和清除时间
+#[tauri::command]
+pub fn clear_alarm(alarms: State<'_, AlarmEngine>, id: i64, operator: &str) -> Result<Alarm, AlarmError> {
+    debug!("{}", tr!("cmd.call.clearAlarm", id = id, operator = operator));
+
+    alarms.clear(id, operator).inspect_err(|e| {
+        error!("{}", tr!("alarm.handleFailed", id = id, error = e));
+    })
+}
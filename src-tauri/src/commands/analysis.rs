@@ -0,0 +1,40 @@
+// 粮情分析命令
+
+use crate::analysis::{trend, AnalysisError, TrendQuery, TrendReport};
+use crate::history::HistoryStore;
+use crate::i18n::tr;
+use crate::layout::Layouts;
+use log::{debug, error, info};
+use tauri::State;
+
+/// 分析粮仓测温点的温度趋势
+///
+/// 计算各测温点、各层和整仓的升温速率与日环比，标记升温速率超过上限的测温点，按粮仓的测温点布局分层
+#[tauri::command]
+pub fn analyze_trends(
+    history: State<'_, HistoryStore>,
+    layouts: State<'_, Layouts>,
+    query: TrendQuery,
+) -> Result<TrendReport, AnalysisError> {
+    debug!("{}", tr!("cmd.call.analyzeTrends", query = format!("{:?}", query)));
+
+    let layout = layouts.for_granary(query.granary.trim());
+    match trend::analyze(&history, &layout, &query) {
+        Ok(report) => {
+            info!(
+                "{}",
+                tr!(
+                    "analysis.analyzed",
+                    granary = report.granary,
+                    sensors = report.sensors.len(),
+                    flagged = report.flagged.len()
+                )
+            );
+            Ok(report)
+        }
+        Err(e) => {
+            error!("{}", tr!("analysis.failed", error = e));
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,119 @@
+// 批量轮询命令
+
+use crate::alarm::AlarmEngine;
+use crate::history::HistoryStore;
+use crate::i18n::tr;
+use crate::inventory::Inventory;
+use crate::layout::Layouts;
+use crate::network::batch;
+use crate::network::{
+    BatchOptions, BatchSummary, DeviceServer, NetworkError, PollTarget, RequestControl, RequestRegistry,
+    TargetResult,
+};
+use log::{debug, info, warn};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+/// 批量轮询结果事件名称
+pub const POLL_BATCH_EVENT: &str = "poll-batch";
+
+/// 批量轮询过程中推送的事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "camelCase", rename_all_fields = "camelCase")]
+enum BatchEvent<'a> {
+    /// 单个目标完成
+    Result {
+        batch_id: &'a str,
+        #[serde(flatten)]
+        result: &'a TargetResult,
+    },
+    /// 全部目标结束
+    Finished {
+        batch_id: &'a str,
+        #[serde(flatten)]
+        summary: &'a BatchSummary,
+    },
+}
+
+/// 批量读取粮情数据命令
+///
+/// 未指定粮仓的目标按台账中的采集器归属到仓房。按全局和单主机并发上限同时读取多个目标，每完成一个目标按测温点布局填写坐标、保存历史数据、判定告警并推送一次结果事件，结束时推送汇总事件并返回汇总。
+/// 可通过 `cancel_request` 以 `batch_id` 取消，未完成的目标以取消错误结束
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn poll_batch(
+    app: AppHandle,
+    registry: State<'_, RequestRegistry>,
+    server: State<'_, DeviceServer>,
+    history: State<'_, HistoryStore>,
+    alarms: State<'_, AlarmEngine>,
+    layouts: State<'_, Layouts>,
+    inventory: State<'_, Inventory>,
+    batch_id: String,
+    mut targets: Vec<PollTarget>,
+    options: Option<BatchOptions>,
+) -> Result<BatchSummary, NetworkError> {
+    let options = options.unwrap_or_default();
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.pollBatch",
+            id = batch_id,
+            count = targets.len(),
+            options = format!("{:?}", options)
+        )
+    );
+    info!("{}", tr!("batch.started", id = batch_id, count = targets.len()));
+    for target in &mut targets {
+        inventory.attribute(target);
+    }
+
+    let registered = registry.register(&batch_id);
+    let control = RequestControl::new(registered.token());
+    let handle = app.clone();
+    let id = batch_id.clone();
+    let history = history.inner().clone();
+    let alarms = alarms.inner().clone();
+    let layouts = layouts.inner().clone();
+    let summary = batch::poll_batch(targets, &options, server.inner(), &control, move |result| {
+        layouts.locate_target(result);
+        match history.record_target(result) {
+            Ok(Some(id)) => {
+                if let Err(e) = alarms.evaluate(id) {
+                    warn!("{}", tr!("alarm.evaluateFailed", error = e));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => warn!("{}", tr!("history.recordFailed", error = e)),
+        }
+        emit_event(&handle, &BatchEvent::Result { batch_id: &id, result });
+    })
+    .await;
+    drop(registered);
+
+    emit_event(
+        &app,
+        &BatchEvent::Finished {
+            batch_id: &batch_id,
+            summary: &summary,
+        },
+    );
+    info!(
+        "{}",
+        tr!(
+            "batch.finished",
+            id = batch_id,
+            succeeded = summary.succeeded,
+            failed = summary.failed,
+            elapsed = summary.elapsed_ms
+        )
+    );
+    Ok(summary)
+}
+
+/// 推送批量轮询事件，失败时只记录日志
+fn emit_event(app: &AppHandle, event: &BatchEvent<'_>) {
+    if let Err(e) = app.emit(POLL_BATCH_EVENT, event) {
+        warn!("{}", tr!("batch.eventEmitFailed", error = e));
+    }
+}
@@ -0,0 +1,63 @@
+// 配置包导入导出命令
+
+use crate::alarm::AlarmEngine;
+use crate::bundle::{self, BundleError, BundleInfo, ImportReport};
+use crate::config::ConfigStore;
+use crate::i18n::tr;
+use crate::scheduler::Scheduler;
+use log::{debug, error, info};
+use std::path::Path;
+use tauri::State;
+
+/// 导出配置包命令
+///
+/// 把应用配置（含站点台账和测温点布局）、告警规则和定时轮询任务导出到 `path`，文件中带内容的SHA-256校验和
+#[tauri::command]
+pub fn export_config_bundle(
+    config: State<'_, ConfigStore>,
+    alarms: State<'_, AlarmEngine>,
+    scheduler: State<'_, Scheduler>,
+    path: &str,
+) -> Result<BundleInfo, BundleError> {
+    debug!("{}", tr!("cmd.call.exportConfigBundle", path = path));
+
+    let content = bundle::current(&config, &alarms, &scheduler);
+    match bundle::export(Path::new(path), &content) {
+        Ok(info) => {
+            info!("{}", tr!("bundle.exported", path = path));
+            Ok(info)
+        }
+        Err(e) => {
+            error!("{}", tr!("bundle.exportFailed", error = e));
+            Err(e)
+        }
+    }
+}
+
+/// 导入配置包命令
+///
+/// 校验 `path` 的格式和校验和，返回导入后新增、变化和删除的条目；`dry_run` 为true时只预览差异，
+/// 否则替换全部应用配置、告警规则和定时轮询任务
+#[tauri::command]
+pub fn import_config_bundle(
+    config: State<'_, ConfigStore>,
+    alarms: State<'_, AlarmEngine>,
+    scheduler: State<'_, Scheduler>,
+    path: &str,
+    dry_run: bool,
+) -> Result<ImportReport, BundleError> {
+    debug!("{}", tr!("cmd.call.importConfigBundle", path = path, dryRun = dry_run));
+
+    match bundle::import(Path::new(path), &config, &alarms, &scheduler, dry_run) {
+        Ok(report) => {
+            if report.applied {
+                info!("{}", tr!("bundle.imported", path = path, count = report.changes.len()));
+            }
+            Ok(report)
+        }
+        Err(e) => {
+            error!("{}", tr!("bundle.importFailed", error = e));
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// 应用配置命令
+
+use crate::config::{AppConfig, ConfigError, ConfigStore};
+use crate::i18n::tr;
+use log::{debug, error, info};
+use serde_json::Value;
+use tauri::State;
+
+/// 配置变化事件名称
+pub const CONFIG_EVENT: &str = "app-config";
+
+/// 读取应用配置
+#[tauri::command]
+pub fn get_app_config(config: State<'_, ConfigStore>) -> AppConfig {
+    debug!("{}", tr!("cmd.called", command = "get_app_config"));
+    config.get()
+}
+
+/// 保存应用配置
+///
+/// 没有版本号的配置按前端LocalStorage中的旧格式升级后保存，内容有变化时推送配置事件
+#[tauri::command]
+pub fn save_app_config(config: State<'_, ConfigStore>, document: Value) -> Result<AppConfig, ConfigError> {
+    debug!("{}", tr!("cmd.call.saveAppConfig", version = format!("{:?}", document.get("version"))));
+
+    match config.set(document) {
+        Ok(saved) => {
+            info!("{}", tr!("config.saved", version = saved.version));
+            Ok(saved)
+        }
+        Err(e) => {
+            error!("{}", tr!("config.saveFailed", error = e));
+            Err(e)
+        }
+    }
+}
+
+/// 校验应用配置，返回升级到当前版本后的配置，不保存
+#[tauri::command]
+pub fn validate_app_config(document: Value) -> Result<AppConfig, ConfigError> {
+    debug!(
+        "{}",
+        tr!("cmd.call.validateAppConfig", version = format!("{:?}", document.get("version")))
+    );
+    ConfigStore::validate(document)
+}
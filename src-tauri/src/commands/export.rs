@@ -0,0 +1,58 @@
+// 读数导出命令
+
+use crate::export::{self, ExportError, ExportFormat, ExportQuery, ExportSummary};
+use crate::history::HistoryStore;
+use crate::i18n::tr;
+use crate::layout::Layouts;
+use log::{debug, error, info};
+use std::path::Path;
+use tauri::State;
+
+/// 导出读数命令
+///
+/// 把一次读取（`query.readingId`）或一段时间内的读数导出为CSV或Excel文件，`path` 由前端通过文件对话框选择。
+/// Excel文件中每层一个工作表按电缆行×列排列温度，汇总工作表列出各层最高、最低、平均温度、仓内外温湿度和异常测温点
+#[tauri::command]
+pub async fn export_readings(
+    history: State<'_, HistoryStore>,
+    layouts: State<'_, Layouts>,
+    query: ExportQuery,
+    format: ExportFormat,
+    path: String,
+) -> Result<ExportSummary, ExportError> {
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.exportReadings",
+            query = format!("{:?}", query),
+            format = format!("{:?}", format),
+            path = path
+        )
+    );
+
+    // 读取历史数据和写入文件都是阻塞操作，大范围导出耗时较长，不能占住异步工作线程
+    let history = history.inner().clone();
+    let layouts = layouts.inner().clone();
+    let target = path.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        export::export_readings(&history, &layouts, &query, format, Path::new(&target))
+    })
+    .await
+    .unwrap_or_else(|e| {
+        Err(ExportError::Storage {
+            path: path.clone(),
+            message: e.to_string(),
+        })
+    });
+
+    match result {
+        Ok(summary) => {
+            info!("{}", tr!("export.finished", count = summary.reading_count, path = path));
+            Ok(summary)
+        }
+        Err(e) => {
+            error!("{}", tr!("export.failed", error = e));
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,96 @@
+// 历史数据查询命令
+
+use crate::alarm::AlarmEngine;
+use crate::history::{HistoryError, HistoryLocation, HistoryQuery, HistoryReading, HistoryStore};
+use crate::i18n::tr;
+use crate::layout::Layouts;
+use crate::protocol;
+use crate::utils::hex_utils::hex_string_to_bytes;
+use log::{debug, error, info, warn};
+use tauri::State;
+
+/// 按时间范围和位置查询历史读数
+///
+/// 按采样时间从新到旧排列，`includeSensors` 为true时返回每条记录的测温点读数，并按所属粮仓的测温点布局填写坐标
+#[tauri::command]
+pub fn query_history(
+    history: State<'_, HistoryStore>,
+    layouts: State<'_, Layouts>,
+    query: HistoryQuery,
+) -> Result<Vec<HistoryReading>, HistoryError> {
+    debug!("{}", tr!("cmd.call.queryHistory", query = format!("{:?}", query)));
+
+    let start_time = std::time::Instant::now();
+    match history.query(&query) {
+        Ok(mut readings) => {
+            if query.include_sensors {
+                for reading in &mut readings {
+                    layouts.for_granary(&reading.granary).locate_reading(reading);
+                }
+            }
+            info!(
+                "{}",
+                tr!("history.queried", count = readings.len(), elapsed = start_time.elapsed().as_millis())
+            );
+            Ok(readings)
+        }
+        Err(e) => {
+            error!("{}", tr!("history.queryFailed", error = e));
+            Err(e)
+        }
+    }
+}
+
+/// 按编号读取一条历史记录，包含测温点读数和原始帧，测温点按所属粮仓的测温点布局填写坐标
+#[tauri::command]
+pub fn get_history_reading(
+    history: State<'_, HistoryStore>,
+    layouts: State<'_, Layouts>,
+    id: i64,
+) -> Result<HistoryReading, HistoryError> {
+    debug!("{}", tr!("cmd.call.getHistoryReading", id = id));
+    let mut reading = history.reading(id)?;
+    layouts.for_granary(&reading.granary).locate_reading(&mut reading);
+    Ok(reading)
+}
+
+/// 列出有历史记录的粮仓和分机，以及各自的记录数量和时间范围
+#[tauri::command]
+pub fn list_history_locations(history: State<'_, HistoryStore>) -> Result<Vec<HistoryLocation>, HistoryError> {
+    debug!("{}", tr!("cmd.called", command = "list_history_locations"));
+    history.locations()
+}
+
+/// 保存前端通过 `send_hex_data` 读取的响应帧
+///
+/// 解析16进制响应帧后保存并判定告警，返回新记录的编号
+#[tauri::command]
+pub fn record_reading(
+    history: State<'_, HistoryStore>,
+    alarms: State<'_, AlarmEngine>,
+    granary: Option<String>,
+    source: String,
+    data: &str,
+) -> Result<i64, HistoryError> {
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.recordReading",
+            granary = format!("{:?}", granary),
+            source = source,
+            length = data.len()
+        )
+    );
+
+    let bytes = hex_string_to_bytes(data).map_err(|message| HistoryError::InvalidFrame {
+        error: protocol::FrameError::InvalidHex { message },
+    })?;
+    let mut frame = protocol::decode_response(&bytes).map_err(|error| HistoryError::InvalidFrame { error })?;
+    let id = history.record(granary.as_deref(), &source, &mut frame).inspect_err(|e| {
+        error!("{}", tr!("history.recordFailed", error = e));
+    })?;
+    if let Err(e) = alarms.evaluate(id) {
+        warn!("{}", tr!("alarm.evaluateFailed", error = e));
+    }
+    Ok(id)
+}
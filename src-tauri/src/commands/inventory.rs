@@ -0,0 +1,108 @@
+// 站点台账命令
+
+use crate::config::ConfigError;
+use crate::i18n::tr;
+use crate::inventory::{Collector, Depot, Inventory, InventoryConfig, Warehouse};
+use crate::network::PollTarget;
+use log::{debug, error};
+use tauri::State;
+
+/// 读取全部库点、仓房和采集器
+#[tauri::command]
+pub fn get_inventory(inventory: State<'_, Inventory>) -> InventoryConfig {
+    debug!("{}", tr!("cmd.called", command = "get_inventory"));
+    inventory.config()
+}
+
+/// 新建或更新库点
+///
+/// `id` 为空时新建库点并返回生成的标识
+#[tauri::command]
+pub fn save_depot(inventory: State<'_, Inventory>, depot: Depot) -> Result<Depot, ConfigError> {
+    debug!("{}", tr!("cmd.call.saveDepot", id = format!("{:?}", depot.id), name = depot.name));
+    inventory.save_depot(depot).inspect_err(|e| {
+        error!("{}", tr!("inventory.saveFailed", error = e));
+    })
+}
+
+/// 删除库点，库点下仍有仓房时返回错误
+///
+/// 返回库点是否存在
+#[tauri::command]
+pub fn delete_depot(inventory: State<'_, Inventory>, id: &str) -> Result<bool, ConfigError> {
+    debug!("{}", tr!("cmd.call.deleteDepot", id = id));
+    inventory.delete_depot(id).inspect_err(|e| {
+        error!("{}", tr!("inventory.deleteFailed", id = id, error = e));
+    })
+}
+
+/// 新建或更新仓房
+///
+/// `id` 为空时新建仓房并返回生成的标识；仓房标识即历史数据和告警中的粮仓
+#[tauri::command]
+pub fn save_warehouse(inventory: State<'_, Inventory>, warehouse: Warehouse) -> Result<Warehouse, ConfigError> {
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.saveWarehouse",
+            id = format!("{:?}", warehouse.id),
+            name = warehouse.name,
+            depot = warehouse.depot_id
+        )
+    );
+    inventory.save_warehouse(warehouse).inspect_err(|e| {
+        error!("{}", tr!("inventory.saveFailed", error = e));
+    })
+}
+
+/// 删除仓房，仓房下仍有采集器时返回错误；已保存的历史数据和告警保留
+///
+/// 返回仓房是否存在
+#[tauri::command]
+pub fn delete_warehouse(inventory: State<'_, Inventory>, id: &str) -> Result<bool, ConfigError> {
+    debug!("{}", tr!("cmd.call.deleteWarehouse", id = id));
+    inventory.delete_warehouse(id).inspect_err(|e| {
+        error!("{}", tr!("inventory.deleteFailed", id = id, error = e));
+    })
+}
+
+/// 新建或更新采集器
+///
+/// `id` 为空时新建采集器并返回生成的标识；同一链路和分机地址只能登记一个采集器
+#[tauri::command]
+pub fn save_collector(inventory: State<'_, Inventory>, collector: Collector) -> Result<Collector, ConfigError> {
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.saveCollector",
+            id = format!("{:?}", collector.id),
+            name = collector.name,
+            warehouse = collector.warehouse_id,
+            link = format!("{:?}", collector.link),
+            address = collector.sub_device_address
+        )
+    );
+    inventory.save_collector(collector).inspect_err(|e| {
+        error!("{}", tr!("inventory.saveFailed", error = e));
+    })
+}
+
+/// 删除采集器
+///
+/// 返回采集器是否存在
+#[tauri::command]
+pub fn delete_collector(inventory: State<'_, Inventory>, id: &str) -> Result<bool, ConfigError> {
+    debug!("{}", tr!("cmd.call.deleteCollector", id = id));
+    inventory.delete_collector(id).inspect_err(|e| {
+        error!("{}", tr!("inventory.deleteFailed", id = id, error = e));
+    })
+}
+
+/// 按台账生成轮询目标，可直接用于批量轮询或定时轮询任务
+///
+/// 传入 `warehouse_id` 时只包含该仓房的采集器
+#[tauri::command]
+pub fn list_inventory_targets(inventory: State<'_, Inventory>, warehouse_id: Option<String>) -> Vec<PollTarget> {
+    debug!("{}", tr!("cmd.call.listInventoryTargets", warehouse = format!("{:?}", warehouse_id)));
+    inventory.poll_targets(warehouse_id.as_deref())
+}
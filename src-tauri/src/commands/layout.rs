@@ -0,0 +1,63 @@
+// 测温点布局命令
+
+use crate::config::ConfigError;
+use crate::i18n::tr;
+use crate::layout::{LayoutConfig, Layouts, SensorPosition};
+use log::{debug, error, info};
+use serde::Serialize;
+use tauri::State;
+
+/// 测温点编号及其坐标
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SensorLocation {
+    /// 测温点编号
+    pub sensor_id: u16,
+    #[serde(flatten)]
+    pub position: SensorPosition,
+}
+
+/// 读取默认布局和按粮仓单独设置的布局
+#[tauri::command]
+pub fn get_sensor_layouts(layouts: State<'_, Layouts>) -> LayoutConfig {
+    debug!("{}", tr!("cmd.called", command = "get_sensor_layouts"));
+    layouts.config()
+}
+
+/// 保存全部测温点布局，保存后立即用于之后的解析、告警判定和分析
+#[tauri::command]
+pub fn save_sensor_layouts(layouts: State<'_, Layouts>, config: LayoutConfig) -> Result<LayoutConfig, ConfigError> {
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.saveSensorLayouts",
+            layout = format!("{:?}", config.default),
+            granaries = format!("{:?}", config.granaries.keys().collect::<Vec<_>>())
+        )
+    );
+
+    match layouts.save(config) {
+        Ok(config) => {
+            info!("{}", tr!("layout.saved", count = config.granaries.len()));
+            Ok(config)
+        }
+        Err(e) => {
+            error!("{}", tr!("layout.saveFailed", error = e));
+            Err(e)
+        }
+    }
+}
+
+/// 按粮仓的测温点布局列出每个测温点编号对应的层、行、列坐标
+///
+/// 粮仓没有单独设置布局时使用默认布局
+#[tauri::command]
+pub fn locate_sensors(layouts: State<'_, Layouts>, granary: &str) -> Vec<SensorLocation> {
+    debug!("{}", tr!("cmd.call.locateSensors", granary = granary));
+    layouts
+        .for_granary(granary)
+        .sensor_map()
+        .iter()
+        .map(|(sensor_id, position)| SensorLocation { sensor_id, position })
+        .collect()
+}
@@ -1,63 +1,141 @@
 // 命令模块，包含所有Tauri命令
 
+use crate::alarm::AlarmEngine;
+use crate::config::ConfigStore;
+use crate::history::HistoryStore;
+use crate::i18n::{self, tr, Locale};
+use crate::inventory::Inventory;
+use crate::layout::Layouts;
 use crate::logger::{self, LogEntry};
-use crate::network;
+use crate::network::advice::{self, ErrorAdvice};
+use crate::network::address::format_host_port;
+use crate::network::link::DeviceLink;
+use crate::network::{self, DeviceServer, NetworkError, RequestRegistry, SendResult};
+use crate::protocol::{self, FrameError, RequestFrame, ResponseFrame};
+use crate::utils::hex_utils::{format_bytes_to_hex, hex_string_to_bytes};
+use chrono::NaiveDateTime;
 use log::{debug, error, info, warn};
+use tauri::{AppHandle, State};
+
+// 告警规则与告警处理命令
+mod alarm;
+// 粮情分析命令
+mod analysis;
+// 批量轮询命令
+mod batch;
+// 配置包导入导出命令
+mod bundle;
+// 应用配置命令
+mod config;
+// 读数导出命令
+mod export;
+// 历史数据查询命令
+mod history;
+// 站点台账命令
+mod inventory;
+// 测温点布局命令
+mod layout;
+// 检测报告命令
+mod report;
+// 网络请求取消与进度命令
+mod request;
+// 定时轮询任务命令
+mod scheduler;
+// 串口通信命令
+mod serial;
+// 设备接入服务命令
+mod server;
+// 设备长连接会话命令
+mod session;
+
+pub use alarm::{
+    acknowledge_alarm, clear_alarm, delete_alarm_rule, list_alarm_rules, query_alarms, save_alarm_rule, ALARM_EVENT,
+};
+pub use analysis::analyze_trends;
+pub use batch::poll_batch;
+pub use bundle::{export_config_bundle, import_config_bundle};
+pub use config::{get_app_config, save_app_config, validate_app_config, CONFIG_EVENT};
+pub use export::export_readings;
+pub use history::{get_history_reading, list_history_locations, query_history, record_reading};
+pub use inventory::{
+    delete_collector, delete_depot, delete_warehouse, get_inventory, list_inventory_targets, save_collector,
+    save_depot, save_warehouse,
+};
+pub use layout::{get_sensor_layouts, locate_sensors, save_sensor_layouts};
+pub use report::{list_report_templates, render_inspection_report, save_inspection_report};
+pub use request::cancel_request;
+pub use scheduler::{
+    cancel_poll_job, delete_poll_job, list_job_runs, list_poll_jobs, run_poll_job, save_poll_job,
+    SCHEDULER_EVENT,
+};
+pub use serial::{list_serial_ports, send_hex_serial};
+pub use server::{
+    get_device_server, list_inbound_devices, start_device_server, stop_device_server,
+    INBOUND_DEVICE_EVENT,
+};
+pub use session::{close_session, list_sessions, open_session, send_on_session, SESSION_STATE_EVENT};
 
 /// 发送16进制数据命令
 ///
-/// 向指定IP和端口发送16进制数据，并返回响应
+/// 向指定IP和端口发送16进制数据，并返回响应和实际连接的地址；传入 `device_id` 时发送到该接入设备，忽略IP和端口。
+/// 传入 `request_id` 时可通过 `cancel_request` 取消，并推送连接、发送和接收进度事件
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
-pub fn send_hex_data(
-    ip: &str,
+pub async fn send_hex_data(
+    app: AppHandle,
+    registry: State<'_, RequestRegistry>,
+    server: State<'_, DeviceServer>,
+    ip: String,
     port: u16,
-    data: &str,
+    data: String,
     timeout_ms: Option<u64>,
-) -> Result<String, String> {
+    request_id: Option<String>,
+    device_id: Option<String>,
+) -> Result<SendResult, NetworkError> {
     debug!(
-        "命令调用: send_hex_data - IP: {}, 端口: {}, 超时: {:?}",
-        ip, port, timeout_ms
-    );
-    info!(
-        "准备发送数据到 {}:{}, 数据长度: {} 字节",
-        ip,
-        port,
-        data.len()
+        "{}",
+        tr!(
+            "cmd.call.sendHexData",
+            ip = ip,
+            port = port,
+            device = format!("{:?}", device_id),
+            timeout = format!("{:?}", timeout_ms),
+            requestId = format!("{:?}", request_id)
+        )
     );
+    let address = target_label(&ip, port, device_id.as_deref());
+    info!("{}", tr!("cmd.sendPrepare", address = address, length = data.len()));
 
-    // 记录数据摘要（只显示前30个字符，避免日志过长）
-    let data_preview = if data.len() > 30 {
-        format!("{}...(共{}字节)", &data[0..30], data.len())
-    } else {
-        data.to_string()
-    };
-    debug!("发送数据内容: {}", data_preview);
+    debug!("{}", tr!("cmd.sendData", data = preview(&data, 30)));
 
     // 调用网络模块发送数据
+    let (control, _registered) = request::track_request(&app, &registry, request_id.as_deref());
     let start_time = std::time::Instant::now();
-    let result = network::send_hex_data(ip, port, data, timeout_ms);
+    let result = match &device_id {
+        Some(device_id) => server.send(device_id, &data, timeout_ms, &control).await,
+        None => network::send_hex_data(&ip, port, &data, timeout_ms, &control).await,
+    };
     let elapsed = start_time.elapsed();
 
     // 记录结果日志
     match &result {
-        Ok(response) => {
+        Ok(SendResult { response, .. }) => {
             let response_len = response.len();
-            let response_preview = if response.len() > 30 {
-                format!("{}...(共{}字节)", &response[0..30], response_len)
-            } else {
-                response.to_string()
-            };
-
             info!(
-                "成功接收来自 {}:{} 的响应，长度: {} 字节，耗时: {:?}",
-                ip, port, response_len, elapsed
+                "{}",
+                tr!(
+                    "cmd.sendSucceeded",
+                    address = address,
+                    length = response_len,
+                    elapsed = elapsed.as_millis()
+                )
             );
-            debug!("响应数据内容: {}", response_preview);
+            debug!("{}", tr!("cmd.responseData", data = preview(response, 30)));
         }
         Err(e) => {
             error!(
-                "发送数据到 {}:{} 失败: {}, 耗时: {:?}",
-                ip, port, e, elapsed
+                "{}",
+                tr!("cmd.sendFailed", address = address, error = e, elapsed = elapsed.as_millis())
             );
         }
     }
@@ -65,33 +143,300 @@ pub fn send_hex_data(
     result
 }
 
+/// 读取粮情数据命令
+///
+/// 自动构建请求帧并读取指定分机的粮情，测温点超过512个时按多包响应重组，读取成功后保存到历史数据库并判定告警。
+/// 传入 `device_id` 时读取该接入设备，忽略IP和端口；传入 `request_id` 时可取消并推送进度事件；
+/// `granary` 为历史数据中的粮仓，未传入时按台账中采集器所属的仓房，台账中没有该采集器时为设备地址，按该粮仓的测温点布局填写坐标；
+/// 传入 `total_points` 时总数以内没有数据的测温点标记为缺失；
+/// 未传入超时时间和补发轮数时使用应用配置中的默认值
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn poll_grain_data(
+    app: AppHandle,
+    registry: State<'_, RequestRegistry>,
+    server: State<'_, DeviceServer>,
+    history: State<'_, HistoryStore>,
+    alarms: State<'_, AlarmEngine>,
+    layouts: State<'_, Layouts>,
+    config: State<'_, ConfigStore>,
+    inventory: State<'_, Inventory>,
+    ip: String,
+    port: u16,
+    sub_device_address: u8,
+    total_points: Option<usize>,
+    timeout_ms: Option<u64>,
+    max_retries: Option<u32>,
+    request_id: Option<String>,
+    device_id: Option<String>,
+    granary: Option<String>,
+) -> Result<ResponseFrame, NetworkError> {
+    let configured_points = total_points;
+    let total_points = total_points.unwrap_or(protocol::response::SENSORS_PER_PACKET);
+    let (default_timeout, default_retries) =
+        config.read(|config| (config.network.timeout_ms, config.network.max_retries));
+    let timeout_ms = timeout_ms.or(Some(default_timeout));
+    let max_retries = max_retries.unwrap_or(default_retries);
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.pollGrainData",
+            ip = ip,
+            port = port,
+            device = format!("{:?}", device_id),
+            address = sub_device_address,
+            points = total_points,
+            timeout = format!("{:?}", timeout_ms),
+            retries = max_retries
+        )
+    );
+    let address = target_label(&ip, port, device_id.as_deref());
+    let link = match &device_id {
+        Some(device_id) => DeviceLink::Inbound {
+            device_id: device_id.clone(),
+        },
+        None => DeviceLink::Tcp { ip: ip.clone(), port },
+    };
+    let granary = granary
+        .filter(|granary| !granary.trim().is_empty())
+        .or_else(|| inventory.granary_for(&link, sub_device_address));
+
+    let (control, _registered) = request::track_request(&app, &registry, request_id.as_deref());
+    let start_time = std::time::Instant::now();
+    let result = match &device_id {
+        Some(device_id) => {
+            server
+                .poll(device_id, sub_device_address, total_points, timeout_ms, max_retries, &control)
+                .await
+        }
+        None => {
+            network::poll_grain_data(
+                &ip,
+                port,
+                sub_device_address,
+                total_points,
+                timeout_ms,
+                max_retries,
+                &control,
+            )
+            .await
+        }
+    };
+    let elapsed = start_time.elapsed();
+
+    match result {
+        Ok(mut frame) => {
+            if let Some(total_points) = configured_points {
+                frame.mark_missing(total_points);
+            }
+            layouts.for_granary(granary.as_deref().unwrap_or(&address)).locate(&mut frame);
+            info!(
+                "{}",
+                tr!(
+                    "cmd.pollSucceeded",
+                    address = address,
+                    device = sub_device_address,
+                    packets = frame.packet_count,
+                    sensors = frame.sensors.len(),
+                    elapsed = elapsed.as_millis()
+                )
+            );
+            // 保存历史数据、判定告警都是阻塞的SQLite操作，不能占住异步工作线程
+            let history = history.inner().clone();
+            let alarms = alarms.inner().clone();
+            let unrecorded = frame.clone();
+            let recorded = tokio::task::spawn_blocking(move || {
+                match history.record(granary.as_deref(), &address, &mut frame) {
+                    Ok(id) => {
+                        if let Err(e) = alarms.evaluate(id) {
+                            warn!("{}", tr!("alarm.evaluateFailed", error = e));
+                        }
+                    }
+                    Err(e) => warn!("{}", tr!("history.recordFailed", error = e)),
+                }
+                frame
+            })
+            .await;
+            Ok(recorded.unwrap_or_else(|e| {
+                warn!("{}", tr!("history.recordFailed", error = e));
+                unrecorded
+            }))
+        }
+        Err(e) => {
+            error!(
+                "{}",
+                tr!(
+                    "cmd.pollFailed",
+                    address = address,
+                    device = sub_device_address,
+                    error = e,
+                    elapsed = elapsed.as_millis()
+                )
+            );
+            Err(e)
+        }
+    }
+}
+
+/// 日志中显示的目标：接入设备标识或 "主机:端口"
+fn target_label(ip: &str, port: u16, device_id: Option<&str>) -> String {
+    match device_id {
+        Some(device_id) => device_id.to_string(),
+        None => format_host_port(ip, port),
+    }
+}
+
+/// 日志中显示的内容摘要，超过 `limit` 个字符时截断并注明总字符数，避免日志过长
+fn preview(text: &str, limit: usize) -> String {
+    let length = text.chars().count();
+    if length > limit {
+        let head: String = text.chars().take(limit).collect();
+        tr!("cmd.preview", text = head, length = length)
+    } else {
+        text.to_string()
+    }
+}
+
+/// 生成网络错误说明命令
+///
+/// 根据网络命令返回的结构化错误生成可能原因与排查建议
+#[tauri::command]
+pub fn explain_network_error(error: NetworkError) -> ErrorAdvice {
+    debug!("{}", tr!("cmd.call.explainNetworkError", error = error));
+    advice::advise(&error)
+}
+
+/// 解析粮情响应帧命令
+///
+/// 将设备返回的16进制响应解析为结构化的温湿度读数，帧损坏时返回校验错误详情。
+/// 按 `granary` 的测温点布局填写坐标，未传入时使用默认布局
+#[tauri::command]
+pub fn decode_response_frame(
+    layouts: State<'_, Layouts>,
+    data: &str,
+    granary: Option<String>,
+) -> Result<ResponseFrame, FrameError> {
+    debug!(
+        "{}",
+        tr!("cmd.call.decodeResponseFrame", length = data.len(), granary = format!("{:?}", granary))
+    );
+
+    let bytes = hex_string_to_bytes(data).map_err(|e| {
+        error!("{}", tr!("cmd.hexParseFailed", error = e));
+        FrameError::InvalidHex { message: e }
+    })?;
+
+    let start_time = std::time::Instant::now();
+    match protocol::decode_response(&bytes) {
+        Ok(mut frame) => {
+            // 单独设置布局的粮仓名称不能为空，空名称总是对应默认布局
+            layouts.for_granary(granary.as_deref().unwrap_or_default()).locate(&mut frame);
+            let elapsed = start_time.elapsed();
+            info!(
+                "{}",
+                tr!(
+                    "cmd.decodeSucceeded",
+                    device = frame.sub_device_address,
+                    sensors = frame.sensors.len(),
+                    elapsed = elapsed.as_millis()
+                )
+            );
+            Ok(frame)
+        }
+        Err(e) => {
+            let elapsed = start_time.elapsed();
+            error!("{}", tr!("cmd.decodeFailed", error = e, elapsed = elapsed.as_millis()));
+            Err(e)
+        }
+    }
+}
+
+/// 构建读取粮情的请求帧命令
+///
+/// 根据分机地址、时间和命令码生成带校验和与帧尾的16进制请求数据
+#[tauri::command]
+pub fn build_poll_frame(
+    sub_device_address: u8,
+    timestamp: Option<String>,
+    command: Option<u8>,
+) -> Result<String, String> {
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.buildPollFrame",
+            address = sub_device_address,
+            timestamp = format!("{:?}", timestamp),
+            command = format!("{:?}", command)
+        )
+    );
+
+    let mut request = RequestFrame::new(sub_device_address);
+    if let Some(timestamp) = timestamp {
+        // 兼容前端datetime-local输入框的 "YYYY-MM-DDTHH:MM:SS" 格式
+        let parsed = NaiveDateTime::parse_from_str(&timestamp, "%Y-%m-%d %H:%M:%S")
+            .or_else(|_| NaiveDateTime::parse_from_str(&timestamp, "%Y-%m-%dT%H:%M:%S"))
+            .or_else(|_| NaiveDateTime::parse_from_str(&timestamp, "%Y-%m-%dT%H:%M"))
+            .map_err(|e| {
+                error!("{}", tr!("cmd.timestampParseFailed", timestamp = timestamp, error = e));
+                tr!("cmd.invalidTimestamp", timestamp = timestamp)
+            })?;
+        request = request.timestamp(parsed);
+    }
+    if let Some(command) = command {
+        request = request.command(command);
+    }
+
+    match request.build() {
+        Ok(frame) => {
+            let hex = format_bytes_to_hex(&frame);
+            info!(
+                "{}",
+                tr!("cmd.buildSucceeded", device = sub_device_address, length = frame.len())
+            );
+            Ok(hex)
+        }
+        Err(e) => {
+            error!("{}", tr!("cmd.buildFailed", error = e));
+            Err(e)
+        }
+    }
+}
+
 /// 获取日志
 ///
 /// 从日志文件中读取最近的日志
 #[tauri::command]
 pub fn get_logs(level: Option<String>, limit: Option<usize>) -> Result<Vec<LogEntry>, String> {
     debug!(
-        "命令调用: get_logs - 级别过滤: {:?}, 限制数量: {:?}",
-        level, limit
+        "{}",
+        tr!("cmd.call.getLogs", level = format!("{:?}", level), limit = format!("{:?}", limit))
     );
 
     let actual_limit = limit.unwrap_or(100);
     info!(
-        "正在获取日志，级别过滤: {:?}，限制数量: {}",
-        level, actual_limit
+        "{}",
+        tr!(
+            "cmd.getLogs",
+            level = level.as_deref().unwrap_or("all"),
+            limit = actual_limit
+        )
     );
 
     let start_time = std::time::Instant::now();
     match logger::read_logs(level.clone(), Some(actual_limit)) {
         Ok(logs) => {
             let elapsed = start_time.elapsed();
-            info!("成功获取 {} 条日志记录，耗时: {:?}", logs.len(), elapsed);
+            info!(
+                "{}",
+                tr!("cmd.getLogsSucceeded", count = logs.len(), elapsed = elapsed.as_millis())
+            );
             Ok(logs)
         }
         Err(e) => {
             let elapsed = start_time.elapsed();
-            error!("读取日志失败: {}, 耗时: {:?}", e, elapsed);
-            Err(format!("读取日志失败: {}", e))
+            let message = tr!("cmd.readLogsFailed", error = e);
+            error!("{}, {:?}", message, elapsed);
+            Err(message)
         }
     }
 }
@@ -101,39 +446,30 @@ pub fn get_logs(level: Option<String>, limit: Option<usize>) -> Result<Vec<LogEn
 /// 允许前端添加日志到日志文件
 #[tauri::command]
 pub fn add_log(level: &str, message: &str) -> Result<(), String> {
-    debug!(
-        "命令调用: add_log - 级别: {}, 消息长度: {}",
-        level,
-        message.len()
-    );
+    debug!("{}", tr!("cmd.call.addLog", level = level, length = message.len()));
 
-    // 记录消息摘要（只显示前50个字符，避免日志过长）
-    let message_preview = if message.chars().count() > 50 {
-        let preview: String = message.chars().take(50).collect();
-        format!("{}...(共{}字符)", preview, message.chars().count())
-    } else {
-        message.to_string()
-    };
+    let message_preview = preview(message, 50);
 
     // 根据级别记录日志
     match level {
-        "info" => info!("前端日志 [{}]: {}", level, message_preview),
-        "warn" => warn!("前端日志 [{}]: {}", level, message_preview),
-        "error" => error!("前端日志 [{}]: {}", level, message_preview),
-        _ => info!("前端日志 [{}]: {}", level, message_preview),
+        "info" => info!("{}", tr!("cmd.frontendLog", level = level, message = message_preview)),
+        "warn" => warn!("{}", tr!("cmd.frontendLog", level = level, message = message_preview)),
+        "error" => error!("{}", tr!("cmd.frontendLog", level = level, message = message_preview)),
+        _ => info!("{}", tr!("cmd.frontendLog", level = level, message = message_preview)),
     }
 
     let start_time = std::time::Instant::now();
     match logger::append_to_log(&level.to_uppercase(), message) {
         Ok(_) => {
             let elapsed = start_time.elapsed();
-            debug!("成功添加日志，耗时: {:?}", elapsed);
+            debug!("{}", tr!("cmd.addLogSucceeded", elapsed = elapsed.as_millis()));
             Ok(())
         }
         Err(e) => {
             let elapsed = start_time.elapsed();
-            error!("添加日志失败: {}, 耗时: {:?}", e, elapsed);
-            Err(format!("添加日志失败: {}", e))
+            let message = tr!("cmd.addLogFailed", error = e);
+            error!("{}, {:?}", message, elapsed);
+            Err(message)
         }
     }
 }
@@ -143,20 +479,59 @@ pub fn add_log(level: &str, message: &str) -> Result<(), String> {
 /// 清空当天的日志文件
 #[tauri::command]
 pub fn clear_logs() -> Result<(), String> {
-    debug!("命令调用: clear_logs");
-    info!("正在清空日志文件");
+    debug!("{}", tr!("cmd.called", command = "clear_logs"));
+    info!("{}", tr!("cmd.clearLogs"));
 
     let start_time = std::time::Instant::now();
     match logger::clear_logs() {
         Ok(_) => {
             let elapsed = start_time.elapsed();
-            info!("成功清空日志文件，耗时: {:?}", elapsed);
+            info!("{}", tr!("cmd.clearLogsSucceeded", elapsed = elapsed.as_millis()));
             Ok(())
         }
         Err(e) => {
             let elapsed = start_time.elapsed();
-            error!("清空日志失败: {}, 耗时: {:?}", e, elapsed);
-            Err(format!("清空日志失败: {}", e))
+            let message = tr!("cmd.clearLogsFailed", error = e);
+            error!("{}, {:?}", message, elapsed);
+            Err(message)
+        }
+    }
+}
+
+/// 设置界面语言
+///
+/// 影响之后返回的错误信息、排查建议、日志和崩溃报告，支持 zh-CN 和 en-US；
+/// 语言保存在应用配置中，下次启动时沿用
+#[tauri::command]
+pub fn set_locale(config: State<'_, ConfigStore>, locale: &str) -> Result<Locale, String> {
+    debug!("{}", tr!("cmd.call.setLocale", locale = locale));
+
+    match Locale::parse(locale) {
+        Some(parsed) => {
+            i18n::set_locale(parsed);
+            info!("{}", tr!("locale.changed", locale = parsed));
+            // 已切换的语言在本次运行中保持生效，保存失败只影响下次启动
+            if let Err(e) = config.update(|config| {
+                config.locale = parsed;
+                Ok(())
+            }) {
+                let message = tr!("locale.saveFailed", error = e);
+                warn!("{}", message);
+                return Err(message);
+            }
+            Ok(parsed)
+        }
+        None => {
+            let message = tr!("locale.unsupported", locale = locale);
+            warn!("{}", message);
+            Err(message)
         }
     }
 }
+
+/// 获取当前界面语言
+#[tauri::command]
+pub fn get_locale() -> Locale {
+    debug!("{}", tr!("cmd.called", command = "get_locale"));
+    i18n::current_locale()
+}
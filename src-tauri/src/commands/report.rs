@@ -0,0 +1,83 @@
+// 检测报告命令
+
+use crate::i18n::tr;
+use crate::report::{ReportError, ReportRequest, ReportSummary, ReportTemplate, Reports};
+use log::{debug, error, info, warn};
+use std::path::Path;
+use tauri::State;
+
+/// 列出报告模板命令
+///
+/// 内置的标准粮情检测报告模板在前，之后是模板目录中的自定义模板
+#[tauri::command]
+pub fn list_report_templates(reports: State<'_, Reports>) -> Result<Vec<ReportTemplate>, ReportError> {
+    debug!("{}", tr!("cmd.called", command = "list_report_templates"));
+    reports.templates()
+}
+
+/// 生成检测报告命令
+///
+/// 按模板把一次读取（`request.readingId`）或一段时间内的读数生成为HTML，用于在界面中预览和打印
+#[tauri::command]
+pub async fn render_inspection_report(
+    reports: State<'_, Reports>,
+    request: ReportRequest,
+) -> Result<String, ReportError> {
+    debug!("{}", tr!("cmd.call.renderInspectionReport", request = format!("{:?}", request)));
+
+    let reports = reports.inner().clone();
+    run_blocking(move || reports.render(&request)).await.inspect_err(|e| {
+        error!("{}", tr!("report.failed", error = e));
+    })
+}
+
+/// 保存检测报告命令
+///
+/// 生成检测报告并写入 `path`，`path` 由前端通过文件对话框选择；`open` 为true时用系统默认浏览器打开，
+/// 在浏览器中打印或另存为PDF
+#[tauri::command]
+pub async fn save_inspection_report(
+    reports: State<'_, Reports>,
+    request: ReportRequest,
+    path: String,
+    open: bool,
+) -> Result<ReportSummary, ReportError> {
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.saveInspectionReport",
+            request = format!("{:?}", request),
+            path = path,
+            open = open
+        )
+    );
+
+    let reports = reports.inner().clone();
+    let target = path.clone();
+    let summary = run_blocking(move || reports.save(&request, Path::new(&target)))
+        .await
+        .inspect_err(|e| {
+            error!("{}", tr!("report.failed", error = e));
+        })?;
+    info!("{}", tr!("report.saved", count = summary.inspection_count, path = path));
+    // 报告已保存，打开失败只记录日志
+    if open {
+        if let Err(e) = opener::open(&path) {
+            warn!("{}", tr!("report.openFailed", path = path, error = e));
+        }
+    }
+    Ok(summary)
+}
+
+/// 在阻塞线程池中生成报告
+///
+/// 读取历史数据、渲染模板和写入文件都是阻塞操作，大范围的报告耗时较长，不能占住异步工作线程
+async fn run_blocking<T, F>(f: F) -> Result<T, ReportError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, ReportError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .unwrap_or_else(|e| Err(ReportError::Task { message: e.to_string() }))
+}
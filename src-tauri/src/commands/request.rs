@@ -0,0 +1,65 @@
+// 网络请求取消与进度事件命令
+
+use crate::i18n::tr;
+use crate::network::{NetworkProgress, RequestControl, RequestRegistry};
+use crate::network::control::RegisteredRequest;
+use log::{debug, info, warn};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+/// 网络请求进度事件名称
+pub const NETWORK_PROGRESS_EVENT: &str = "network-progress";
+
+/// 推送给前端的进度事件，`requestId` 为前端发起请求时传入的ID
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProgressEvent<'a> {
+    request_id: &'a str,
+    #[serde(flatten)]
+    progress: &'a NetworkProgress,
+}
+
+/// 为带ID的请求登记取消令牌并推送进度事件，未提供ID的请求不可取消
+///
+/// # 返回值
+///
+/// 请求的控制参数，以及在请求结束时需要释放的登记项
+pub(crate) fn track_request<'a>(
+    app: &AppHandle,
+    registry: &'a RequestRegistry,
+    request_id: Option<&str>,
+) -> (RequestControl, Option<RegisteredRequest<'a>>) {
+    let Some(request_id) = request_id else {
+        return (RequestControl::default(), None);
+    };
+
+    let registered = registry.register(request_id);
+    let handle = app.clone();
+    let id = request_id.to_string();
+    let control = RequestControl::new(registered.token()).with_progress(move |progress| {
+        let event = ProgressEvent {
+            request_id: &id,
+            progress,
+        };
+        if let Err(e) = handle.emit(NETWORK_PROGRESS_EVENT, event) {
+            warn!("{}", tr!("cmd.progressEmitFailed", error = e));
+        }
+    });
+    (control, Some(registered))
+}
+
+/// 取消进行中的网络请求
+///
+/// 返回请求是否存在，已结束的请求返回false
+#[tauri::command]
+pub fn cancel_request(registry: State<'_, RequestRegistry>, request_id: &str) -> bool {
+    debug!("{}", tr!("cmd.call.cancelRequest", id = request_id));
+
+    let cancelled = registry.cancel(request_id);
+    if cancelled {
+        info!("{}", tr!("cmd.requestCancelled", id = request_id));
+    } else {
+        debug!("{}", tr!("cmd.requestNotFound", id = request_id));
+    }
+    cancelled
+}
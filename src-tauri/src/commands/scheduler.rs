@@ -0,0 +1,89 @@
+// 定时轮询任务命令
+
+use crate::i18n::tr;
+use crate::scheduler::{JobRun, JobStatus, PollJob, Scheduler, SchedulerError};
+use log::{debug, error, info};
+use tauri::State;
+
+/// 定时轮询事件名称
+pub const SCHEDULER_EVENT: &str = "scheduler";
+
+/// 列出定时轮询任务及其下一次执行时间
+#[tauri::command]
+pub fn list_poll_jobs(scheduler: State<'_, Scheduler>) -> Vec<JobStatus> {
+    debug!("{}", tr!("cmd.called", command = "list_poll_jobs"));
+    scheduler.jobs()
+}
+
+/// 新建或更新定时轮询任务
+///
+/// `id` 为空时新建任务并返回生成的标识，保存后立即按新计划调度
+#[tauri::command]
+pub fn save_poll_job(scheduler: State<'_, Scheduler>, job: PollJob) -> Result<PollJob, SchedulerError> {
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.savePollJob",
+            id = format!("{:?}", job.id),
+            name = job.name,
+            schedule = format!("{:?}", job.schedule),
+            count = job.targets.len()
+        )
+    );
+
+    scheduler.save_job(job).inspect_err(|e| {
+        error!("{}", tr!("scheduler.saveFailed", error = e));
+    })
+}
+
+/// 删除定时轮询任务，正在执行时一并取消
+///
+/// 返回任务是否存在
+#[tauri::command]
+pub fn delete_poll_job(scheduler: State<'_, Scheduler>, id: &str) -> Result<bool, SchedulerError> {
+    debug!("{}", tr!("cmd.call.deletePollJob", id = id));
+    scheduler.delete_job(id).inspect_err(|e| {
+        error!("{}", tr!("scheduler.deleteFailed", id = id, error = e));
+    })
+}
+
+/// 立即执行一次定时轮询任务
+///
+/// 执行过程与按计划执行相同，通过定时轮询事件推送结果
+#[tauri::command]
+pub fn run_poll_job(scheduler: State<'_, Scheduler>, id: &str) -> Result<(), SchedulerError> {
+    debug!("{}", tr!("cmd.call.runPollJob", id = id));
+    match scheduler.run_now(id) {
+        Ok(()) => {
+            info!("{}", tr!("scheduler.manualRun", id = id));
+            Ok(())
+        }
+        Err(e) => {
+            error!("{}", tr!("scheduler.manualRunFailed", id = id, error = e));
+            Err(e)
+        }
+    }
+}
+
+/// 取消正在执行的定时轮询任务
+///
+/// 返回任务是否正在执行
+#[tauri::command]
+pub fn cancel_poll_job(scheduler: State<'_, Scheduler>, id: &str) -> bool {
+    debug!("{}", tr!("cmd.call.cancelPollJob", id = id));
+    scheduler.cancel_run(id)
+}
+
+/// 查询定时轮询的执行记录，按时间从新到旧排列
+#[tauri::command]
+pub fn list_job_runs(
+    scheduler: State<'_, Scheduler>,
+    job_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<JobRun>, SchedulerError> {
+    debug!(
+        "{}",
+        tr!("cmd.call.listJobRuns", id = format!("{:?}", job_id), limit = format!("{:?}", limit))
+    );
+    scheduler.runs(job_id.as_deref(), limit.unwrap_or(100))
+}
@@ -0,0 +1,72 @@
+// 串口（RS-485）通信命令
+
+use super::request::track_request;
+use crate::i18n::tr;
+use crate::network::serial::{self, SerialPortDescription, SerialSettings};
+use crate::network::{NetworkError, RequestRegistry};
+use log::{debug, error, info};
+use tauri::{AppHandle, State};
+
+/// 列出可用串口命令
+#[tauri::command]
+pub fn list_serial_ports() -> Result<Vec<SerialPortDescription>, NetworkError> {
+    debug!("{}", tr!("cmd.called", command = "list_serial_ports"));
+    let ports = serial::list_serial_ports()?;
+    info!("{}", tr!("serial.listed", count = ports.len()));
+    Ok(ports)
+}
+
+/// 通过串口发送16进制数据命令
+///
+/// 返回响应的16进制字符串。传入 `request_id` 时可取消并推送进度事件
+#[tauri::command]
+pub async fn send_hex_serial(
+    app: AppHandle,
+    registry: State<'_, RequestRegistry>,
+    settings: SerialSettings,
+    data: String,
+    timeout_ms: Option<u64>,
+    request_id: Option<String>,
+) -> Result<String, NetworkError> {
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.sendHexSerial",
+            settings = format!("{:?}", settings),
+            timeout = format!("{:?}", timeout_ms),
+            requestId = format!("{:?}", request_id)
+        )
+    );
+    info!(
+        "{}",
+        tr!("cmd.sendPrepare", address = settings.port_name, length = data.len())
+    );
+
+    let (control, _registered) = track_request(&app, &registry, request_id.as_deref());
+    let start_time = std::time::Instant::now();
+    let result = serial::send_hex_serial(&settings, &data, timeout_ms, &control).await;
+    let elapsed = start_time.elapsed();
+
+    match &result {
+        Ok(response) => info!(
+            "{}",
+            tr!(
+                "cmd.sendSucceeded",
+                address = settings.port_name,
+                length = response.len(),
+                elapsed = elapsed.as_millis()
+            )
+        ),
+        Err(e) => error!(
+            "{}",
+            tr!(
+                "cmd.sendFailed",
+                address = settings.port_name,
+                error = e,
+                elapsed = elapsed.as_millis()
+            )
+        ),
+    }
+
+    result
+}
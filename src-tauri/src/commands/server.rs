@@ -0,0 +1,67 @@
+// 设备接入服务命令
+
+use crate::i18n::tr;
+use crate::network::{DeviceServer, InboundDeviceInfo, NetworkError, ServerInfo};
+use log::{debug, error, info};
+use tauri::State;
+
+/// 接入设备上线或下线事件名称
+pub const INBOUND_DEVICE_EVENT: &str = "inbound-device";
+
+/// 启动设备接入服务
+///
+/// 在指定端口接受设备主动连入，已在运行时按新参数重新启动
+#[tauri::command]
+pub async fn start_device_server(
+    server: State<'_, DeviceServer>,
+    bind_address: Option<String>,
+    port: u16,
+    identify_timeout_ms: Option<u64>,
+) -> Result<ServerInfo, NetworkError> {
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.startDeviceServer",
+            address = format!("{:?}", bind_address),
+            port = port,
+            timeout = format!("{:?}", identify_timeout_ms)
+        )
+    );
+
+    let bind_address = bind_address.unwrap_or_else(|| "0.0.0.0".to_string());
+    match server.start(&bind_address, port, identify_timeout_ms).await {
+        Ok(info) => {
+            info!("{}", tr!("server.started", address = info.local_addr));
+            Ok(info)
+        }
+        Err(e) => {
+            error!("{}", tr!("server.startFailed", error = e));
+            Err(e)
+        }
+    }
+}
+
+/// 停止设备接入服务并断开全部接入设备
+#[tauri::command]
+pub fn stop_device_server(server: State<'_, DeviceServer>) -> bool {
+    debug!("{}", tr!("cmd.called", command = "stop_device_server"));
+    let stopped = server.stop();
+    if stopped {
+        info!("{}", tr!("server.stopped"));
+    }
+    stopped
+}
+
+/// 获取设备接入服务的运行状态，未运行时返回null
+#[tauri::command]
+pub fn get_device_server(server: State<'_, DeviceServer>) -> Option<ServerInfo> {
+    debug!("{}", tr!("cmd.called", command = "get_device_server"));
+    server.info()
+}
+
+/// 列出当前连入的设备
+#[tauri::command]
+pub fn list_inbound_devices(server: State<'_, DeviceServer>) -> Vec<InboundDeviceInfo> {
+    debug!("{}", tr!("cmd.called", command = "list_inbound_devices"));
+    server.list()
+}
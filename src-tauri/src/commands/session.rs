@@ -0,0 +1,129 @@
+// 设备长连接会话命令
+
+use super::request::track_request;
+use crate::i18n::tr;
+use crate::network::address::format_host_port;
+use crate::network::{NetworkError, RequestRegistry, SessionInfo, SessionManager};
+use log::{debug, error, info};
+use tauri::{AppHandle, State};
+
+/// 会话状态变化事件名称
+pub const SESSION_STATE_EVENT: &str = "session-state";
+
+/// 打开设备会话
+///
+/// 建立一个命名的长连接，后续请求复用该连接
+#[tauri::command]
+pub async fn open_session(
+    manager: State<'_, SessionManager>,
+    name: String,
+    ip: String,
+    port: u16,
+    timeout_ms: Option<u64>,
+) -> Result<SessionInfo, NetworkError> {
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.openSession",
+            name = name,
+            ip = ip,
+            port = port,
+            timeout = format!("{:?}", timeout_ms)
+        )
+    );
+
+    let start_time = std::time::Instant::now();
+    match manager.open(&name, &ip, port, timeout_ms).await {
+        Ok(session) => {
+            info!(
+                "{}",
+                tr!(
+                    "session.opened",
+                    name = name,
+                    address = format_host_port(&ip, port),
+                    elapsed = start_time.elapsed().as_millis()
+                )
+            );
+            Ok(session)
+        }
+        Err(e) => {
+            error!(
+                "{}",
+                tr!(
+                    "session.openFailed",
+                    name = name,
+                    error = e,
+                    elapsed = start_time.elapsed().as_millis()
+                )
+            );
+            Err(e)
+        }
+    }
+}
+
+/// 在会话上发送16进制数据
+///
+/// 连接断开时自动重连，返回响应的16进制字符串。传入 `request_id` 时可取消并推送进度事件
+#[tauri::command]
+pub async fn send_on_session(
+    app: AppHandle,
+    manager: State<'_, SessionManager>,
+    registry: State<'_, RequestRegistry>,
+    name: String,
+    data: String,
+    request_id: Option<String>,
+) -> Result<String, NetworkError> {
+    debug!(
+        "{}",
+        tr!(
+            "cmd.call.sendOnSession",
+            name = name,
+            length = data.len(),
+            requestId = format!("{:?}", request_id)
+        )
+    );
+
+    let (control, _registered) = track_request(&app, &registry, request_id.as_deref());
+    let start_time = std::time::Instant::now();
+    let result = manager.send(&name, &data, &control).await;
+    let elapsed = start_time.elapsed();
+
+    match &result {
+        Ok(response) => info!(
+            "{}",
+            tr!(
+                "session.response",
+                name = name,
+                length = response.len(),
+                elapsed = elapsed.as_millis()
+            )
+        ),
+        Err(e) => error!(
+            "{}",
+            tr!("session.sendFailed", name = name, error = e, elapsed = elapsed.as_millis())
+        ),
+    }
+
+    result
+}
+
+/// 关闭设备会话
+#[tauri::command]
+pub async fn close_session(
+    manager: State<'_, SessionManager>,
+    name: String,
+) -> Result<(), NetworkError> {
+    debug!("{}", tr!("cmd.call.closeSession", name = name));
+
+    manager.close(&name).await.map_err(|e| {
+        error!("{}", tr!("session.closeFailed", name = name, error = e));
+        e
+    })
+}
+
+/// 列出全部设备会话
+#[tauri::command]
+pub fn list_sessions(manager: State<'_, SessionManager>) -> Vec<SessionInfo> {
+    debug!("{}", tr!("cmd.called", command = "list_sessions"));
+    manager.list()
+}
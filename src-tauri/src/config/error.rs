@@ -0,0 +1,80 @@
+// 应用配置错误定义
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::i18n::tr;
+use crate::inventory::InventoryError;
+use crate::layout::LayoutError;
+
+/// 应用配置校验、升级或存储失败的原因
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ConfigError {
+    /// 配置由更新版本的程序保存，当前程序无法识别
+    UnsupportedVersion { version: u64, current: u64 },
+    /// 配置内容不是有效的JSON对象或字段类型错误
+    InvalidDocument { message: String },
+    /// 配置内容不是JSON对象
+    NotAnObject,
+    /// 版本号不是正整数
+    InvalidVersion { value: String },
+    /// 默认设备地址为空
+    EmptyAddress,
+    /// 默认端口为0
+    InvalidPort,
+    /// 默认分机地址不在1-99范围内
+    InvalidSubDeviceAddress { value: u8 },
+    /// 超时时间为0
+    InvalidTimeout,
+    /// 温度区间的下限不低于上限
+    InvalidTemperatureRange { index: usize },
+    /// 卡滞判定的连续读数次数小于最小值
+    InvalidStuckReadingCount { value: usize, minimum: usize },
+    /// 测温点布局无效
+    Layout { error: LayoutError },
+    /// 站点台账无效
+    Inventory { error: InventoryError },
+    /// 读写配置文件失败
+    Storage { path: String, message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ConfigError::UnsupportedVersion { version, current } => {
+                tr!("config.unsupportedVersion", version = version, current = current)
+            }
+            ConfigError::InvalidDocument { message } => tr!("config.invalidDocument", message = message),
+            ConfigError::NotAnObject => tr!("config.notAnObject"),
+            ConfigError::InvalidVersion { value } => tr!("config.invalidVersion", value = value),
+            ConfigError::EmptyAddress => tr!("config.emptyAddress"),
+            ConfigError::InvalidPort => tr!("config.invalidPort"),
+            ConfigError::InvalidSubDeviceAddress { value } => tr!("config.invalidSubDeviceAddress", value = value),
+            ConfigError::InvalidTimeout => tr!("config.invalidTimeout"),
+            ConfigError::InvalidTemperatureRange { index } => {
+                tr!("config.invalidTemperatureRange", index = index + 1)
+            }
+            ConfigError::InvalidStuckReadingCount { value, minimum } => {
+                tr!("config.invalidStuckReadingCount", value = value, minimum = minimum)
+            }
+            ConfigError::Layout { error } => error.to_string(),
+            ConfigError::Inventory { error } => error.to_string(),
+            ConfigError::Storage { path, message } => tr!("config.storage", path = path, error = message),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<LayoutError> for ConfigError {
+    fn from(error: LayoutError) -> Self {
+        ConfigError::Layout { error }
+    }
+}
+
+impl From<InventoryError> for ConfigError {
+    fn from(error: InventoryError) -> Self {
+        ConfigError::Inventory { error }
+    }
+}
@@ -0,0 +1,190 @@
+// 配置格式升级
+use serde_json::{Map, Value};
+
+use super::ConfigError;
+use crate::layout::GranaryLayout;
+
+/// 把配置从上一版本升级到下一版本的函数
+type Migration = fn(&mut Map<String, Value>);
+
+/// 按版本排列的升级函数，第N条把配置从版本N升级到N+1
+///
+/// 版本1为前端保存在LocalStorage中的格式，没有版本号字段
+const MIGRATIONS: &[Migration] = &[
+    // 版本2：按网络、测温点布局和界面显示分组
+    group_local_storage_fields,
+];
+
+/// 当前配置版本
+pub const CURRENT_VERSION: u64 = MIGRATIONS.len() as u64 + 1;
+
+/// 把配置升级到当前版本
+///
+/// # 参数
+///
+/// * `document` - 配置内容，没有版本号时按版本1处理
+///
+/// # 返回值
+///
+/// 升级后的配置和升级前的版本
+pub fn migrate(document: Value) -> Result<(Value, u64), ConfigError> {
+    let Value::Object(mut object) = document else {
+        return Err(ConfigError::NotAnObject);
+    };
+    let version = match object.get("version") {
+        None | Some(Value::Null) => 1,
+        Some(value) => value.as_u64().filter(|version| *version >= 1).ok_or_else(|| {
+            ConfigError::InvalidVersion {
+                value: value.to_string(),
+            }
+        })?,
+    };
+    if version > CURRENT_VERSION {
+        return Err(ConfigError::UnsupportedVersion {
+            version,
+            current: CURRENT_VERSION,
+        });
+    }
+    for migration in MIGRATIONS.iter().skip(version as usize - 1) {
+        migration(&mut object);
+    }
+    object.insert("version".to_string(), CURRENT_VERSION.into());
+    Ok((Value::Object(object), version))
+}
+
+/// 版本1到版本2：LocalStorage中的平铺字段按用途分组，默认层数、行数和列数转换为默认测温点布局
+fn group_local_storage_fields(object: &mut Map<String, Value>) {
+    let mut network = Map::new();
+    move_field(object, "defaultIp", &mut network, "defaultIp");
+    move_number(object, "defaultPort", &mut network, "defaultPort");
+    move_number(object, "defaultSubDeviceAddr", &mut network, "defaultSubDeviceAddress");
+    move_number(object, "timeout", &mut network, "timeoutMs");
+    move_field(object, "defaultSendData", &mut network, "defaultSendData");
+
+    // 测温点布局的字段都是必填的，缺少的字段保留默认值
+    let mut layout = match serde_json::to_value(GranaryLayout::default()) {
+        Ok(Value::Object(layout)) => layout,
+        _ => Map::new(),
+    };
+    move_number(object, "defaultRows", &mut layout, "cableRows");
+    move_number(object, "defaultColumns", &mut layout, "cableColumns");
+    move_number(object, "defaultLayers", &mut layout, "pointsPerCable");
+    let mut layouts = Map::new();
+    layouts.insert("default".to_string(), Value::Object(layout));
+
+    // 前端保存时正负无穷大被转换为null，与不限上下限的含义一致
+    let mut display = Map::new();
+    if let Some(Value::Object(mut temperature)) = object.remove("temperatureConfig") {
+        move_field(&mut temperature, "ranges", &mut display, "temperatureRanges");
+        move_field(&mut temperature, "invalid", &mut display, "invalid");
+    }
+    move_field(object, "sampleResponseData", &mut display, "sampleResponseData");
+
+    object.insert("network".to_string(), Value::Object(network));
+    object.insert("layouts".to_string(), Value::Object(layouts));
+    object.insert("display".to_string(), Value::Object(display));
+}
+
+/// 把字段移动到新位置，原字段不存在或为null时不做处理
+fn move_field(from: &mut Map<String, Value>, key: &str, to: &mut Map<String, Value>, new_key: &str) {
+    if let Some(value) = from.remove(key).filter(|value| !value.is_null()) {
+        to.insert(new_key.to_string(), value);
+    }
+}
+
+/// 移动数值字段，前端输入框保存的数字字符串转换为数值，无法转换时丢弃并使用默认值
+fn move_number(from: &mut Map<String, Value>, key: &str, to: &mut Map<String, Value>, new_key: &str) {
+    let value = match from.remove(key) {
+        Some(Value::Number(number)) => number.as_u64(),
+        Some(Value::String(text)) => text.trim().parse().ok(),
+        _ => None,
+    };
+    if let Some(value) = value {
+        to.insert(new_key.to_string(), value.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use serde_json::json;
+
+    #[test]
+    fn migrates_local_storage_config() {
+        // 前端输入框保存的数字为字符串，正负无穷大保存为null
+        let document = json!({
+            "defaultIp": "192.168.1.10",
+            "defaultPort": "2000",
+            "defaultSubDeviceAddr": " 3 ",
+            "timeout": 5000,
+            "defaultSendData": null,
+            "defaultLayers": "4",
+            "defaultRows": "5",
+            "defaultColumns": "abc",
+            "sampleResponseData": null,
+            "temperatureConfig": {
+                "ranges": [
+                    {"min": null, "max": 12, "color": "#008000", "textColor": "#FFFFFF", "name": "低温"},
+                    {"min": 12, "max": null, "color": "#FF0000", "textColor": "#FFFFFF", "name": "高温"}
+                ],
+                "invalid": {"color": "#CFCFCF", "textColor": "#000000", "name": "故障"}
+            }
+        });
+
+        let (document, version) = migrate(document).unwrap();
+        assert_eq!(version, 1);
+        assert_eq!(document["version"], CURRENT_VERSION);
+        let config: AppConfig = serde_json::from_value(document).unwrap();
+        config.validate().unwrap();
+
+        assert_eq!(config.network.default_ip, "192.168.1.10");
+        assert_eq!(config.network.default_port, 2000);
+        assert_eq!(config.network.default_sub_device_address, 3);
+        assert_eq!(config.network.timeout_ms, 5000);
+        assert_eq!(config.network.default_send_data, None);
+        let layout = &config.layouts.default;
+        assert_eq!(layout.points_per_cable, 4);
+        assert_eq!(layout.cable_rows, 5);
+        // 无法转换的数字保留默认值
+        assert_eq!(layout.cable_columns, GranaryLayout::default().cable_columns);
+        let ranges = &config.display.temperature_ranges;
+        assert_eq!(ranges.len(), 2);
+        assert_eq!((ranges[0].min, ranges[0].max), (None, Some(12.0)));
+        assert_eq!((ranges[1].min, ranges[1].max), (Some(12.0), None));
+        assert_eq!(config.display.invalid.name, "故障");
+        assert_eq!(config.display.sample_response_data, None);
+    }
+
+    #[test]
+    fn keeps_current_version() {
+        let document = serde_json::to_value(AppConfig::default()).unwrap();
+        let (migrated, version) = migrate(document.clone()).unwrap();
+        assert_eq!(version, CURRENT_VERSION);
+        assert_eq!(migrated, document);
+    }
+
+    #[test]
+    fn rejects_newer_version() {
+        let document = json!({"version": CURRENT_VERSION + 1});
+        assert_eq!(
+            migrate(document).unwrap_err(),
+            ConfigError::UnsupportedVersion {
+                version: CURRENT_VERSION + 1,
+                current: CURRENT_VERSION,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_documents() {
+        assert_eq!(migrate(json!([1, 2])).unwrap_err(), ConfigError::NotAnObject);
+        assert_eq!(migrate(json!("config")).unwrap_err(), ConfigError::NotAnObject);
+        assert_eq!(
+            migrate(json!({"version": 0})).unwrap_err(),
+            ConfigError::InvalidVersion {
+                value: "0".to_string(),
+            }
+        );
+    }
+}
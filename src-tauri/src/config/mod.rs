@@ -0,0 +1,303 @@
+// 应用配置模块，在数据目录中保存带版本号的配置文件，程序升级时自动升级配置格式
+use log::{error, info, warn};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::i18n::tr;
+
+// 导出错误定义
+pub mod error;
+// 导出配置格式升级模块
+pub mod migrate;
+// 导出配置定义模块
+pub mod model;
+
+pub use error::ConfigError;
+pub use model::AppConfig;
+
+/// 配置中的分组
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfigSection {
+    /// 网络通信默认参数
+    Network,
+    /// 测温点布局
+    Layouts,
+    /// 界面显示设置
+    Display,
+    /// 界面语言
+    Locale,
+    /// 站点台账
+    Inventory,
+    /// 历史数据设置
+    History,
+}
+
+/// 配置事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ConfigEvent {
+    /// 配置已保存，`sections` 为内容有变化的分组
+    Changed {
+        config: AppConfig,
+        sections: Vec<ConfigSection>,
+    },
+}
+
+type Listener = Box<dyn Fn(&ConfigEvent) + Send + Sync>;
+
+struct Shared {
+    path: PathBuf,
+    config: Mutex<AppConfig>,
+    listener: Listener,
+}
+
+/// 应用配置，保存在Tauri托管状态中，可克隆后在其他模块中使用
+#[derive(Clone)]
+pub struct ConfigStore {
+    shared: Arc<Shared>,
+}
+
+impl ConfigStore {
+    /// 读取配置文件，配置变化时调用 `listener`
+    ///
+    /// 文件不存在时使用默认配置；旧版本的配置升级后立即写回；
+    /// 文件无法解析、版本过新或内容无效时改名为 `.broken` 保留并使用默认配置，避免之后保存时覆盖
+    ///
+    /// # 参数
+    ///
+    /// * `path` - 配置文件路径，所在目录在首次写入时创建
+    /// * `listener` - 配置事件回调
+    pub fn with_listener<F>(path: &Path, listener: F) -> Self
+    where
+        F: Fn(&ConfigEvent) + Send + Sync + 'static,
+    {
+        let config = load(path).unwrap_or_else(|e| {
+            error!("{}", tr!("config.loadFailed", error = e));
+            AppConfig::default()
+        });
+        ConfigStore {
+            shared: Arc::new(Shared {
+                path: path.to_path_buf(),
+                config: Mutex::new(config),
+                listener: Box::new(listener),
+            }),
+        }
+    }
+
+    /// 当前配置
+    pub fn get(&self) -> AppConfig {
+        lock(&self.shared.config).clone()
+    }
+
+    /// 读取配置中的部分内容，避免复制整个配置
+    pub fn read<R>(&self, f: impl FnOnce(&AppConfig) -> R) -> R {
+        f(&lock(&self.shared.config))
+    }
+
+    /// 升级并校验配置内容，不保存
+    ///
+    /// # 返回值
+    ///
+    /// 升级到当前版本后的配置
+    pub fn validate(document: Value) -> Result<AppConfig, ConfigError> {
+        parse(document).map(|(config, _)| config)
+    }
+
+    /// 升级、校验并保存全部配置，旧版本的配置（如前端LocalStorage中的配置）按当前版本保存
+    pub fn set(&self, document: Value) -> Result<AppConfig, ConfigError> {
+        let config = Self::validate(document)?;
+        self.commit(lock(&self.shared.config), config)
+    }
+
+    /// 修改并保存配置，修改期间持有锁，避免同时修改时丢失其中一次修改
+    ///
+    /// `f` 返回错误时放弃修改
+    pub fn update<F>(&self, f: F) -> Result<AppConfig, ConfigError>
+    where
+        F: FnOnce(&mut AppConfig) -> Result<(), ConfigError>,
+    {
+        let current = lock(&self.shared.config);
+        let mut config = current.clone();
+        f(&mut config)?;
+        config.validate()?;
+        self.commit(current, config)
+    }
+
+    /// 保存配置并替换当前配置，内容有变化时推送事件
+    fn commit(&self, mut current: MutexGuard<'_, AppConfig>, mut config: AppConfig) -> Result<AppConfig, ConfigError> {
+        config.version = migrate::CURRENT_VERSION;
+        let sections = changed_sections(&current, &config);
+        save(&self.shared.path, &config)?;
+        *current = config.clone();
+        drop(current);
+
+        info!("{}", tr!("config.sectionsSaved", sections = format!("{:?}", sections)));
+        if !sections.is_empty() {
+            (self.shared.listener)(&ConfigEvent::Changed {
+                config: config.clone(),
+                sections,
+            });
+        }
+        Ok(config)
+    }
+}
+
+/// 读取配置文件
+fn load(path: &Path) -> Result<AppConfig, ConfigError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            info!("{}", tr!("config.defaultUsed", path = path.display()));
+            return Ok(AppConfig::default());
+        }
+        Err(e) => return Err(storage_error(path, e)),
+    };
+    let parsed = serde_json::from_str(&content)
+        .map_err(|e| ConfigError::InvalidDocument { message: e.to_string() })
+        .and_then(parse);
+    match parsed {
+        Ok((config, version)) => {
+            if version < migrate::CURRENT_VERSION {
+                save(path, &config)?;
+                info!("{}", tr!("config.migrated", from = version, to = migrate::CURRENT_VERSION));
+            }
+            info!("{}", tr!("config.loaded", path = path.display()));
+            Ok(config)
+        }
+        Err(e) => {
+            let backup = path.with_extension("json.broken");
+            warn!(
+                "{}",
+                tr!("config.backedUp", path = path.display(), error = e, backup = backup.display())
+            );
+            fs::rename(path, &backup).map_err(|e| storage_error(path, e))?;
+            Ok(AppConfig::default())
+        }
+    }
+}
+
+/// 升级、解析并校验配置内容，同时返回升级前的版本
+fn parse(document: Value) -> Result<(AppConfig, u64), ConfigError> {
+    let (document, version) = migrate::migrate(document)?;
+    let config: AppConfig = serde_json::from_value(document).map_err(|e| ConfigError::InvalidDocument {
+        message: e.to_string(),
+    })?;
+    config.validate()?;
+    Ok((config, version))
+}
+
+/// 先写入临时文件再替换，避免写入中断时损坏原文件
+fn save(path: &Path, config: &AppConfig) -> Result<(), ConfigError> {
+    let content = serde_json::to_string_pretty(config).map_err(|e| storage_error(path, e))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| storage_error(dir, e))?;
+    }
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, content).map_err(|e| storage_error(&temp, e))?;
+    fs::rename(&temp, path).map_err(|e| storage_error(path, e))
+}
+
+/// 比较两份配置，返回内容有变化的分组
+fn changed_sections(before: &AppConfig, after: &AppConfig) -> Vec<ConfigSection> {
+    let mut sections = Vec::new();
+    if before.network != after.network {
+        sections.push(ConfigSection::Network);
+    }
+    if before.layouts != after.layouts {
+        sections.push(ConfigSection::Layouts);
+    }
+    if before.display != after.display {
+        sections.push(ConfigSection::Display);
+    }
+    if before.locale != after.locale {
+        sections.push(ConfigSection::Locale);
+    }
+    if before.inventory != after.inventory {
+        sections.push(ConfigSection::Inventory);
+    }
+    if before.history != after.history {
+        sections.push(ConfigSection::History);
+    }
+    sections
+}
+
+/// 生成存储错误
+fn storage_error(path: &Path, error: impl std::fmt::Display) -> ConfigError {
+    ConfigError::Storage {
+        path: path.display().to_string(),
+        message: error.to_string(),
+    }
+}
+
+/// 获取锁，锁中毒时继续使用内部数据
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Locale;
+
+    fn temp_config(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("config-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("config.json")
+    }
+
+    #[test]
+    fn backs_up_broken_config() {
+        let path = temp_config("broken");
+        fs::write(&path, "{ not json").unwrap();
+
+        let config = load(&path).unwrap();
+
+        assert_eq!(config, AppConfig::default());
+        assert!(!path.exists());
+        let backup = path.with_extension("json.broken");
+        assert_eq!(fs::read_to_string(backup).unwrap(), "{ not json");
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn saves_migrated_config() {
+        let path = temp_config("migrated");
+        fs::write(&path, r#"{"defaultIp": "10.0.0.2", "defaultPort": "2001"}"#).unwrap();
+
+        let config = load(&path).unwrap();
+
+        assert_eq!(config.network.default_ip, "10.0.0.2");
+        assert_eq!(config.network.default_port, 2001);
+        let saved: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["version"], migrate::CURRENT_VERSION);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn keeps_saved_locale() {
+        let path = temp_config("locale");
+        let store = ConfigStore::with_listener(&path, |_| {});
+        assert_eq!(store.read(|config| config.locale), Locale::ZhCn);
+
+        store
+            .update(|config| {
+                config.locale = Locale::EnUs;
+                Ok(())
+            })
+            .unwrap();
+
+        let saved: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["locale"], "en-US");
+        assert_eq!(load(&path).unwrap().locale, Locale::EnUs);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
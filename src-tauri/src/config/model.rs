@@ -0,0 +1,192 @@
+// 应用配置定义
+use serde::{Deserialize, Serialize};
+
+use super::migrate::CURRENT_VERSION;
+use super::ConfigError;
+use crate::history::{HistoryConfig, MIN_STUCK_READING_COUNT};
+use crate::i18n::Locale;
+use crate::inventory::InventoryConfig;
+use crate::layout::{GranaryLayout, LayoutConfig};
+
+/// 应用配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppConfig {
+    /// 配置格式版本，保存时总是写入当前版本
+    pub version: u64,
+    /// 网络通信默认参数
+    #[serde(default)]
+    pub network: NetworkConfig,
+    /// 测温点布局
+    #[serde(default)]
+    pub layouts: LayoutConfig,
+    /// 界面显示设置
+    #[serde(default)]
+    pub display: DisplayConfig,
+    /// 界面语言，影响错误信息、排查建议、日志和崩溃报告，未保存过时为简体中文
+    #[serde(default)]
+    pub locale: Locale,
+    /// 站点台账
+    #[serde(default)]
+    pub inventory: InventoryConfig,
+    /// 历史数据设置
+    #[serde(default)]
+    pub history: HistoryConfig,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        AppConfig {
+            version: CURRENT_VERSION,
+            network: NetworkConfig::default(),
+            layouts: LayoutConfig::default(),
+            display: DisplayConfig::default(),
+            locale: Locale::default(),
+            inventory: InventoryConfig::default(),
+            history: HistoryConfig::default(),
+        }
+    }
+}
+
+/// 网络通信默认参数，请求中未指定时使用
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NetworkConfig {
+    /// 默认设备地址
+    pub default_ip: String,
+    /// 默认端口
+    pub default_port: u16,
+    /// 默认分机地址（1-99）
+    pub default_sub_device_address: u8,
+    /// 超时时间（毫秒）
+    pub timeout_ms: u64,
+    /// 补发缺失包的最大轮数
+    pub max_retries: u32,
+    /// 默认发送的16进制数据，为None时按分机地址生成
+    pub default_send_data: Option<String>,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        NetworkConfig {
+            default_ip: "127.0.0.1".to_string(),
+            default_port: 2000,
+            default_sub_device_address: 1,
+            timeout_ms: 10_000,
+            max_retries: 2,
+            default_send_data: None,
+        }
+    }
+}
+
+/// 温度的显示样式
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemperatureStyle {
+    /// 背景颜色
+    pub color: String,
+    /// 文字颜色
+    pub text_color: String,
+    /// 名称
+    pub name: String,
+}
+
+/// 温度区间及其显示样式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemperatureRange {
+    /// 下限（°C，含），为None时不限
+    pub min: Option<f64>,
+    /// 上限（°C，不含），为None时不限
+    pub max: Option<f64>,
+    #[serde(flatten)]
+    pub style: TemperatureStyle,
+}
+
+/// 界面显示设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DisplayConfig {
+    /// 温度区间，按温度从低到高排列
+    pub temperature_ranges: Vec<TemperatureRange>,
+    /// 故障或无效数据的显示样式
+    pub invalid: TemperatureStyle,
+    /// 示例响应数据
+    pub sample_response_data: Option<String>,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        DisplayConfig {
+            temperature_ranges: vec![
+                temperature_range(None, Some(12.0), "#008000", "#FFFFFF", "低温"),
+                temperature_range(Some(12.0), Some(16.0), "#FFD700", "#000000", "正常"),
+                temperature_range(Some(16.0), Some(30.0), "#8B4513", "#FFFFFF", "偏高"),
+                temperature_range(Some(30.0), None, "#FF0000", "#FFFFFF", "高温"),
+            ],
+            invalid: temperature_style("#CFCFCF", "#000000", "故障"),
+            sample_response_data: None,
+        }
+    }
+}
+
+impl AppConfig {
+    /// 校验全部配置
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let network = &self.network;
+        if network.default_ip.trim().is_empty() {
+            return Err(ConfigError::EmptyAddress);
+        }
+        if network.default_port == 0 {
+            return Err(ConfigError::InvalidPort);
+        }
+        if !(1..=99).contains(&network.default_sub_device_address) {
+            return Err(ConfigError::InvalidSubDeviceAddress {
+                value: network.default_sub_device_address,
+            });
+        }
+        if network.timeout_ms == 0 {
+            return Err(ConfigError::InvalidTimeout);
+        }
+        for (index, range) in self.display.temperature_ranges.iter().enumerate() {
+            if let (Some(min), Some(max)) = (range.min, range.max) {
+                if min >= max {
+                    return Err(ConfigError::InvalidTemperatureRange { index });
+                }
+            }
+        }
+        if self.history.stuck_reading_count < MIN_STUCK_READING_COUNT {
+            return Err(ConfigError::InvalidStuckReadingCount {
+                value: self.history.stuck_reading_count,
+                minimum: MIN_STUCK_READING_COUNT,
+            });
+        }
+        self.layouts.validate()?;
+        self.inventory.validate()?;
+        Ok(())
+    }
+
+    /// 粮仓使用的测温点布局：台账中仓房设置的布局优先，其次为测温点布局设置中该粮仓单独设置的布局，最后为默认布局
+    pub fn layout_for(&self, granary: &str) -> &GranaryLayout {
+        self.inventory
+            .warehouse(granary)
+            .and_then(|warehouse| warehouse.layout.as_ref())
+            .unwrap_or_else(|| self.layouts.for_granary(granary))
+    }
+}
+
+fn temperature_range(min: Option<f64>, max: Option<f64>, color: &str, text_color: &str, name: &str) -> TemperatureRange {
+    TemperatureRange {
+        min,
+        max,
+        style: temperature_style(color, text_color, name),
+    }
+}
+
+fn temperature_style(color: &str, text_color: &str, name: &str) -> TemperatureStyle {
+    TemperatureStyle {
+        color: color.to_string(),
+        text_color: text_color.to_string(),
+        name: name.to_string(),
+    }
+}
@@ -5,6 +5,7 @@ use chrono::{DateTime, Local};
 use std::backtrace::Backtrace;
 use std::panic;
 use log::error;
+use crate::i18n::tr;
 use crate::logger;
 
 /// 获取崩溃日志目录 - 使用与普通日志相同的目录
@@ -30,7 +31,7 @@ pub fn init_crash_handler() {
     let crash_dir = get_crash_log_dir();
     if !crash_dir.exists() {
         if let Err(e) = fs::create_dir_all(&crash_dir) {
-            error!("创建崩溃日志目录失败: {}", e);
+            error!("{}", tr!("crash.dirFailed", error = e));
             return;
         }
     }
@@ -51,27 +52,27 @@ pub fn init_crash_handler() {
             .open(&crash_file_path)
         {
             // 写入崩溃时间
-            let _ = writeln!(file, "崩溃时间: {}", current_time.format("%Y-%m-%d %H:%M:%S%.3f"));
+            let _ = writeln!(file, "{}: {}", tr!("crash.time"), current_time.format("%Y-%m-%d %H:%M:%S%.3f"));
             
             // 写入panic信息
-            let _ = writeln!(file, "\n崩溃信息:");
+            let _ = writeln!(file, "\n{}:", tr!("crash.message"));
             let _ = writeln!(file, "{}",  panic_info);
             
             // 写入发生位置
             if let Some(location) = panic_info.location() {
-                let _ = writeln!(file, "\n发生位置:");
-                let _ = writeln!(file, "文件: {}", location.file());
-                let _ = writeln!(file, "行号: {}", location.line());
-                let _ = writeln!(file, "列号: {}", location.column());
+                let _ = writeln!(file, "\n{}:", tr!("crash.location"));
+                let _ = writeln!(file, "{}: {}", tr!("crash.file"), location.file());
+                let _ = writeln!(file, "{}: {}", tr!("crash.line"), location.line());
+                let _ = writeln!(file, "{}: {}", tr!("crash.column"), location.column());
             }
             
             // 写入调用栈
-            let _ = writeln!(file, "\n调用栈:");
+            let _ = writeln!(file, "\n{}:", tr!("crash.backtrace"));
             let _ = writeln!(file, "{}", backtrace);
             
-            error!("应用程序崩溃，崩溃日志已保存到: {}", crash_file_path.display());
+            error!("{}", tr!("crash.saved", path = crash_file_path.display()));
         } else {
-            error!("无法创建崩溃日志文件: {}", crash_file_path.display());
+            error!("{}", tr!("crash.createFailed", path = crash_file_path.display()));
         }
     }));
 }
@@ -0,0 +1,98 @@
+// 读数导出为CSV，每个测温点一行
+use std::fmt::Write as _;
+
+use super::table::{sensor_cells, status_name};
+use crate::history::HistoryReading;
+use crate::i18n::tr;
+
+/// Excel按UTF-8打开CSV需要的字节顺序标记
+const UTF8_BOM: &str = "\u{feff}";
+
+/// 生成CSV内容，每次读取的每个测温点一行，附带该次读取的仓内外温湿度
+///
+/// # 参数
+///
+/// * `readings` - 带测温点读数和坐标的历史记录，按采样时间排列
+///
+/// # 返回值
+///
+/// CSV内容和数据行数
+pub fn render(readings: &[HistoryReading]) -> (String, usize) {
+    let mut content = String::from(UTF8_BOM);
+    let header = [
+        tr!("export.column.readingId"),
+        tr!("export.column.granary"),
+        tr!("export.column.subDeviceAddress"),
+        tr!("export.column.sampledAt"),
+        tr!("export.column.sensorId"),
+        tr!("export.column.layer"),
+        tr!("export.column.row"),
+        tr!("export.column.column"),
+        tr!("export.column.temperature"),
+        tr!("export.column.status"),
+        tr!("export.column.indoorTemperature"),
+        tr!("export.column.indoorHumidity"),
+        tr!("export.column.outdoorTemperature"),
+        tr!("export.column.outdoorHumidity"),
+    ];
+    write_row(&mut content, header.iter().map(String::as_str));
+
+    let mut rows = 0;
+    for reading in readings {
+        let environment = &reading.environment;
+        for cell in sensor_cells(reading) {
+            let position = cell.position;
+            let fields = [
+                reading.id.to_string(),
+                reading.granary.clone(),
+                reading.sub_device_address.to_string(),
+                reading.sampled_at.clone(),
+                cell.sensor_id.to_string(),
+                optional(position.map(|position| position.layer)),
+                optional(position.map(|position| position.row)),
+                optional(position.map(|position| position.column)),
+                optional(cell.temperature.map(|temperature| format!("{:.1}", temperature))),
+                status_name(cell.status),
+                optional(environment.indoor_temperature.map(|value| format!("{:.1}", value))),
+                optional(environment.indoor_humidity),
+                optional(environment.outdoor_temperature.map(|value| format!("{:.1}", value))),
+                optional(environment.outdoor_humidity),
+            ];
+            write_row(&mut content, fields.iter().map(String::as_str));
+            rows += 1;
+        }
+    }
+    (content, rows)
+}
+
+/// 写入一行，含逗号、引号或换行的字段加引号
+fn write_row<'a>(content: &mut String, fields: impl Iterator<Item = &'a str>) {
+    for (index, field) in fields.enumerate() {
+        if index > 0 {
+            content.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            let _ = write!(content, "\"{}\"", field.replace('"', "\"\""));
+        } else {
+            content.push_str(field);
+        }
+    }
+    content.push_str("\r\n");
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_fields_with_separators() {
+        let mut content = String::new();
+        write_row(&mut content, ["1号仓", "A,B", "say \"hi\"", "line\nbreak", "cr\rreturn", ""].into_iter());
+
+        assert_eq!(content, "1号仓,\"A,B\",\"say \"\"hi\"\"\",\"line\nbreak\",\"cr\rreturn\",\r\n");
+    }
+}
@@ -0,0 +1,37 @@
+// 读数导出错误定义
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::history::HistoryError;
+use crate::i18n::tr;
+
+/// 导出读数失败的原因
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ExportError {
+    /// 没有符合条件的记录
+    NoReadings,
+    /// 读取历史数据失败
+    History { error: HistoryError },
+    /// 生成或写入文件失败
+    Storage { path: String, message: String },
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ExportError::NoReadings => tr!("export.noReadings"),
+            ExportError::History { error } => error.to_string(),
+            ExportError::Storage { path, message } => tr!("export.storage", path = path, error = message),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<HistoryError> for ExportError {
+    fn from(error: HistoryError) -> Self {
+        ExportError::History { error }
+    }
+}
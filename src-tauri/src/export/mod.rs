@@ -0,0 +1,150 @@
+// 读数导出模块，把一次读取或一段时间内的读数导出为CSV或Excel文件
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::history::{HistoryError, HistoryQuery, HistoryReading, HistoryStore};
+use crate::i18n::tr;
+use crate::layout::{GranaryLayout, Layouts};
+
+// 导出CSV模块
+pub mod csv;
+// 导出错误定义
+pub mod error;
+// 导出表格共用的整理与统计模块
+pub mod table;
+// 导出Excel模块
+pub mod xlsx;
+
+pub use error::ExportError;
+
+/// 单次导出最多包含的记录数
+const MAX_EXPORT_READINGS: usize = 1000;
+
+/// 导出文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    /// CSV，每个测温点一行
+    Csv,
+    /// Excel，每层一个工作表，另有汇总工作表
+    Xlsx,
+}
+
+/// 导出范围，设置 `reading_id` 时只导出该次读取，否则按其余条件导出
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportQuery {
+    /// 历史记录编号
+    pub reading_id: Option<i64>,
+    /// 粮仓
+    pub granary: Option<String>,
+    /// 分机地址
+    pub sub_device_address: Option<u8>,
+    /// 起始时间（含），格式 "YYYY-MM-DD HH:MM:SS" 或 "YYYY-MM-DD"
+    pub from: Option<String>,
+    /// 结束时间（不含），格式同起始时间
+    pub to: Option<String>,
+}
+
+/// 导出结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportSummary {
+    /// 文件路径
+    pub path: String,
+    /// 文件格式
+    pub format: ExportFormat,
+    /// 导出的记录数量
+    pub reading_count: usize,
+}
+
+/// 按范围读取历史记录并导出到文件
+///
+/// # 参数
+///
+/// * `history` - 历史数据库
+/// * `layouts` - 测温点布局，按各记录的粮仓填写测温点坐标
+/// * `query` - 导出范围
+/// * `format` - 文件格式
+/// * `path` - 文件路径，已存在时覆盖
+pub fn export_readings(
+    history: &HistoryStore,
+    layouts: &Layouts,
+    query: &ExportQuery,
+    format: ExportFormat,
+    path: &Path,
+) -> Result<ExportSummary, ExportError> {
+    let readings = load_readings(history, layouts, query)?;
+    if readings.is_empty() {
+        return Err(ExportError::NoReadings);
+    }
+    let reading_count = readings.len();
+
+    match format {
+        ExportFormat::Csv => {
+            let readings: Vec<_> = readings.into_iter().map(|(reading, _)| reading).collect();
+            let (content, rows) = csv::render(&readings);
+            fs::write(path, content).map_err(|e| storage_error(path, e))?;
+            info!(
+                "{}",
+                tr!("export.writtenRows", count = reading_count, rows = rows, path = path.display())
+            );
+        }
+        ExportFormat::Xlsx => {
+            let mut workbook = xlsx::render(&readings).map_err(|e| storage_error(path, e))?;
+            workbook.save(path).map_err(|e| storage_error(path, e))?;
+            info!("{}", tr!("export.written", count = reading_count, path = path.display()));
+        }
+    }
+    Ok(ExportSummary {
+        path: path.display().to_string(),
+        format,
+        reading_count,
+    })
+}
+
+/// 按范围读取带测温点读数的历史记录，按采样时间从旧到新排列，并按各记录的粮仓填写测温点坐标
+///
+/// # 返回值
+///
+/// 历史记录及其粮仓的测温点布局，没有符合条件的记录时为空
+pub fn load_readings(
+    history: &HistoryStore,
+    layouts: &Layouts,
+    query: &ExportQuery,
+) -> Result<Vec<(HistoryReading, GranaryLayout)>, HistoryError> {
+    let mut readings = match query.reading_id {
+        Some(id) => vec![history.reading(id)?],
+        None => history.query(&HistoryQuery {
+            granary: query.granary.clone(),
+            sub_device_address: query.sub_device_address,
+            from: query.from.clone(),
+            to: query.to.clone(),
+            limit: Some(MAX_EXPORT_READINGS),
+            include_sensors: true,
+        })?,
+    };
+    if readings.len() == MAX_EXPORT_READINGS {
+        warn!("{}", tr!("export.truncated", limit = MAX_EXPORT_READINGS));
+    }
+    // 查询结果从新到旧排列，导出时按时间顺序排列
+    readings.reverse();
+    Ok(readings
+        .into_iter()
+        .map(|mut reading| {
+            let layout = layouts.for_granary(&reading.granary);
+            layout.locate_reading(&mut reading);
+            (reading, layout)
+        })
+        .collect())
+}
+
+/// 生成存储错误
+fn storage_error(path: &Path, error: impl std::fmt::Display) -> ExportError {
+    ExportError::Storage {
+        path: path.display().to_string(),
+        message: error.to_string(),
+    }
+}
@@ -0,0 +1,220 @@
+// 导出表格共用的测温点整理与分层统计
+use std::collections::{BTreeMap, HashMap};
+
+use crate::history::HistoryReading;
+use crate::i18n::tr;
+use crate::layout::{GranaryLayout, SensorPosition};
+use crate::protocol::SensorStatus;
+
+/// 一个测温点在一次读取中的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorCell {
+    /// 测温点编号
+    pub sensor_id: u16,
+    /// 坐标，编号超出布局时为None
+    pub position: Option<SensorPosition>,
+    /// 温度，断路、短路和缺失时为None
+    pub temperature: Option<f64>,
+    /// 状态
+    pub status: SensorStatus,
+}
+
+/// 一层或整仓的统计
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayerStats {
+    /// 层号，整仓统计为None
+    pub layer: Option<u16>,
+    /// 读数正常的测温点数量
+    pub valid_count: usize,
+    /// 最高温度
+    pub max: Option<f64>,
+    /// 最低温度
+    pub min: Option<f64>,
+    /// 平均温度
+    pub average: Option<f64>,
+    /// 异常的测温点，按编号排列
+    pub faults: Vec<SensorCell>,
+}
+
+/// 按测温点编号整理一次读取的读数和异常测温点
+///
+/// 记录需带有测温点读数和异常测温点，且已按粮仓的测温点布局填写坐标
+pub fn sensor_cells(reading: &HistoryReading) -> Vec<SensorCell> {
+    let mut cells: BTreeMap<u16, SensorCell> = BTreeMap::new();
+    for sensor in reading.sensors.iter().flatten() {
+        cells.insert(
+            sensor.sensor_id,
+            SensorCell {
+                sensor_id: sensor.sensor_id,
+                position: sensor.position,
+                temperature: Some(sensor.temperature),
+                status: SensorStatus::Valid,
+            },
+        );
+    }
+    // 卡滞的测温点同时出现在读数和异常中，保留读数并标记状态
+    for fault in reading.faults.iter().flatten() {
+        let cell = cells.entry(fault.sensor_id).or_insert(SensorCell {
+            sensor_id: fault.sensor_id,
+            position: fault.position,
+            temperature: fault.temperature,
+            status: fault.status,
+        });
+        cell.status = fault.status;
+    }
+    cells.into_values().collect()
+}
+
+/// 按坐标索引测温点
+pub fn cells_by_position(cells: &[SensorCell]) -> HashMap<SensorPosition, &SensorCell> {
+    cells
+        .iter()
+        .filter_map(|cell| cell.position.map(|position| (position, cell)))
+        .collect()
+}
+
+/// 整仓统计和按布局逐层统计，层号从上到下排列，没有测温点的层也列出
+pub fn layer_stats(cells: &[SensorCell], layout: &GranaryLayout) -> (LayerStats, Vec<LayerStats>) {
+    let overall = stats(None, cells.iter());
+    let layers = (1..=layout.points_per_cable)
+        .map(|layer| {
+            stats(
+                Some(layer),
+                cells
+                    .iter()
+                    .filter(|cell| cell.position.is_some_and(|position| position.layer == layer)),
+            )
+        })
+        .collect();
+    (overall, layers)
+}
+
+fn stats<'a>(layer: Option<u16>, cells: impl Iterator<Item = &'a SensorCell>) -> LayerStats {
+    let mut result = LayerStats {
+        layer,
+        ..LayerStats::default()
+    };
+    let mut sum = 0.0;
+    for cell in cells {
+        match (cell.status, cell.temperature) {
+            (SensorStatus::Valid, Some(temperature)) => {
+                result.valid_count += 1;
+                sum += temperature;
+                result.max = Some(result.max.map_or(temperature, |max| max.max(temperature)));
+                result.min = Some(result.min.map_or(temperature, |min| min.min(temperature)));
+            }
+            _ => result.faults.push(cell.clone()),
+        }
+    }
+    if result.valid_count > 0 {
+        result.average = Some(sum / result.valid_count as f64);
+    }
+    result
+}
+
+/// 测温点状态的显示名称
+pub fn status_name(status: SensorStatus) -> String {
+    match status {
+        SensorStatus::Valid => tr!("export.status.valid"),
+        SensorStatus::OpenCircuit => tr!("export.status.openCircuit"),
+        SensorStatus::ShortCircuit => tr!("export.status.shortCircuit"),
+        SensorStatus::OutOfRange => tr!("export.status.outOfRange"),
+        SensorStatus::Stuck => tr!("export.status.stuck"),
+        SensorStatus::Missing => tr!("export.status.missing"),
+    }
+}
+
+/// 异常测温点列表，如 "12(断路), 35(卡滞)"
+pub fn fault_list(faults: &[SensorCell]) -> String {
+    faults
+        .iter()
+        .map(|cell| format!("{}({})", cell.sensor_id, status_name(cell.status)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::model::WiringOrder;
+    use crate::protocol::{EnvironmentData, SensorFault, SensorReading};
+
+    fn position(layer: u16) -> Option<SensorPosition> {
+        Some(SensorPosition { layer, row: 1, column: 1 })
+    }
+
+    fn reading() -> HistoryReading {
+        let sensor = |sensor_id, layer, temperature| SensorReading {
+            sensor_id,
+            temperature,
+            position: position(layer),
+        };
+        HistoryReading {
+            id: 1,
+            granary: "1号仓".to_string(),
+            sub_device_address: 1,
+            source: "127.0.0.1:2000".to_string(),
+            sampled_at: "2024-05-01 08:00:00".to_string(),
+            device_time_valid: true,
+            received_at: "2024-05-01 08:00:00".to_string(),
+            packet_count: 1,
+            sensor_count: 3,
+            environment: EnvironmentData::default(),
+            sensors: Some(vec![sensor(1, 1, 20.0), sensor(3, 1, 24.0), sensor(2, 1, 40.0)]),
+            faults: Some(vec![
+                SensorFault {
+                    sensor_id: 2,
+                    status: SensorStatus::Stuck,
+                    raw: None,
+                    temperature: Some(40.0),
+                    position: position(1),
+                },
+                SensorFault {
+                    sensor_id: 4,
+                    status: SensorStatus::OpenCircuit,
+                    raw: Some(0xFFFF),
+                    temperature: None,
+                    position: position(2),
+                },
+            ]),
+            raw_frame: None,
+        }
+    }
+
+    #[test]
+    fn keeps_stuck_reading_but_marks_status() {
+        let cells = sensor_cells(&reading());
+
+        assert_eq!(cells.iter().map(|cell| cell.sensor_id).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(cells[1].temperature, Some(40.0));
+        assert_eq!(cells[1].status, SensorStatus::Stuck);
+        assert_eq!(cells[3].temperature, None);
+        assert_eq!(cells[3].status, SensorStatus::OpenCircuit);
+    }
+
+    #[test]
+    fn excludes_faults_from_stats_and_lists_empty_layers() {
+        let layout = GranaryLayout {
+            cable_rows: 1,
+            cable_columns: 1,
+            points_per_cable: 3,
+            wiring: WiringOrder::ByLayer,
+            skipped: Vec::new(),
+        };
+        let (overall, layers) = layer_stats(&sensor_cells(&reading()), &layout);
+
+        assert_eq!(overall.valid_count, 2);
+        assert_eq!(overall.max, Some(24.0));
+        assert_eq!(overall.min, Some(20.0));
+        assert_eq!(overall.average, Some(22.0));
+        assert_eq!(overall.faults.iter().map(|cell| cell.sensor_id).collect::<Vec<_>>(), vec![2, 4]);
+
+        assert_eq!(layers.iter().map(|stats| stats.layer).collect::<Vec<_>>(), vec![Some(1), Some(2), Some(3)]);
+        assert_eq!(layers[0].valid_count, 2);
+        assert_eq!(layers[0].faults.len(), 1);
+        assert_eq!(layers[1].valid_count, 0);
+        assert_eq!(layers[1].average, None);
+        assert_eq!(layers[1].faults.len(), 1);
+        assert_eq!(layers[2], LayerStats { layer: Some(3), ..LayerStats::default() });
+    }
+}
@@ -0,0 +1,181 @@
+// 读数导出为Excel，每层一个工作表按行×列排列温度，另有汇总工作表
+use rust_xlsxwriter::{Color, Format, FormatAlign, Workbook, Worksheet, XlsxError};
+
+use super::table::{cells_by_position, fault_list, layer_stats, sensor_cells, status_name, LayerStats};
+use crate::history::HistoryReading;
+use crate::i18n::tr;
+use crate::layout::{GranaryLayout, SensorPosition};
+use crate::protocol::SensorStatus;
+
+/// 异常测温点的背景颜色，与界面中故障数据的颜色一致
+const FAULT_COLOR: u32 = 0xCFCFCF;
+
+/// 工作表中使用的单元格格式
+struct Formats {
+    title: Format,
+    header: Format,
+    temperature: Format,
+    fault: Format,
+}
+
+impl Formats {
+    fn new() -> Self {
+        Formats {
+            title: Format::new().set_bold(),
+            header: Format::new().set_bold().set_align(FormatAlign::Center),
+            temperature: Format::new().set_num_format("0.0").set_align(FormatAlign::Center),
+            fault: Format::new()
+                .set_background_color(Color::RGB(FAULT_COLOR))
+                .set_align(FormatAlign::Center),
+        }
+    }
+}
+
+/// 生成Excel工作簿
+///
+/// # 参数
+///
+/// * `readings` - 带测温点读数和坐标的历史记录及其粮仓的测温点布局，按采样时间排列
+pub fn render(readings: &[(HistoryReading, GranaryLayout)]) -> Result<Workbook, XlsxError> {
+    let formats = Formats::new();
+    let mut workbook = Workbook::new();
+    write_summary(workbook.add_worksheet(), readings, &formats)?;
+
+    let layer_count = readings.iter().map(|(_, layout)| layout.points_per_cable).max().unwrap_or(0);
+    for layer in 1..=layer_count {
+        let sheet = workbook.add_worksheet();
+        sheet.set_name(tr!("export.sheet.layer", layer = layer))?;
+        write_layer(sheet, layer, readings, &formats)?;
+    }
+    Ok(workbook)
+}
+
+/// 汇总工作表：每次读取一行整仓统计（含仓内外温湿度），其后每层一行
+fn write_summary(
+    sheet: &mut Worksheet,
+    readings: &[(HistoryReading, GranaryLayout)],
+    formats: &Formats,
+) -> Result<(), XlsxError> {
+    sheet.set_name(tr!("export.sheet.summary"))?;
+    let header = [
+        tr!("export.column.readingId"),
+        tr!("export.column.granary"),
+        tr!("export.column.subDeviceAddress"),
+        tr!("export.column.sampledAt"),
+        tr!("export.column.layer"),
+        tr!("export.column.validCount"),
+        tr!("export.column.maxTemperature"),
+        tr!("export.column.minTemperature"),
+        tr!("export.column.averageTemperature"),
+        tr!("export.column.faultCount"),
+        tr!("export.column.faults"),
+        tr!("export.column.indoorTemperature"),
+        tr!("export.column.indoorHumidity"),
+        tr!("export.column.outdoorTemperature"),
+        tr!("export.column.outdoorHumidity"),
+    ];
+    for (col, title) in header.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, title, &formats.header)?;
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    sheet.set_column_width(3, 20)?;
+    sheet.set_column_width(10, 40)?;
+
+    let mut row = 1;
+    for (reading, layout) in readings {
+        let cells = sensor_cells(reading);
+        let (overall, layers) = layer_stats(&cells, layout);
+        for stats in std::iter::once(&overall).chain(&layers) {
+            sheet.write_number(row, 0, reading.id as f64)?;
+            sheet.write_string(row, 1, &reading.granary)?;
+            sheet.write_number(row, 2, reading.sub_device_address)?;
+            sheet.write_string(row, 3, &reading.sampled_at)?;
+            let layer = match stats.layer {
+                Some(layer) => tr!("export.layer", layer = layer),
+                None => tr!("export.overall"),
+            };
+            sheet.write_string(row, 4, layer)?;
+            write_stats(sheet, row, stats, formats)?;
+            if stats.layer.is_none() {
+                let environment = &reading.environment;
+                write_optional(sheet, row, 11, environment.indoor_temperature, &formats.temperature)?;
+                write_optional(sheet, row, 12, environment.indoor_humidity.map(f64::from), &formats.temperature)?;
+                write_optional(sheet, row, 13, environment.outdoor_temperature, &formats.temperature)?;
+                write_optional(sheet, row, 14, environment.outdoor_humidity.map(f64::from), &formats.temperature)?;
+            }
+            row += 1;
+        }
+    }
+    Ok(())
+}
+
+/// 写入统计列
+fn write_stats(sheet: &mut Worksheet, row: u32, stats: &LayerStats, formats: &Formats) -> Result<(), XlsxError> {
+    sheet.write_number(row, 5, stats.valid_count as f64)?;
+    write_optional(sheet, row, 6, stats.max, &formats.temperature)?;
+    write_optional(sheet, row, 7, stats.min, &formats.temperature)?;
+    write_optional(sheet, row, 8, stats.average, &formats.temperature)?;
+    sheet.write_number(row, 9, stats.faults.len() as f64)?;
+    sheet.write_string(row, 10, fault_list(&stats.faults))?;
+    Ok(())
+}
+
+/// 单层工作表：每次读取一个区块，标题行之后按电缆行×列排列温度，异常测温点显示异常类型
+fn write_layer(
+    sheet: &mut Worksheet,
+    layer: u16,
+    readings: &[(HistoryReading, GranaryLayout)],
+    formats: &Formats,
+) -> Result<(), XlsxError> {
+    let mut row = 0;
+    for (reading, layout) in readings.iter().filter(|(_, layout)| layer <= layout.points_per_cable) {
+        let cells = sensor_cells(reading);
+        let positions = cells_by_position(&cells);
+        let title = tr!(
+            "export.blockTitle",
+            id = reading.id,
+            granary = reading.granary,
+            device = reading.sub_device_address,
+            time = reading.sampled_at
+        );
+        sheet.write_string_with_format(row, 0, title, &formats.title)?;
+        row += 1;
+
+        for column in 1..=layout.cable_columns {
+            sheet.write_string_with_format(row, column, tr!("export.columnLabel", column = column), &formats.header)?;
+        }
+        row += 1;
+        for cable_row in 1..=layout.cable_rows {
+            sheet.write_string_with_format(row, 0, tr!("export.rowLabel", row = cable_row), &formats.header)?;
+            for column in 1..=layout.cable_columns {
+                let position = SensorPosition {
+                    layer,
+                    row: cable_row,
+                    column,
+                };
+                let Some(cell) = positions.get(&position) else {
+                    continue;
+                };
+                match (cell.status, cell.temperature) {
+                    (SensorStatus::Valid, Some(temperature)) => {
+                        sheet.write_number_with_format(row, column, temperature, &formats.temperature)?;
+                    }
+                    _ => {
+                        sheet.write_string_with_format(row, column, status_name(cell.status), &formats.fault)?;
+                    }
+                }
+            }
+            row += 1;
+        }
+        // 区块之间空一行
+        row += 1;
+    }
+    Ok(())
+}
+
+fn write_optional(sheet: &mut Worksheet, row: u32, col: u16, value: Option<f64>, format: &Format) -> Result<(), XlsxError> {
+    if let Some(value) = value {
+        sheet.write_number_with_format(row, col, value, format)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,42 @@
+// 历史数据库错误定义
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::i18n::tr;
+use crate::protocol::FrameError;
+
+/// 历史数据保存或查询失败的原因
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum HistoryError {
+    /// 数据库打开、升级或读写失败
+    Database { message: String },
+    /// 要保存的响应帧无效
+    InvalidFrame { error: FrameError },
+    /// 查询时间不是 "YYYY-MM-DD HH:MM:SS" 格式
+    InvalidTime { value: String },
+    /// 记录不存在
+    NotFound { id: i64 },
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            HistoryError::Database { message } => tr!("history.database", error = message),
+            HistoryError::InvalidFrame { error } => tr!("history.invalidFrame", error = error),
+            HistoryError::InvalidTime { value } => tr!("history.invalidTime", value = value),
+            HistoryError::NotFound { id } => tr!("history.notFound", id = id),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+impl From<rusqlite::Error> for HistoryError {
+    fn from(error: rusqlite::Error) -> Self {
+        HistoryError::Database {
+            message: error.to_string(),
+        }
+    }
+}
@@ -0,0 +1,587 @@
+// 历史数据模块，把每次读取的粮情保存到本地SQLite数据库，按时间和位置查询
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use log::{debug, info};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::i18n::tr;
+use crate::network::TargetResult;
+use crate::protocol::{EnvironmentData, ResponseFrame, SensorFault, SensorReading, SensorStatus};
+use crate::utils::hex_utils::format_bytes_to_hex;
+
+// 导出错误定义
+pub mod error;
+// 导出表结构与升级模块
+pub mod schema;
+
+pub use error::HistoryError;
+
+/// 数据库中的时间格式，按字符串排序即按时间排序
+pub const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+/// 查询未指定数量时最多返回的记录数
+const DEFAULT_QUERY_LIMIT: usize = 1000;
+/// 卡滞判定默认使用的连续读数次数（含本次）
+pub const DEFAULT_STUCK_READING_COUNT: usize = 6;
+/// 卡滞判定至少需要的连续读数次数（含本次）
+pub const MIN_STUCK_READING_COUNT: usize = 2;
+
+/// 历史数据设置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HistoryConfig {
+    /// 测温点连续这么多次读数完全相同时视为卡滞（含本次）
+    pub stuck_reading_count: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        HistoryConfig {
+            stuck_reading_count: DEFAULT_STUCK_READING_COUNT,
+        }
+    }
+}
+
+/// 历史数据查询条件，未设置的条件不参与筛选
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryQuery {
+    /// 粮仓
+    pub granary: Option<String>,
+    /// 分机地址
+    pub sub_device_address: Option<u8>,
+    /// 起始时间（含），格式 "YYYY-MM-DD HH:MM:SS" 或 "YYYY-MM-DD"
+    pub from: Option<String>,
+    /// 结束时间（不含），格式同起始时间
+    pub to: Option<String>,
+    /// 最多返回的记录数，默认1000
+    pub limit: Option<usize>,
+    /// 是否返回每条记录的测温点读数和异常测温点
+    #[serde(default)]
+    pub include_sensors: bool,
+}
+
+/// 一次读取的历史记录
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryReading {
+    /// 记录编号
+    pub id: i64,
+    /// 粮仓
+    pub granary: String,
+    /// 分机地址
+    pub sub_device_address: u8,
+    /// 读取时使用的链路
+    pub source: String,
+    /// 采样时间，取自帧头的设备时间，设备时间无效时为接收时间
+    pub sampled_at: String,
+    /// 帧头的设备时间是否有效
+    pub device_time_valid: bool,
+    /// 本机接收时间
+    pub received_at: String,
+    /// 响应包数量
+    pub packet_count: usize,
+    /// 有效测温点数量
+    pub sensor_count: usize,
+    /// 仓内外温湿度
+    pub environment: EnvironmentData,
+    /// 测温点读数，查询时未要求返回则为null
+    pub sensors: Option<Vec<SensorReading>>,
+    /// 异常测温点，与测温点读数一同返回
+    pub faults: Option<Vec<SensorFault>>,
+    /// 原始帧（16进制），只在按编号读取时返回
+    pub raw_frame: Option<String>,
+}
+
+impl HistoryReading {
+    /// 测温点状态：有异常记录时为异常类型，有读数时为正常，否则为缺失
+    ///
+    /// 需要记录带有测温点读数和异常测温点
+    pub fn sensor_status(&self, sensor_id: u16) -> SensorStatus {
+        let fault = self.faults.iter().flatten().find(|fault| fault.sensor_id == sensor_id);
+        match fault {
+            Some(fault) => fault.status,
+            None if self.sensors.iter().flatten().any(|sensor| sensor.sensor_id == sensor_id) => SensorStatus::Valid,
+            None => SensorStatus::Missing,
+        }
+    }
+}
+
+/// 有历史记录的位置
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryLocation {
+    /// 粮仓
+    pub granary: String,
+    /// 分机地址
+    pub sub_device_address: u8,
+    /// 记录数量
+    pub reading_count: usize,
+    /// 最早的采样时间
+    pub first_sampled_at: String,
+    /// 最近的采样时间
+    pub last_sampled_at: String,
+}
+
+/// 历史数据库，保存在Tauri托管状态中，可克隆后在后台任务中使用
+#[derive(Clone)]
+pub struct HistoryStore {
+    connection: Arc<Mutex<Connection>>,
+    stuck_reading_count: Arc<AtomicUsize>,
+}
+
+impl HistoryStore {
+    /// 打开数据库文件，不存在时创建，并升级到最新表结构
+    pub fn open(path: &Path) -> Result<Self, HistoryError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| HistoryError::Database {
+                message: e.to_string(),
+            })?;
+        }
+        let connection = Connection::open(path)?;
+        info!("{}", tr!("history.databaseOpened", path = path.display()));
+        Self::with_connection(connection)
+    }
+
+    /// 打开内存数据库，数据在应用退出后丢失，用于数据库文件无法打开时
+    pub fn open_in_memory() -> Result<Self, HistoryError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(mut connection: Connection) -> Result<Self, HistoryError> {
+        schema::migrate(&mut connection)?;
+        Ok(HistoryStore {
+            connection: Arc::new(Mutex::new(connection)),
+            stuck_reading_count: Arc::new(AtomicUsize::new(DEFAULT_STUCK_READING_COUNT)),
+        })
+    }
+
+    /// 设置卡滞判定使用的连续读数次数（含本次），小于最小值时按最小值处理
+    pub fn set_stuck_reading_count(&self, count: usize) {
+        self.stuck_reading_count.store(count.max(MIN_STUCK_READING_COUNT), Ordering::Relaxed);
+    }
+
+    /// 保存一次读取结果
+    ///
+    /// 保存前与同一粮仓同一分机之前的读数比较，卡滞的测温点加入 `frame.faults`
+    ///
+    /// # 参数
+    ///
+    /// * `granary` - 粮仓，为None时使用链路描述
+    /// * `source` - 读取时使用的链路描述
+    /// * `frame` - 解析后的响应帧，需带有原始帧数据
+    ///
+    /// # 返回值
+    ///
+    /// 新记录的编号
+    pub fn record(&self, granary: Option<&str>, source: &str, frame: &mut ResponseFrame) -> Result<i64, HistoryError> {
+        let received_at = Local::now().naive_local();
+        let device_time = frame.timestamp.as_ref().and_then(|timestamp| timestamp.to_naive());
+        let sampled_at = device_time.unwrap_or(received_at);
+        let granary = granary.map(str::trim).filter(|granary| !granary.is_empty()).unwrap_or(source);
+
+        let mut connection = self.lock();
+        let transaction = connection.transaction()?;
+        let count = self.stuck_reading_count.load(Ordering::Relaxed);
+        let stuck = detect_stuck(&transaction, granary, frame, count)?;
+        if !stuck.is_empty() {
+            debug!(
+                "{}",
+                tr!(
+                    "history.stuckSensors",
+                    granary = granary,
+                    address = frame.sub_device_address,
+                    count = stuck.len()
+                )
+            );
+            frame.faults.extend(stuck);
+            frame.faults.sort_by_key(|fault| fault.sensor_id);
+        }
+        transaction.execute(
+            "INSERT INTO readings (granary, sub_device_address, source, sampled_at, device_time_valid,
+                received_at, packet_count, sensor_count, indoor_temperature, indoor_humidity,
+                outdoor_temperature, outdoor_humidity, raw_frame)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                granary,
+                frame.sub_device_address,
+                source,
+                sampled_at.format(TIME_FORMAT).to_string(),
+                device_time.is_some(),
+                received_at.format(TIME_FORMAT).to_string(),
+                frame.packet_count,
+                frame.sensors.len(),
+                frame.environment.indoor_temperature,
+                frame.environment.indoor_humidity,
+                frame.environment.outdoor_temperature,
+                frame.environment.outdoor_humidity,
+                frame.raw,
+            ],
+        )?;
+        let id = transaction.last_insert_rowid();
+        {
+            let mut statement = transaction
+                .prepare("INSERT INTO sensor_values (reading_id, sensor_id, temperature) VALUES (?1, ?2, ?3)")?;
+            for sensor in &frame.sensors {
+                statement.execute(params![id, sensor.sensor_id, sensor.temperature])?;
+            }
+            let mut statement = transaction.prepare(
+                "INSERT INTO sensor_faults (reading_id, sensor_id, status, raw, temperature) VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            for fault in &frame.faults {
+                statement.execute(params![
+                    id,
+                    fault.sensor_id,
+                    status_name(fault.status),
+                    fault.raw,
+                    fault.temperature
+                ])?;
+            }
+        }
+        transaction.commit()?;
+
+        debug!(
+            "{}",
+            tr!(
+                "history.readingSaved",
+                id = id,
+                granary = granary,
+                address = frame.sub_device_address,
+                count = frame.sensors.len()
+            )
+        );
+        Ok(id)
+    }
+
+    /// 保存批量或定时轮询中一个目标的读取结果
+    ///
+    /// # 返回值
+    ///
+    /// 读取成功时返回新记录的编号，读取失败的目标不保存并返回None
+    pub fn record_target(&self, result: &mut TargetResult) -> Result<Option<i64>, HistoryError> {
+        match &mut result.frame {
+            Some(frame) => {
+                let source = result.target.link.describe();
+                self.record(result.target.granary.as_deref(), &source, frame).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// 按条件查询历史记录，按采样时间从新到旧排列
+    pub fn query(&self, query: &HistoryQuery) -> Result<Vec<HistoryReading>, HistoryError> {
+        let mut conditions = Vec::new();
+        let mut values: Vec<Value> = Vec::new();
+        if let Some(granary) = &query.granary {
+            conditions.push("granary = ?");
+            values.push(Value::Text(granary.clone()));
+        }
+        if let Some(address) = query.sub_device_address {
+            conditions.push("sub_device_address = ?");
+            values.push(Value::Integer(address.into()));
+        }
+        if let Some(from) = &query.from {
+            conditions.push("sampled_at >= ?");
+            values.push(Value::Text(parse_query_time(from)?));
+        }
+        if let Some(to) = &query.to {
+            conditions.push("sampled_at < ?");
+            values.push(Value::Text(parse_query_time(to)?));
+        }
+        let filter = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let limit = query.limit.unwrap_or(DEFAULT_QUERY_LIMIT);
+        values.push(Value::Integer(i64::try_from(limit).unwrap_or(i64::MAX)));
+
+        let connection = self.lock();
+        let sql = format!(
+            "SELECT {} FROM readings {} ORDER BY sampled_at DESC, id DESC LIMIT ?",
+            READING_COLUMNS, filter
+        );
+        let mut statement = connection.prepare(&sql)?;
+        let mut readings = statement
+            .query_map(params_from_iter(values), reading_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        if query.include_sensors {
+            for reading in &mut readings {
+                reading.sensors = Some(load_sensors(&connection, reading.id)?);
+                reading.faults = Some(load_faults(&connection, reading.id)?);
+            }
+        }
+        debug!(
+            "{}",
+            tr!("history.readingsFound", count = readings.len(), query = format!("{:?}", query))
+        );
+        Ok(readings)
+    }
+
+    /// 按编号读取一条记录，包含测温点读数、异常测温点和原始帧
+    pub fn reading(&self, id: i64) -> Result<HistoryReading, HistoryError> {
+        let connection = self.lock();
+        let sql = format!("SELECT {}, raw_frame FROM readings WHERE id = ?1", READING_COLUMNS);
+        let result = connection.query_row(&sql, [id], |row| {
+            let mut reading = reading_from_row(row)?;
+            let raw: Vec<u8> = row.get(READING_COLUMN_COUNT)?;
+            reading.raw_frame = Some(format_bytes_to_hex(&raw));
+            Ok(reading)
+        });
+        let mut reading = match result {
+            Ok(reading) => reading,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Err(HistoryError::NotFound { id }),
+            Err(e) => return Err(e.into()),
+        };
+        reading.sensors = Some(load_sensors(&connection, id)?);
+        reading.faults = Some(load_faults(&connection, id)?);
+        Ok(reading)
+    }
+
+    /// 读取同一粮仓同一分机在该记录之前保存的一条记录，包含测温点读数和异常测温点
+    ///
+    /// 按保存顺序而不是采样时间查找，设备时间被校正过时也能取到上一次读取的结果
+    pub fn previous(&self, reading: &HistoryReading) -> Result<Option<HistoryReading>, HistoryError> {
+        let connection = self.lock();
+        let sql = format!(
+            "SELECT {} FROM readings WHERE granary = ?1 AND sub_device_address = ?2 AND id < ?3
+             ORDER BY id DESC LIMIT 1",
+            READING_COLUMNS
+        );
+        let previous = connection
+            .query_row(
+                &sql,
+                params![reading.granary, reading.sub_device_address, reading.id],
+                reading_from_row,
+            )
+            .optional()?;
+        match previous {
+            Some(mut previous) => {
+                previous.sensors = Some(load_sensors(&connection, previous.id)?);
+                previous.faults = Some(load_faults(&connection, previous.id)?);
+                Ok(Some(previous))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// 列出有历史记录的粮仓和分机
+    pub fn locations(&self) -> Result<Vec<HistoryLocation>, HistoryError> {
+        let connection = self.lock();
+        let mut statement = connection.prepare(
+            "SELECT granary, sub_device_address, COUNT(*), MIN(sampled_at), MAX(sampled_at)
+             FROM readings GROUP BY granary, sub_device_address ORDER BY granary, sub_device_address",
+        )?;
+        let locations = statement
+            .query_map([], |row| {
+                Ok(HistoryLocation {
+                    granary: row.get(0)?,
+                    sub_device_address: row.get(1)?,
+                    reading_count: row.get(2)?,
+                    first_sampled_at: row.get(3)?,
+                    last_sampled_at: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(locations)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Connection> {
+        match self.connection.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// 查询记录时读取的列，顺序与 `reading_from_row` 一致
+const READING_COLUMNS: &str = "id, granary, sub_device_address, source, sampled_at, device_time_valid, \
+     received_at, packet_count, sensor_count, indoor_temperature, indoor_humidity, outdoor_temperature, \
+     outdoor_humidity";
+/// `READING_COLUMNS` 中的列数
+const READING_COLUMN_COUNT: usize = 13;
+
+fn reading_from_row(row: &Row<'_>) -> rusqlite::Result<HistoryReading> {
+    Ok(HistoryReading {
+        id: row.get(0)?,
+        granary: row.get(1)?,
+        sub_device_address: row.get(2)?,
+        source: row.get(3)?,
+        sampled_at: row.get(4)?,
+        device_time_valid: row.get(5)?,
+        received_at: row.get(6)?,
+        packet_count: row.get(7)?,
+        sensor_count: row.get(8)?,
+        environment: EnvironmentData {
+            indoor_temperature: row.get(9)?,
+            indoor_humidity: row.get(10)?,
+            outdoor_temperature: row.get(11)?,
+            outdoor_humidity: row.get(12)?,
+        },
+        sensors: None,
+        faults: None,
+        raw_frame: None,
+    })
+}
+
+/// 读取一条记录的测温点读数，按测温点编号排列
+fn load_sensors(connection: &Connection, reading_id: i64) -> Result<Vec<SensorReading>, HistoryError> {
+    let mut statement = connection
+        .prepare_cached("SELECT sensor_id, temperature FROM sensor_values WHERE reading_id = ?1 ORDER BY sensor_id")?;
+    let sensors = statement
+        .query_map([reading_id], |row| {
+            Ok(SensorReading {
+                sensor_id: row.get(0)?,
+                temperature: row.get(1)?,
+                position: None,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(sensors)
+}
+
+/// 读取一条记录的异常测温点，按测温点编号排列
+fn load_faults(connection: &Connection, reading_id: i64) -> Result<Vec<SensorFault>, HistoryError> {
+    let mut statement = connection.prepare_cached(
+        "SELECT sensor_id, status, raw, temperature FROM sensor_faults WHERE reading_id = ?1 ORDER BY sensor_id",
+    )?;
+    let faults = statement
+        .query_map([reading_id], |row| {
+            Ok(SensorFault {
+                sensor_id: row.get(0)?,
+                status: parse_status(&row.get::<_, String>(1)?),
+                raw: row.get(2)?,
+                temperature: row.get(3)?,
+                position: None,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(faults)
+}
+
+/// 找出与之前 `count - 1` 次读数完全相同的测温点，之前的读数不足时不判定
+fn detect_stuck(
+    connection: &Connection,
+    granary: &str,
+    frame: &ResponseFrame,
+    count: usize,
+) -> Result<Vec<SensorFault>, HistoryError> {
+    let mut statement = connection.prepare_cached(
+        "SELECT v.sensor_id, v.temperature FROM sensor_values v
+         JOIN (SELECT id FROM readings WHERE granary = ?1 AND sub_device_address = ?2 ORDER BY id DESC LIMIT ?3) r
+           ON v.reading_id = r.id",
+    )?;
+    let mut before: HashMap<u16, Vec<f64>> = HashMap::new();
+    let rows = statement.query_map(
+        params![granary, frame.sub_device_address, count - 1],
+        |row| Ok((row.get::<_, u16>(0)?, row.get::<_, f64>(1)?)),
+    )?;
+    for row in rows {
+        let (sensor_id, temperature) = row?;
+        before.entry(sensor_id).or_default().push(temperature);
+    }
+
+    let stuck = frame
+        .sensors
+        .iter()
+        .filter(|sensor| {
+            before.get(&sensor.sensor_id).is_some_and(|values| {
+                values.len() == count - 1 && values.iter().all(|value| *value == sensor.temperature)
+            })
+        })
+        .map(|sensor| SensorFault {
+            sensor_id: sensor.sensor_id,
+            status: SensorStatus::Stuck,
+            raw: None,
+            temperature: Some(sensor.temperature),
+            position: sensor.position,
+        })
+        .collect();
+    Ok(stuck)
+}
+
+/// 数据库中保存的测温点状态名称，与序列化名称一致
+fn status_name(status: SensorStatus) -> &'static str {
+    match status {
+        SensorStatus::Valid => "valid",
+        SensorStatus::OpenCircuit => "openCircuit",
+        SensorStatus::ShortCircuit => "shortCircuit",
+        SensorStatus::OutOfRange => "outOfRange",
+        SensorStatus::Stuck => "stuck",
+        SensorStatus::Missing => "missing",
+    }
+}
+
+fn parse_status(value: &str) -> SensorStatus {
+    match value {
+        "openCircuit" => SensorStatus::OpenCircuit,
+        "shortCircuit" => SensorStatus::ShortCircuit,
+        "outOfRange" => SensorStatus::OutOfRange,
+        "stuck" => SensorStatus::Stuck,
+        "missing" => SensorStatus::Missing,
+        _ => SensorStatus::Valid,
+    }
+}
+
+/// 把查询时间统一为数据库中的格式，只有日期时按当天0点处理
+///
+/// 兼容前端datetime-local输入框的 "YYYY-MM-DDTHH:MM:SS" 和 "YYYY-MM-DDTHH:MM" 格式
+fn parse_query_time(value: &str) -> Result<String, HistoryError> {
+    let trimmed = value.trim();
+    NaiveDateTime::parse_from_str(trimmed, TIME_FORMAT)
+        .or_else(|_| NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%dT%H:%M:%S"))
+        .or_else(|_| NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%dT%H:%M"))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .map(|time| time.format(TIME_FORMAT).to_string())
+        .ok_or_else(|| HistoryError::InvalidTime {
+            value: value.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::decode_response;
+    use crate::protocol::test_data::sample_response;
+
+    fn record_sample(store: &HistoryStore) -> ResponseFrame {
+        let mut frame = decode_response(&sample_response()).unwrap();
+        store.record(Some("1号仓"), "127.0.0.1:2000", &mut frame).unwrap();
+        frame
+    }
+
+    fn stuck_count(frame: &ResponseFrame) -> usize {
+        frame.faults.iter().filter(|fault| fault.status == SensorStatus::Stuck).count()
+    }
+
+    #[test]
+    fn uses_configured_stuck_reading_count() {
+        let store = HistoryStore::open_in_memory().unwrap();
+        store.set_stuck_reading_count(3);
+
+        assert_eq!(stuck_count(&record_sample(&store)), 0);
+        assert_eq!(stuck_count(&record_sample(&store)), 0);
+        let frame = record_sample(&store);
+        assert_eq!(stuck_count(&frame), frame.sensors.len());
+    }
+
+    #[test]
+    fn waits_for_default_stuck_reading_count() {
+        let store = HistoryStore::open_in_memory().unwrap();
+
+        for _ in 1..DEFAULT_STUCK_READING_COUNT {
+            assert_eq!(stuck_count(&record_sample(&store)), 0);
+        }
+        assert!(stuck_count(&record_sample(&store)) > 0);
+    }
+}
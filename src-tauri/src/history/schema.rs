@@ -0,0 +1,60 @@
+// 历史数据库表结构与升级
+use log::info;
+use rusqlite::Connection;
+
+use super::HistoryError;
+use crate::i18n::tr;
+
+/// 按版本排列的升级脚本，第N条把数据库从版本N升级到N+1
+const MIGRATIONS: &[&str] = &[
+    // 版本1：读数主表与测温点明细
+    "CREATE TABLE readings (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        granary TEXT NOT NULL,
+        sub_device_address INTEGER NOT NULL,
+        source TEXT NOT NULL,
+        sampled_at TEXT NOT NULL,
+        device_time_valid INTEGER NOT NULL,
+        received_at TEXT NOT NULL,
+        packet_count INTEGER NOT NULL,
+        sensor_count INTEGER NOT NULL,
+        indoor_temperature REAL,
+        indoor_humidity INTEGER,
+        outdoor_temperature REAL,
+        outdoor_humidity INTEGER,
+        raw_frame BLOB NOT NULL
+    );
+    CREATE INDEX idx_readings_location ON readings (granary, sub_device_address, sampled_at);
+    CREATE INDEX idx_readings_sampled_at ON readings (sampled_at);
+    CREATE TABLE sensor_values (
+        reading_id INTEGER NOT NULL REFERENCES readings (id) ON DELETE CASCADE,
+        sensor_id INTEGER NOT NULL,
+        temperature REAL NOT NULL,
+        PRIMARY KEY (reading_id, sensor_id)
+    ) WITHOUT ROWID;",
+    // 版本2：异常测温点
+    "CREATE TABLE sensor_faults (
+        reading_id INTEGER NOT NULL REFERENCES readings (id) ON DELETE CASCADE,
+        sensor_id INTEGER NOT NULL,
+        status TEXT NOT NULL,
+        raw INTEGER,
+        temperature REAL,
+        PRIMARY KEY (reading_id, sensor_id)
+    ) WITHOUT ROWID;",
+];
+
+/// 把数据库升级到最新版本
+///
+/// 版本号保存在 `PRAGMA user_version` 中，每个版本的升级在单独的事务中执行
+pub fn migrate(connection: &mut Connection) -> Result<(), HistoryError> {
+    connection.pragma_update(None, "foreign_keys", true)?;
+    let current: usize = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    for (version, script) in MIGRATIONS.iter().enumerate().skip(current) {
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(script)?;
+        transaction.pragma_update(None, "user_version", version + 1)?;
+        transaction.commit()?;
+        info!("{}", tr!("history.schemaUpgraded", version = version + 1));
+    }
+    Ok(())
+}
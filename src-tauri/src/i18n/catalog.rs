@@ -0,0 +1,551 @@
+// 消息目录，每条消息包含中文和英文文本
+//
+// 占位符使用 `{名称}` 形式；排查建议类消息以换行分隔多条内容
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+use super::Locale;
+
+/// 消息标识、中文文本、英文文本
+const MESSAGES: &[(&str, &str, &str)] = &[
+    // 语言设置
+    ("locale.changed", "界面语言已切换为 {locale}", "Locale switched to {locale}"),
+    ("locale.unsupported", "不支持的语言: {locale}，可选 zh-CN 或 en-US", "Unsupported locale: {locale}, expected zh-CN or en-US"),
+    ("locale.saveFailed", "保存界面语言失败: {error}", "Failed to save the locale: {error}"),
+    // 网络交互阶段
+    ("phase.resolve", "解析地址", "resolving the address"),
+    ("phase.connect", "建立连接", "connecting"),
+    ("phase.write", "发送数据", "sending data"),
+    ("phase.read", "接收数据", "receiving data"),
+    // 网络错误
+    ("network.badHex", "解析16进制数据失败: {message}", "Invalid hex data: {message}"),
+    ("network.invalidRequest", "请求参数无效: {message}", "Invalid request: {message}"),
+    ("network.unresolvable", "无法解析设备地址 {address}", "Cannot resolve device address {address}"),
+    ("network.connectRefused", "连接 {address} 被拒绝", "Connection to {address} was refused"),
+    ("network.connectTimeout", "连接 {address} 超时（{elapsed}ms）", "Connection to {address} timed out ({elapsed} ms)"),
+    ("network.unreachable", "设备 {address} 不可达", "Device {address} is unreachable"),
+    ("network.reset", "{phase}时连接 {address} 被重置", "Connection to {address} was reset while {phase}"),
+    ("network.closed", "{phase}时连接已被设备 {address} 关闭", "Device {address} closed the connection while {phase}"),
+    ("network.writeTimeout", "向 {address} 发送数据超时（{elapsed}ms）", "Sending data to {address} timed out ({elapsed} ms)"),
+    ("network.readTimeout", "等待 {address} 响应超时（{elapsed}ms）", "No response from {address} within {elapsed} ms"),
+    ("network.emptyResponse", "设备 {address} 未返回数据", "Device {address} returned no data"),
+    ("network.portUnavailable", "无法打开串口 {address}: {error}", "Cannot open serial port {address}: {error}"),
+    ("network.io", "{phase}失败: {error}", "Failed while {phase}: {error}"),
+    ("network.unknownError", "未知错误", "unknown error"),
+    ("network.damagedFrame", "响应帧已损坏: {error}", "Damaged response frame: {error}"),
+    ("network.incompleteFrame", "响应帧不完整: 收到 {received} 字节后中断，完整帧应为 {expected} 字节", "Incomplete response frame: stopped after {received} bytes, a full frame is {expected} bytes"),
+    ("network.missingPackets", "多包响应不完整: 共 {expected} 包，缺失包序号 {missing}", "Incomplete multi-packet response: {expected} packets expected, missing {missing}"),
+    ("network.sessionNotFound", "会话 '{name}' 不存在", "Session '{name}' does not exist"),
+    ("network.deviceNotConnected", "接入设备 '{device}' 未连入", "Inbound device '{device}' is not connected"),
+    ("network.cancelled", "请求已取消", "Request cancelled"),
+    ("network.emptyAddress", "设备地址不能为空", "Device address must not be empty"),
+    // 帧错误
+    ("frame.invalidHex", "无效的16进制数据: {message}", "Invalid hex data: {message}"),
+    ("frame.tooShort", "帧长度不足: {length} 字节，至少需要 {minimum} 字节", "Frame too short: {length} bytes, at least {minimum} required"),
+    ("frame.invalidHeader", "无效的包头: {found}，应为 {expected}", "Invalid header: {found}, expected {expected}"),
+    ("frame.missingTrailer", "位置[{offset}] 帧尾为 {found}，应为 EF EF", "Trailer at offset {offset} is {found}, expected EF EF"),
+    ("frame.tooManyPackets", "需要 {expected} 个响应包，超过测温点编号可表示的 {maximum} 个", "{expected} response packets needed, more than the {maximum} that sensor IDs can address"),
+    ("frame.checksumMismatch", "位置[{offset}] 校验和错误({algorithm}): 计算值 {expected}，帧中为 {actual}", "Checksum mismatch at offset {offset} ({algorithm}): computed {expected}, frame carries {actual}"),
+    // 排查建议
+    ("advice.badHex.causes", "输入中包含非16进制字符或未用空格分隔的字节", "The input contains non-hex characters or bytes not separated by spaces"),
+    ("advice.badHex.suggestions", "检查发送数据，每个字节为两位16进制数并以空格分隔", "Check the data: each byte must be two hex digits separated by spaces"),
+    ("advice.invalidRequest.causes", "分机地址、时间或命令码超出协议允许的范围", "The sub-device address, time or command code is outside the range allowed by the protocol"),
+    ("advice.invalidRequest.suggestions", "确认分机地址在1-99范围内", "Make sure the sub-device address is between 1 and 99"),
+    ("advice.unresolvable.causes", "主机名拼写错误\n本机DNS服务器不可用或没有该主机的记录", "The host name is misspelled\nThe DNS server is unavailable or has no record for this host"),
+    ("advice.unresolvable.suggestions", "检查主机名拼写是否正确\n检查本机DNS设置\n直接使用设备的IP地址", "Check the spelling of the host name\nCheck the DNS settings of this computer\nUse the device IP address directly"),
+    ("advice.connectRefused.causes", "目标设备 {address} 上没有程序在监听该端口\n防火墙阻止了连接\n目标设备在线但服务未启动", "Nothing is listening on {address}\nA firewall blocked the connection\nThe device is online but its service is not running"),
+    ("advice.connectRefused.suggestions", "检查设备是否启动并运行服务\n验证端口号是否正确\n检查防火墙设置", "Check that the device and its service are running\nVerify the port number\nCheck the firewall settings"),
+    ("advice.connectTimeout.causes", "目标设备 {host} 不在线或网络拥塞\n目标设备防火墙默认丢弃连接请求（不响应）\n网络路径中有路由器/设备阻止了连接", "The device {host} is offline or the network is congested\nThe device firewall silently drops connection attempts\nA router or device on the path blocks the connection"),
+    ("advice.connectTimeout.suggestions", "检查设备是否开机并连接到网络\n尝试 ping {host} 测试基本连通性\n检查网络设置和防火墙", "Check that the device is powered on and connected\nTry ping {host} to test basic connectivity\nCheck the network settings and firewall"),
+    ("advice.unreachable.causes", "本机网络配置问题（如无有效IP地址）\n目标地址与本机不在同一网络且没有可用路由\n中间路由器上的ACL或防火墙规则阻止了连接", "This computer has a network configuration problem (e.g. no valid IP address)\nThe target is on another network with no route to it\nAn ACL or firewall rule on a router blocks the connection"),
+    ("advice.unreachable.suggestions", "确认地址 {host} 是否在您的网络范围内\n使用 traceroute {host} 查看网络路径\n检查网关和路由设置", "Confirm that {host} is within your network\nRun traceroute {host} to inspect the path\nCheck the gateway and routing settings"),
+    ("advice.connectionLost.causes", "设备在处理请求过程中断开或崩溃\n网络连接突然中断\n设备检测到无效数据并关闭了连接", "The device disconnected or crashed while handling the request\nThe network connection dropped\nThe device rejected invalid data and closed the connection"),
+    ("advice.connectionLost.suggestions", "检查设备状态和网络稳定性\n确认发送的命令格式正确\n查看设备日志获取更多信息", "Check the device status and network stability\nMake sure the command format is correct\nCheck the device logs for details"),
+    ("advice.writeTimeout.causes", "网络拥塞或不稳定\n设备无响应或处于忙碌状态", "The network is congested or unstable\nThe device is unresponsive or busy"),
+    ("advice.writeTimeout.suggestions", "增加超时时间\n检查设备状态", "Increase the timeout\nCheck the device status"),
+    ("advice.readTimeout.causes", "设备响应时间超过了设定的超时时间（本次等待 {elapsed}ms）\n设备收到请求但未能完成处理\n分机地址与设备不符，设备未作应答", "The device took longer than the timeout to respond (waited {elapsed} ms)\nThe device received the request but could not finish it\nThe sub-device address does not match, so the device did not answer"),
+    ("advice.readTimeout.suggestions", "增加超时时间\n检查设备状态\n确认分机地址是否正确", "Increase the timeout\nCheck the device status\nVerify the sub-device address"),
+    ("advice.emptyResponse.causes", "设备收到请求但没有数据需要返回\n设备协议要求特定格式的请求", "The device had no data to return\nThe device expects a request in a different format"),
+    ("advice.emptyResponse.suggestions", "检查发送的命令格式是否正确\n验证设备是否支持该命令", "Check the command format\nVerify that the device supports this command"),
+    ("advice.portUnavailable.causes", "串口 {address} 不存在或USB转串口设备已拔出\n串口已被其他程序占用\n当前用户没有访问串口的权限", "Serial port {address} does not exist or the USB adapter was unplugged\nAnother program is using the port\nThe current user has no permission to access the port"),
+    ("advice.portUnavailable.suggestions", "刷新串口列表并重新选择\n关闭占用串口的其他程序\n检查波特率、校验位和停止位设置", "Refresh the serial port list and select the port again\nClose other programs using the port\nCheck the baud rate, parity and stop bit settings"),
+    ("advice.io.suggestions", "检查网络连接\n验证IP地址和端口\n确认目标设备状态", "Check the network connection\nVerify the IP address and port\nCheck the device status"),
+    ("advice.damagedFrame.causes", "传输过程中受到干扰导致数据损坏\n设备在发送过程中断开了连接", "Interference corrupted the data in transit\nThe device disconnected while sending"),
+    ("advice.damagedFrame.suggestions", "重新读取一次\n检查线路和网络质量", "Read again\nCheck the cabling and network quality"),
+    ("advice.missingPackets.causes", "设备未响应部分数据包的补发请求", "The device did not answer re-requests for some packets"),
+    ("advice.missingPackets.suggestions", "确认测温点总数设置与设备一致\n增加超时时间或补发轮数", "Make sure the configured sensor count matches the device\nIncrease the timeout or the number of retries"),
+    ("advice.sessionNotFound.suggestions", "重新打开会话", "Open the session again"),
+    ("advice.deviceNotConnected.causes", "设备尚未连入或连接已断开\n接入服务未启动或端口与设备配置不一致\n设备注册包内容与填写的设备标识不符", "The device has not connected yet or its connection dropped\nThe device server is not running or its port differs from the device configuration\nThe device registration packet does not match the given device ID"),
+    ("advice.deviceNotConnected.suggestions", "刷新接入设备列表，确认设备标识\n检查DTU中配置的中心地址和端口\n检查设备的4G信号和SIM卡状态", "Refresh the inbound device list and check the device ID\nCheck the server address and port configured on the DTU\nCheck the device 4G signal and SIM card"),
+    // 命令日志
+    ("cmd.sendPrepare", "准备发送数据到 {address}, 数据长度: {length} 字节", "Sending data to {address}, length: {length} bytes"),
+    ("cmd.sendSucceeded", "成功接收来自 {address} 的响应，长度: {length} 字节，耗时: {elapsed}ms", "Received response from {address}, length: {length} bytes, took {elapsed} ms"),
+    ("cmd.sendFailed", "发送数据到 {address} 失败: {error}, 耗时: {elapsed}ms", "Sending data to {address} failed: {error}, took {elapsed} ms"),
+    ("cmd.pollSucceeded", "成功读取 {address} 分机 {device} 的粮情，共 {packets} 包，测温点: {sensors} 个，耗时: {elapsed}ms", "Read grain data from {address} sub-device {device}: {packets} packets, {sensors} sensors, took {elapsed} ms"),
+    ("cmd.pollFailed", "读取 {address} 分机 {device} 的粮情失败: {error}, 耗时: {elapsed}ms", "Reading grain data from {address} sub-device {device} failed: {error}, took {elapsed} ms"),
+    ("cmd.hexParseFailed", "解析16进制数据失败: {error}", "Failed to parse hex data: {error}"),
+    ("cmd.decodeSucceeded", "成功解析响应帧，分机地址: {device}, 测温点: {sensors} 个，耗时: {elapsed}ms", "Decoded response frame, sub-device: {device}, sensors: {sensors}, took {elapsed} ms"),
+    ("cmd.decodeFailed", "解析响应帧失败: {error}, 耗时: {elapsed}ms", "Failed to decode response frame: {error}, took {elapsed} ms"),
+    ("cmd.timestampParseFailed", "解析请求时间失败: {timestamp} ({error})", "Failed to parse request time: {timestamp} ({error})"),
+    ("cmd.invalidTimestamp", "无效的时间格式: {timestamp}，应为 YYYY-MM-DD HH:MM:SS", "Invalid time format: {timestamp}, expected YYYY-MM-DD HH:MM:SS"),
+    ("cmd.buildSucceeded", "成功构建请求帧，分机地址: {device}, 长度: {length} 字节", "Built request frame, sub-device: {device}, length: {length} bytes"),
+    ("cmd.buildFailed", "构建请求帧失败: {error}", "Failed to build request frame: {error}"),
+    ("cmd.getLogs", "正在获取日志，级别过滤: {level}，限制数量: {limit}", "Fetching logs, level filter: {level}, limit: {limit}"),
+    ("cmd.getLogsSucceeded", "成功获取 {count} 条日志记录，耗时: {elapsed}ms", "Fetched {count} log entries, took {elapsed} ms"),
+    ("cmd.readLogsFailed", "读取日志失败: {error}", "Failed to read logs: {error}"),
+    ("cmd.addLogFailed", "添加日志失败: {error}", "Failed to add log entry: {error}"),
+    ("cmd.clearLogs", "正在清空日志文件", "Clearing the log file"),
+    ("cmd.clearLogsSucceeded", "成功清空日志文件，耗时: {elapsed}ms", "Cleared the log file, took {elapsed} ms"),
+    ("cmd.clearLogsFailed", "清空日志失败: {error}", "Failed to clear logs: {error}"),
+    ("cmd.requestCancelled", "已取消请求 '{id}'", "Cancelled request '{id}'"),
+    ("cmd.progressEmitFailed", "推送请求进度事件失败: {error}", "Failed to emit request progress event: {error}"),
+    ("serial.listed", "找到 {count} 个串口", "Found {count} serial ports"),
+    ("server.started", "设备接入服务已启动，监听 {address}", "Device server started, listening on {address}"),
+    ("server.startFailed", "启动设备接入服务失败: {error}", "Failed to start the device server: {error}"),
+    ("server.stopped", "设备接入服务已停止", "Device server stopped"),
+    ("server.eventEmitFailed", "推送接入设备事件失败: {error}", "Failed to emit inbound device event: {error}"),
+    ("batch.started", "开始批量轮询 {id}，共 {count} 个目标", "Batch poll {id} started with {count} targets"),
+    ("batch.finished", "批量轮询 {id} 完成，成功 {succeeded} 个，失败 {failed} 个，耗时: {elapsed}ms", "Batch poll {id} finished: {succeeded} succeeded, {failed} failed, took {elapsed}ms"),
+    ("batch.eventEmitFailed", "推送批量轮询事件失败: {error}", "Failed to emit batch poll event: {error}"),
+    ("session.opened", "会话 '{name}' 已连接到 {address}，耗时: {elapsed}ms", "Session '{name}' connected to {address}, took {elapsed} ms"),
+    ("session.openFailed", "打开会话 '{name}' 失败: {error}, 耗时: {elapsed}ms", "Failed to open session '{name}': {error}, took {elapsed} ms"),
+    ("session.response", "会话 '{name}' 收到响应，长度: {length} 字节，耗时: {elapsed}ms", "Session '{name}' received a response, length: {length} bytes, took {elapsed} ms"),
+    ("session.sendFailed", "会话 '{name}' 发送失败: {error}, 耗时: {elapsed}ms", "Sending on session '{name}' failed: {error}, took {elapsed} ms"),
+    ("session.closeFailed", "关闭会话 '{name}' 失败: {error}", "Failed to close session '{name}': {error}"),
+    ("session.stateEmitFailed", "推送会话状态事件失败: {error}", "Failed to emit session state event: {error}"),
+    ("cmd.called", "命令调用: {command}", "Command: {command}"),
+    ("cmd.call.saveAlarmRule", "命令调用: save_alarm_rule - 规则: {id}, 名称: {name}, 条件: {condition}", "Command: save_alarm_rule - rule: {id}, name: {name}, condition: {condition}"),
+    ("cmd.call.deleteAlarmRule", "命令调用: delete_alarm_rule - 规则: {id}", "Command: delete_alarm_rule - rule: {id}"),
+    ("cmd.call.queryAlarms", "命令调用: query_alarms - 条件: {query}", "Command: query_alarms - query: {query}"),
+    ("cmd.call.acknowledgeAlarm", "命令调用: acknowledge_alarm - 告警: {id}, 操作员: {operator}", "Command: acknowledge_alarm - alarm: {id}, operator: {operator}"),
+    ("cmd.call.clearAlarm", "命令调用: clear_alarm - 告警: {id}, 操作员: {operator}", "Command: clear_alarm - alarm: {id}, operator: {operator}"),
+    ("cmd.call.analyzeTrends", "命令调用: analyze_trends - 条件: {query}", "Command: analyze_trends - query: {query}"),
+    ("cmd.call.pollBatch", "命令调用: poll_batch - 批次ID: {id}, 目标: {count} 个, 参数: {options}", "Command: poll_batch - batch ID: {id}, targets: {count}, options: {options}"),
+    ("cmd.call.exportConfigBundle", "命令调用: export_config_bundle - 路径: {path}", "Command: export_config_bundle - path: {path}"),
+    ("cmd.call.importConfigBundle", "命令调用: import_config_bundle - 路径: {path}, 只预览: {dryRun}", "Command: import_config_bundle - path: {path}, preview only: {dryRun}"),
+    ("cmd.call.saveAppConfig", "命令调用: save_app_config - 版本: {version}", "Command: save_app_config - version: {version}"),
+    ("cmd.call.validateAppConfig", "命令调用: validate_app_config - 版本: {version}", "Command: validate_app_config - version: {version}"),
+    ("cmd.call.exportReadings", "命令调用: export_readings - 条件: {query}, 格式: {format}, 路径: {path}", "Command: export_readings - query: {query}, format: {format}, path: {path}"),
+    ("cmd.call.queryHistory", "命令调用: query_history - 条件: {query}", "Command: query_history - query: {query}"),
+    ("cmd.call.getHistoryReading", "命令调用: get_history_reading - 编号: {id}", "Command: get_history_reading - ID: {id}"),
+    ("cmd.call.recordReading", "命令调用: record_reading - 粮仓: {granary}, 链路: {source}, 数据长度: {length}", "Command: record_reading - granary: {granary}, link: {source}, data length: {length}"),
+    ("cmd.call.saveDepot", "命令调用: save_depot - 库点: {id}, 名称: {name}", "Command: save_depot - depot: {id}, name: {name}"),
+    ("cmd.call.deleteDepot", "命令调用: delete_depot - 库点: {id}", "Command: delete_depot - depot: {id}"),
+    ("cmd.call.saveWarehouse", "命令调用: save_warehouse - 仓房: {id}, 名称: {name}, 库点: {depot}", "Command: save_warehouse - warehouse: {id}, name: {name}, depot: {depot}"),
+    ("cmd.call.deleteWarehouse", "命令调用: delete_warehouse - 仓房: {id}", "Command: delete_warehouse - warehouse: {id}"),
+    ("cmd.call.saveCollector", "命令调用: save_collector - 采集器: {id}, 名称: {name}, 仓房: {warehouse}, 链路: {link}, 分机地址: {address}", "Command: save_collector - collector: {id}, name: {name}, warehouse: {warehouse}, link: {link}, sub-device address: {address}"),
+    ("cmd.call.deleteCollector", "命令调用: delete_collector - 采集器: {id}", "Command: delete_collector - collector: {id}"),
+    ("cmd.call.listInventoryTargets", "命令调用: list_inventory_targets - 仓房: {warehouse}", "Command: list_inventory_targets - warehouse: {warehouse}"),
+    ("cmd.call.saveSensorLayouts", "命令调用: save_sensor_layouts - 默认布局: {layout}, 单独设置的粮仓: {granaries}", "Command: save_sensor_layouts - default layout: {layout}, granaries with their own layout: {granaries}"),
+    ("cmd.call.locateSensors", "命令调用: locate_sensors - 粮仓: {granary}", "Command: locate_sensors - granary: {granary}"),
+    ("cmd.call.sendHexData", "命令调用: send_hex_data - IP: {ip}, 端口: {port}, 接入设备: {device}, 超时: {timeout}, 请求ID: {requestId}", "Command: send_hex_data - IP: {ip}, port: {port}, inbound device: {device}, timeout: {timeout}, request ID: {requestId}"),
+    ("cmd.sendData", "发送数据内容: {data}", "Sent data: {data}"),
+    ("cmd.responseData", "响应数据内容: {data}", "Response data: {data}"),
+    ("cmd.preview", "{text}...(共{length}字符)", "{text}... ({length} characters)"),
+    ("cmd.call.pollGrainData", "命令调用: poll_grain_data - IP: {ip}, 端口: {port}, 接入设备: {device}, 分机地址: {address}, 测温点: {points}, 超时: {timeout}, 补发轮数: {retries}", "Command: poll_grain_data - IP: {ip}, port: {port}, inbound device: {device}, sub-device address: {address}, sensors: {points}, timeout: {timeout}, resend rounds: {retries}"),
+    ("cmd.call.explainNetworkError", "命令调用: explain_network_error - 错误: {error}", "Command: explain_network_error - error: {error}"),
+    ("cmd.call.decodeResponseFrame", "命令调用: decode_response_frame - 数据长度: {length}, 粮仓: {granary}", "Command: decode_response_frame - data length: {length}, granary: {granary}"),
+    ("cmd.call.buildPollFrame", "命令调用: build_poll_frame - 分机地址: {address}, 时间: {timestamp}, 命令码: {command}", "Command: build_poll_frame - sub-device address: {address}, time: {timestamp}, command code: {command}"),
+    ("cmd.call.getLogs", "命令调用: get_logs - 级别过滤: {level}, 限制数量: {limit}", "Command: get_logs - level filter: {level}, limit: {limit}"),
+    ("cmd.call.addLog", "命令调用: add_log - 级别: {level}, 消息长度: {length}", "Command: add_log - level: {level}, message length: {length}"),
+    ("cmd.frontendLog", "前端日志 [{level}]: {message}", "Frontend log [{level}]: {message}"),
+    ("cmd.addLogSucceeded", "成功添加日志，耗时: {elapsed}ms", "Log added, took {elapsed} ms"),
+    ("cmd.call.setLocale", "命令调用: set_locale - 语言: {locale}", "Command: set_locale - locale: {locale}"),
+    ("cmd.call.renderInspectionReport", "命令调用: render_inspection_report - 条件: {request}", "Command: render_inspection_report - request: {request}"),
+    ("cmd.call.saveInspectionReport", "命令调用: save_inspection_report - 条件: {request}, 路径: {path}, 打开: {open}", "Command: save_inspection_report - request: {request}, path: {path}, open: {open}"),
+    ("cmd.call.cancelRequest", "命令调用: cancel_request - 请求ID: {id}", "Command: cancel_request - request ID: {id}"),
+    ("cmd.requestNotFound", "请求 '{id}' 不存在或已结束", "Request '{id}' does not exist or has finished"),
+    ("cmd.call.savePollJob", "命令调用: save_poll_job - 任务: {id}, 名称: {name}, 计划: {schedule}, 目标: {count} 个", "Command: save_poll_job - job: {id}, name: {name}, schedule: {schedule}, targets: {count}"),
+    ("cmd.call.deletePollJob", "命令调用: delete_poll_job - 任务: {id}", "Command: delete_poll_job - job: {id}"),
+    ("cmd.call.runPollJob", "命令调用: run_poll_job - 任务: {id}", "Command: run_poll_job - job: {id}"),
+    ("cmd.call.cancelPollJob", "命令调用: cancel_poll_job - 任务: {id}", "Command: cancel_poll_job - job: {id}"),
+    ("cmd.call.listJobRuns", "命令调用: list_job_runs - 任务: {id}, 限制数量: {limit}", "Command: list_job_runs - job: {id}, limit: {limit}"),
+    ("cmd.call.sendHexSerial", "命令调用: send_hex_serial - 串口: {settings}, 超时: {timeout}, 请求ID: {requestId}", "Command: send_hex_serial - port: {settings}, timeout: {timeout}, request ID: {requestId}"),
+    ("cmd.call.startDeviceServer", "命令调用: start_device_server - 地址: {address}, 端口: {port}, 识别超时: {timeout}", "Command: start_device_server - address: {address}, port: {port}, identify timeout: {timeout}"),
+    ("cmd.call.openSession", "命令调用: open_session - 名称: {name}, IP: {ip}, 端口: {port}, 超时: {timeout}", "Command: open_session - name: {name}, IP: {ip}, port: {port}, timeout: {timeout}"),
+    ("cmd.call.sendOnSession", "命令调用: send_on_session - 名称: {name}, 数据长度: {length}, 请求ID: {requestId}", "Command: send_on_session - name: {name}, data length: {length}, request ID: {requestId}"),
+    ("cmd.call.closeSession", "命令调用: close_session - 名称: {name}", "Command: close_session - name: {name}"),
+    ("session.opening", "会话管理: 打开会话 '{name}' -> {ip}:{port}", "Session: opening session '{name}' -> {ip}:{port}"),
+    ("session.replaced", "会话管理: 会话 '{name}' 已存在，关闭旧连接", "Session: session '{name}' already exists, closing the old connection"),
+    ("session.hexInvalid", "会话管理: 解析16进制数据失败: {error}", "Session: failed to parse hex data: {error}"),
+    ("session.resending", "会话管理: 会话 '{name}' 收发失败，重连后重发: {error}", "Session: exchange on session '{name}' failed, resending after reconnecting: {error}"),
+    ("session.closed", "会话管理: 会话 '{name}' 已关闭", "Session: session '{name}' closed"),
+    ("session.reconnecting", "会话管理: 会话 '{name}' 第 {attempt} 次重连", "Session: session '{name}' reconnect attempt {attempt}"),
+    ("session.reconnected", "会话管理: 会话 '{name}' 重连成功，尝试次数: {attempt}", "Session: session '{name}' reconnected after {attempt} attempts"),
+    ("session.reconnectFailed", "会话管理: 会话 '{name}' 重连 {attempt} 次均失败: {error}", "Session: session '{name}' failed to reconnect after {attempt} attempts: {error}"),
+    ("session.reconnectRetry", "会话管理: 会话 '{name}' 重连失败，{delay}ms 后重试: {error}", "Session: session '{name}' failed to reconnect, retrying in {delay} ms: {error}"),
+    ("session.stateChanged", "会话管理: 会话 '{name}' 状态 {from} -> {to}", "Session: session '{name}' state {from} -> {to}"),
+    // 定时轮询
+    ("scheduler.invalidTime", "无效的时刻: {value}，应为 HH:MM", "Invalid time of day: {value}, expected HH:MM"),
+    ("scheduler.invalidWeekday", "无效的星期: {value}，应为1-7", "Invalid weekday: {value}, expected 1-7"),
+    ("scheduler.intervalTooShort", "执行间隔 {seconds} 秒过短，至少为 {minimum} 秒", "Interval of {seconds} s is too short, the minimum is {minimum} s"),
+    ("scheduler.emptySchedule", "每日计划至少需要一个时刻", "A daily schedule needs at least one time of day"),
+    ("scheduler.noTargets", "任务至少需要一个轮询目标", "A job needs at least one poll target"),
+    ("scheduler.jobNotFound", "定时任务 '{id}' 不存在", "Scheduled job '{id}' does not exist"),
+    ("scheduler.alreadyRunning", "定时任务 '{id}' 的上一次执行尚未结束", "The previous run of scheduled job '{id}' has not finished"),
+    ("scheduler.storage", "读写 {path} 失败: {error}", "Failed to access {path}: {error}"),
+    ("scheduler.saveFailed", "保存定时任务失败: {error}", "Failed to save the scheduled job: {error}"),
+    ("scheduler.deleteFailed", "删除定时任务 '{id}' 失败: {error}", "Failed to delete scheduled job '{id}': {error}"),
+    ("scheduler.manualRun", "已开始执行定时任务 '{id}'", "Started scheduled job '{id}'"),
+    ("scheduler.manualRunFailed", "执行定时任务 '{id}' 失败: {error}", "Failed to run scheduled job '{id}': {error}"),
+    ("scheduler.eventEmitFailed", "推送定时轮询事件失败: {error}", "Failed to emit scheduler event: {error}"),
+    ("scheduler.loadFailed", "定时轮询: 读取任务失败: {error}", "Scheduler: failed to read jobs: {error}"),
+    ("scheduler.loaded", "定时轮询: 已加载 {count} 个任务", "Scheduler: loaded {count} jobs"),
+    ("scheduler.loopStarted", "定时轮询: 调度循环已启动", "Scheduler: scheduling loop started"),
+    ("scheduler.jobSaved", "定时轮询: 已保存任务 '{name}' ({id})", "Scheduler: saved job '{name}' ({id})"),
+    ("scheduler.jobDeleted", "定时轮询: 已删除任务 {id}", "Scheduler: deleted job {id}"),
+    ("scheduler.jobsReplaced", "定时轮询: 已替换全部任务, 共 {count} 个", "Scheduler: replaced all jobs, {count} in total"),
+    ("scheduler.cancelling", "定时轮询: 取消正在执行的任务 {id}", "Scheduler: cancelling running job {id}"),
+    ("scheduler.warning", "定时轮询: {error}", "Scheduler: {error}"),
+    ("scheduler.jobStarted", "定时轮询: 开始执行任务 '{name}' ({id}), 触发方式: {trigger}, 目标: {count} 个", "Scheduler: running job '{name}' ({id}), trigger: {trigger}, targets: {count}"),
+    ("scheduler.alarmFailed", "定时轮询: 任务 {id} 判定告警失败: {error}", "Scheduler: job {id} failed to evaluate alarms: {error}"),
+    ("scheduler.historyFailed", "定时轮询: 任务 {id} 保存历史数据失败: {error}", "Scheduler: job {id} failed to save history data: {error}"),
+    ("scheduler.runSaveFailed", "定时轮询: 保存任务 {id} 的执行记录失败: {error}", "Scheduler: failed to save the run record of job {id}: {error}"),
+    ("scheduler.jobFinished", "定时轮询: 任务 '{name}' 执行完成, 成功: {succeeded}, 失败: {failed}, 耗时: {elapsed}ms", "Scheduler: job '{name}' finished, succeeded: {succeeded}, failed: {failed}, took {elapsed} ms"),
+    ("scheduler.fileBackedUp", "定时轮询: 任务文件 {path} 无法解析({error})，已改名为 {backup}", "Scheduler: job file {path} cannot be parsed ({error}), renamed to {backup}"),
+    ("scheduler.jobsSaved", "定时轮询: 已保存 {count} 个任务", "Scheduler: saved {count} jobs"),
+    ("scheduler.runSkipped", "定时轮询: 跳过无法解析的执行记录: {error}", "Scheduler: skipped unreadable run record: {error}"),
+    // 历史数据
+    ("history.database", "历史数据库操作失败: {error}", "History database error: {error}"),
+    ("history.invalidFrame", "无法保存无效的响应帧: {error}", "Cannot save an invalid response frame: {error}"),
+    ("history.invalidTime", "无效的查询时间: {value}，应为 YYYY-MM-DD HH:MM:SS 或 YYYY-MM-DD", "Invalid query time: {value}, expected YYYY-MM-DD HH:MM:SS or YYYY-MM-DD"),
+    ("history.notFound", "历史记录 {id} 不存在", "History record {id} does not exist"),
+    ("history.openFailed", "打开历史数据库失败，本次运行的数据将不会保存: {error}", "Failed to open the history database, readings from this session will not be kept: {error}"),
+    ("history.recordFailed", "保存历史数据失败: {error}", "Failed to save the reading to history: {error}"),
+    ("history.queried", "查询到 {count} 条历史记录，耗时: {elapsed}ms", "Found {count} history records, took {elapsed} ms"),
+    ("history.queryFailed", "查询历史数据失败: {error}", "Failed to query history: {error}"),
+    ("history.databaseOpened", "历史数据: 已打开数据库 {path}", "History: opened database {path}"),
+    ("history.stuckSensors", "历史数据: 粮仓 {granary} 分机 {address} 有 {count} 个测温点卡滞", "History: granary {granary} sub-device {address} has {count} stuck sensors"),
+    ("history.readingSaved", "历史数据: 已保存记录 {id}, 粮仓: {granary}, 分机: {address}, 测温点: {count} 个", "History: saved reading {id}, granary: {granary}, sub-device: {address}, sensors: {count}"),
+    ("history.readingsFound", "历史数据: 查询到 {count} 条记录, 条件: {query}", "History: found {count} readings, query: {query}"),
+    ("history.schemaUpgraded", "历史数据: 数据库已升级到版本 {version}", "History: database upgraded to version {version}"),
+    // 告警
+    ("alarm.emptyRuleName", "告警规则名称不能为空", "The alarm rule name must not be empty"),
+    ("alarm.invalidLimit", "无效的告警限值: {value}", "Invalid alarm limit: {value}"),
+    ("alarm.emptyHumidityRange", "湿度告警规则至少需要设置上限或下限", "A humidity rule needs a minimum or a maximum"),
+    ("alarm.invalidHumidityRange", "湿度下限 {min}% 高于上限 {max}%", "The humidity minimum {min}% is above the maximum {max}%"),
+    ("alarm.invalidSensorCount", "传感器故障规则的测温点数量必须大于0", "The sensor fault rule needs at least one sensor"),
+    ("alarm.alarmNotFound", "告警 {id} 不存在", "Alarm {id} does not exist"),
+    ("alarm.invalidState", "告警 {id} 当前状态为 {state}，不能执行该操作", "Alarm {id} is {state} and cannot be changed this way"),
+    ("alarm.emptyOperator", "请填写操作员", "An operator name is required"),
+    ("alarm.database", "告警数据库操作失败: {error}", "Alarm database error: {error}"),
+    ("alarm.openFailed", "打开告警数据库失败，本次运行的告警将不会保存: {error}", "Failed to open the alarm database, alarms from this session will not be kept: {error}"),
+    ("alarm.evaluateFailed", "判定告警失败: {error}", "Failed to evaluate alarm rules: {error}"),
+    ("alarm.saveFailed", "保存告警规则失败: {error}", "Failed to save the alarm rule: {error}"),
+    ("alarm.deleteFailed", "删除告警规则失败: {error}", "Failed to delete the alarm rule: {error}"),
+    ("alarm.handleFailed", "处理告警 {id} 失败: {error}", "Failed to update alarm {id}: {error}"),
+    ("alarm.eventEmitFailed", "推送告警事件失败: {error}", "Failed to emit alarm event: {error}"),
+    ("alarm.loadFailed", "告警: 读取规则失败: {error}", "Alarm: failed to read rules: {error}"),
+    ("alarm.loaded", "告警: 已加载 {count} 条规则", "Alarm: loaded {count} rules"),
+    ("alarm.ruleSaved", "告警: 已保存规则 '{name}' ({id})", "Alarm: saved rule '{name}' ({id})"),
+    ("alarm.ruleDeleted", "告警: 已删除规则 {id}", "Alarm: deleted rule {id}"),
+    ("alarm.rulesReplaced", "告警: 已替换全部规则, 共 {count} 条", "Alarm: replaced all rules, {count} in total"),
+    ("alarm.triggered", "告警: 规则 '{rule}' 触发, 粮仓: {granary}, 分机: {address}, 对象: {subject}, 值: {value}, 限值: {threshold}", "Alarm: rule '{rule}' triggered, granary: {granary}, sub-device: {address}, subject: {subject}, value: {value}, limit: {threshold}"),
+    ("alarm.acknowledged", "告警: 告警 {id} 已由 {operator} 确认", "Alarm: alarm {id} acknowledged by {operator}"),
+    ("alarm.cleared", "告警: 告警 {id} 已由 {operator} 清除", "Alarm: alarm {id} cleared by {operator}"),
+    ("alarm.schemaUpgraded", "告警: 数据库已升级到版本 {version}", "Alarm: database upgraded to version {version}"),
+    ("alarm.databaseOpened", "告警: 已打开数据库 {path}", "Alarm: opened database {path}"),
+    ("alarm.ruleSkipped", "告警: 跳过无法解析的规则 {id}: {error}", "Alarm: skipped unreadable rule {id}: {error}"),
+    // 应用配置
+    ("config.unsupportedVersion", "配置版本 {version} 高于当前程序支持的版本 {current}", "Config version {version} is newer than the supported version {current}"),
+    ("config.invalidDocument", "配置内容无效: {message}", "Invalid config: {message}"),
+    ("config.notAnObject", "配置内容不是JSON对象", "The config is not a JSON object"),
+    ("config.invalidVersion", "配置版本号无效: {value}，应为正整数", "Invalid config version {value}, expected a positive integer"),
+    ("config.emptyAddress", "默认设备地址不能为空", "The default device address must not be empty"),
+    ("config.invalidPort", "默认端口必须大于0", "The default port must be greater than 0"),
+    ("config.invalidSubDeviceAddress", "无效的默认分机地址: {value}，应为1-99", "Invalid default sub-device address: {value}, expected 1-99"),
+    ("config.invalidTimeout", "超时时间必须大于0", "The timeout must be greater than 0"),
+    ("config.invalidTemperatureRange", "第 {index} 个温度区间的下限不低于上限", "Temperature range {index} has a lower bound not below its upper bound"),
+    ("config.invalidStuckReadingCount", "卡滞判定的连续读数次数为 {value}，不能小于 {minimum}", "The stuck reading count is {value}, it must be at least {minimum}"),
+    ("config.storage", "读写 {path} 失败: {error}", "Failed to access {path}: {error}"),
+    ("config.saved", "已保存应用配置（版本 {version}）", "Saved app config (version {version})"),
+    ("config.saveFailed", "保存应用配置失败: {error}", "Failed to save the app config: {error}"),
+    ("config.eventEmitFailed", "推送配置事件失败: {error}", "Failed to emit config event: {error}"),
+    ("config.loadFailed", "配置: 读取配置文件失败: {error}", "Config: failed to read the configuration file: {error}"),
+    ("config.sectionsSaved", "配置: 已保存配置, 变化的分组: {sections}", "Config: configuration saved, changed sections: {sections}"),
+    ("config.defaultUsed", "配置: 配置文件 {path} 不存在，使用默认配置", "Config: configuration file {path} does not exist, using defaults"),
+    ("config.migrated", "配置: 配置文件已从版本 {from} 升级到版本 {to}", "Config: configuration file upgraded from version {from} to version {to}"),
+    ("config.loaded", "配置: 已加载配置文件 {path}", "Config: loaded configuration file {path}"),
+    ("config.backedUp", "配置: 配置文件 {path} 无效({error})，已改名为 {backup}", "Config: configuration file {path} is invalid ({error}), renamed to {backup}"),
+    // 测温点布局
+    ("layout.emptyDimension", "电缆行数、列数和每根电缆的测温点数量都必须大于0", "Cable rows, cable columns and points per cable must all be greater than 0"),
+    ("layout.tooManySensors", "布局共有 {count} 个测温点，超过上限 {maximum}", "The layout has {count} sensors, more than the maximum of {maximum}"),
+    ("layout.positionOutOfRange", "跳过的位置（第{layer}层 第{row}行 第{column}列）不在布局范围内", "Skipped position (layer {layer}, row {row}, column {column}) is outside the layout"),
+    ("layout.emptyGranary", "单独设置布局的粮仓名称不能为空", "A granary with its own layout must have a name"),
+    ("layout.saved", "已保存测温点布局，{count} 个粮仓单独设置", "Saved sensor layouts, {count} granaries with their own layout"),
+    ("layout.saveFailed", "保存测温点布局失败: {error}", "Failed to save sensor layouts: {error}"),
+    // 站点台账
+    ("inventory.emptyId", "库点、仓房和采集器的标识不能为空", "Depot, warehouse and collector IDs must not be empty"),
+    ("inventory.emptyName", "库点、仓房和采集器的名称不能为空", "Depot, warehouse and collector names must not be empty"),
+    ("inventory.duplicateId", "标识 {id} 已被使用", "The ID {id} is already in use"),
+    ("inventory.unknownDepot", "库点 {id} 不存在", "Depot {id} does not exist"),
+    ("inventory.unknownWarehouse", "仓房 {id} 不存在", "Warehouse {id} does not exist"),
+    ("inventory.depotNotEmpty", "库点 {id} 下仍有仓房，请先删除或移走仓房", "Depot {id} still has warehouses; delete or move them first"),
+    ("inventory.warehouseNotEmpty", "仓房 {id} 下仍有采集器，请先删除或移走采集器", "Warehouse {id} still has collectors; delete or move them first"),
+    ("inventory.invalidSubDeviceAddress", "无效的采集器分机地址: {value}，应为1-99", "Invalid collector sub-device address: {value}, expected 1-99"),
+    ("inventory.invalidCapacity", "仓房 {warehouse} 的仓容无效", "Warehouse {warehouse} has an invalid capacity"),
+    ("inventory.duplicateCollector", "{link} 的分机 {device} 已登记为其他采集器", "Sub-device {device} on {link} is already registered as another collector"),
+    ("inventory.layout", "仓房 {warehouse} 的测温点布局无效: {error}", "Warehouse {warehouse} has an invalid sensor layout: {error}"),
+    ("inventory.saveFailed", "保存站点台账失败: {error}", "Failed to save the inventory: {error}"),
+    ("inventory.deleteFailed", "删除 {id} 失败: {error}", "Failed to delete {id}: {error}"),
+    ("inventory.depotSaved", "站点台账: 已保存库点 '{name}' ({id})", "Inventory: saved depot '{name}' ({id})"),
+    ("inventory.depotDeleted", "站点台账: 已删除库点 {id}", "Inventory: deleted depot {id}"),
+    ("inventory.warehouseSaved", "站点台账: 已保存仓房 '{name}' ({id})", "Inventory: saved warehouse '{name}' ({id})"),
+    ("inventory.warehouseDeleted", "站点台账: 已删除仓房 {id}", "Inventory: deleted warehouse {id}"),
+    ("inventory.collectorSaved", "站点台账: 已保存采集器 '{name}' ({id}), 链路: {link}, 分机地址: {address}", "Inventory: saved collector '{name}' ({id}), link: {link}, sub-device address: {address}"),
+    ("inventory.collectorDeleted", "站点台账: 已删除采集器 {id}", "Inventory: deleted collector {id}"),
+    // 配置包
+    ("bundle.invalidFile", "不是有效的配置包: {message}", "Not a valid config bundle: {message}"),
+    ("bundle.unknownFormat", "不是配置包文件，格式标识为 {format}", "Not a config bundle file, the format is {format}"),
+    ("bundle.checksumMismatch", "配置包校验和不符（文件记录 {expected}，实际 {actual}），文件已损坏或被修改", "Config bundle checksum mismatch (recorded {expected}, actual {actual}); the file is damaged or was modified"),
+    ("bundle.duplicateId", "配置包中的标识 {id} 重复", "Duplicate ID {id} in the config bundle"),
+    ("bundle.storage", "读写配置包 {path} 失败: {error}", "Failed to access config bundle {path}: {error}"),
+    ("bundle.exported", "已导出配置包到 {path}", "Exported config bundle to {path}"),
+    ("bundle.exportFailed", "导出配置包失败: {error}", "Failed to export the config bundle: {error}"),
+    ("bundle.imported", "已导入配置包 {path}，{count} 项变化", "Imported config bundle {path}, {count} changes"),
+    ("bundle.importFailed", "导入配置包失败: {error}", "Failed to import the config bundle: {error}"),
+    ("bundle.written", "配置包: 已导出到 {path}, 校验和: {checksum}", "Bundle: exported to {path}, checksum: {checksum}"),
+    ("bundle.applied", "配置包: 已导入 {path}, {count} 项变化", "Bundle: imported {path}, {count} changes"),
+    ("bundle.rollingBack", "配置包: 导入失败，恢复导入前的内容: {error}", "Bundle: import failed, restoring the previous content: {error}"),
+    ("bundle.rollbackFailed", "配置包: 恢复导入前的内容失败: {error}", "Bundle: failed to restore the previous content: {error}"),
+    // 读数导出
+    ("export.noReadings", "没有符合条件的历史记录", "No readings match the export range"),
+    ("export.storage", "写入 {path} 失败: {error}", "Failed to write {path}: {error}"),
+    ("export.finished", "已导出 {count} 条记录到 {path}", "Exported {count} readings to {path}"),
+    ("export.failed", "导出读数失败: {error}", "Failed to export readings: {error}"),
+    ("export.sheet.summary", "汇总", "Summary"),
+    ("export.sheet.layer", "第{layer}层", "Layer {layer}"),
+    ("export.layer", "第{layer}层", "Layer {layer}"),
+    ("export.overall", "全仓", "Whole granary"),
+    ("export.blockTitle", "记录 #{id}  粮仓 {granary}  分机 {device}  采样时间 {time}", "Reading #{id}  Granary {granary}  Sub-device {device}  Sampled at {time}"),
+    ("export.rowLabel", "第{row}行", "Row {row}"),
+    ("export.columnLabel", "第{column}列", "Column {column}"),
+    ("export.column.readingId", "记录编号", "Reading ID"),
+    ("export.column.granary", "粮仓", "Granary"),
+    ("export.column.subDeviceAddress", "分机地址", "Sub-device"),
+    ("export.column.sampledAt", "采样时间", "Sampled at"),
+    ("export.column.sensorId", "测温点", "Sensor"),
+    ("export.column.layer", "层", "Layer"),
+    ("export.column.row", "行", "Row"),
+    ("export.column.column", "列", "Column"),
+    ("export.column.temperature", "温度(°C)", "Temperature (°C)"),
+    ("export.column.status", "状态", "Status"),
+    ("export.column.validCount", "有效测温点", "Valid sensors"),
+    ("export.column.maxTemperature", "最高温度(°C)", "Max (°C)"),
+    ("export.column.minTemperature", "最低温度(°C)", "Min (°C)"),
+    ("export.column.averageTemperature", "平均温度(°C)", "Average (°C)"),
+    ("export.column.faultCount", "异常测温点数", "Faulty sensors"),
+    ("export.column.faults", "异常测温点", "Faulty sensor list"),
+    ("export.column.indoorTemperature", "仓温(°C)", "Indoor temperature (°C)"),
+    ("export.column.indoorHumidity", "仓湿(%RH)", "Indoor humidity (%RH)"),
+    ("export.column.outdoorTemperature", "气温(°C)", "Outdoor temperature (°C)"),
+    ("export.column.outdoorHumidity", "气湿(%RH)", "Outdoor humidity (%RH)"),
+    ("export.status.valid", "正常", "Normal"),
+    ("export.status.openCircuit", "断路", "Open circuit"),
+    ("export.status.shortCircuit", "短路", "Short circuit"),
+    ("export.status.outOfRange", "超量程", "Out of range"),
+    ("export.status.stuck", "卡滞", "Stuck"),
+    ("export.status.missing", "缺失", "Missing"),
+    ("export.writtenRows", "读数导出: 已导出 {count} 条记录 {rows} 行到 {path}", "Export: exported {count} readings in {rows} rows to {path}"),
+    ("export.written", "读数导出: 已导出 {count} 条记录到 {path}", "Export: exported {count} readings to {path}"),
+    ("export.truncated", "读数导出: 符合条件的记录超过 {limit} 条，只导出最近的记录", "Export: more than {limit} readings match, exporting the most recent ones"),
+    // 检测报告
+    ("report.noReadings", "没有符合条件的历史记录", "No readings match the report range"),
+    ("report.unknownTemplate", "报告模板 {name} 不存在", "Report template {name} does not exist"),
+    ("report.invalidTemplate", "报告模板 {name} 格式错误: {error}", "Report template {name} is invalid: {error}"),
+    ("report.storage", "读写 {path} 失败: {error}", "Failed to access {path}: {error}"),
+    ("report.task", "生成检测报告的后台任务异常结束: {error}", "The inspection report task ended abnormally: {error}"),
+    ("report.template.unclosedTag", "第 {offset} 字节处的标签没有结束符", "The tag at byte {offset} is not closed"),
+    ("report.template.unexpectedClose", "区块 {name} 的结束标签没有对应的开始标签", "The closing tag of section {name} has no opening tag"),
+    ("report.template.unclosedSection", "区块 {name} 没有结束标签", "Section {name} has no closing tag"),
+    ("report.template.unescapedTag", "不支持不转义输出的标签 {tag}", "Unescaped tag {tag} is not supported"),
+    ("report.saved", "已生成 {count} 次检测的报告到 {path}", "Generated a report of {count} inspections to {path}"),
+    ("report.failed", "生成检测报告失败: {error}", "Failed to generate the inspection report: {error}"),
+    ("report.openFailed", "打开报告 {path} 失败: {error}", "Failed to open report {path}: {error}"),
+    ("report.written", "检测报告: 已使用模板 {template} 生成 {count} 次检测的报告到 {path}", "Report: rendered {count} inspections with template {template} to {path}"),
+    ("report.templateMissing", "检测报告: 模板 {path} 不存在", "Report: template {path} does not exist"),
+    // 粮情分析
+    ("analysis.emptyGranary", "未指定要分析的粮仓", "No granary specified for analysis"),
+    ("analysis.invalidRiseLimit", "升温速率上限必须大于0，当前为 {value}", "The rise limit must be greater than 0, got {value}"),
+    ("analysis.analyzed", "已分析粮仓 {granary} 的 {sensors} 个测温点，{flagged} 个升温过快", "Analyzed {sensors} sensors in granary {granary}, {flagged} rising too fast"),
+    ("analysis.failed", "粮情分析失败: {error}", "Trend analysis failed: {error}"),
+    ("analysis.truncated", "粮情分析: 粮仓 {granary} 的记录超过 {limit} 条，只分析最近的记录", "Analysis: granary {granary} has more than {limit} readings, analyzing the most recent ones"),
+    ("analysis.summary", "粮情分析: 粮仓 {granary} 分析了 {readings} 条记录, {sensors} 个测温点, {flagged} 个升温过快", "Analysis: granary {granary} analyzed {readings} readings, {sensors} sensors, {flagged} rising too fast"),
+    ("analysis.invalidSampleTime", "粮情分析: 跳过采样时间无效的记录 {id}: {time}", "Analysis: skipped reading {id} with invalid sample time: {time}"),
+    // 应用程序
+    ("app.started", "应用程序启动", "Application started"),
+    // 日志文件
+    ("logger.dirWriteFailed", "无法写入日志目录", "Cannot write the log directory setting"),
+    ("logger.dirNotSet", "日志目录未设置", "Log directory is not set"),
+    ("logger.dirReadFailed", "无法读取日志目录", "Cannot read the log directory setting"),
+    ("logger.cleared", "日志已清空", "Logs cleared"),
+    // 崩溃报告
+    ("crash.dirFailed", "创建崩溃日志目录失败: {error}", "Failed to create the crash log directory: {error}"),
+    ("crash.time", "崩溃时间", "Crash time"),
+    ("crash.message", "崩溃信息", "Panic message"),
+    ("crash.location", "发生位置", "Location"),
+    ("crash.file", "文件", "File"),
+    ("crash.line", "行号", "Line"),
+    ("crash.column", "列号", "Column"),
+    ("crash.backtrace", "调用栈", "Backtrace"),
+    ("crash.saved", "应用程序崩溃，崩溃日志已保存到: {path}", "The application crashed, crash log saved to: {path}"),
+    ("crash.createFailed", "无法创建崩溃日志文件: {path}", "Cannot create the crash log file: {path}"),
+    // 网络模块日志
+    ("network.log.noActiveInterface", "无活跃网络接口, 本地=127.0.0.1", "no active network interface, local=127.0.0.1"),
+    ("network.log.interfacesUnavailable", "无法获取网络接口信息", "network interfaces unavailable"),
+    ("network.log.unknownAddress", "未知", "unknown"),
+    ("network.log.emptyAddress", "网络模块: 设备地址为空", "Network: device address is empty"),
+    ("network.log.ipAddress", "网络模块: 地址 '{host}' 是有效的IP地址", "Network: address '{host}' is a valid IP address"),
+    ("network.log.resolving", "网络模块: 地址 '{host}' 不是IP地址，开始DNS解析", "Network: address '{host}' is not an IP address, resolving via DNS"),
+    ("network.log.resolveFailed", "网络模块: DNS解析 '{host}' 失败: {error}, 耗时: {elapsed}ms", "Network: DNS lookup of '{host}' failed: {error}, took {elapsed} ms"),
+    ("network.log.resolveEmpty", "网络模块: DNS解析 '{host}' 没有返回任何地址", "Network: DNS lookup of '{host}' returned no addresses"),
+    ("network.log.resolved", "网络模块: DNS解析 '{host}' 成功, 地址: {addresses}, 耗时: {elapsed}ms", "Network: resolved '{host}' to {addresses}, took {elapsed} ms"),
+    ("network.log.batchStarted", "网络模块: 开始批量轮询, 目标: {count} 个, 全局并发: {global}, 单主机并发: {perHost}", "Network: batch poll started, targets: {count}, global concurrency: {global}, per-host concurrency: {perHost}"),
+    ("network.log.batchTargetFailed", "网络模块: 批量轮询目标 {link} 分机 {address} 失败: {error}", "Network: batch poll of {link} sub-device {address} failed: {error}"),
+    ("network.log.batchTaskAborted", "网络模块: 批量轮询任务异常结束: {error}", "Network: batch poll task ended abnormally: {error}"),
+    ("network.log.batchFinished", "网络模块: 批量轮询完成, 成功: {succeeded}, 失败: {failed}, 失败类型: {kinds}, 总耗时: {elapsed}ms", "Network: batch poll finished, succeeded: {succeeded}, failed: {failed}, failures by kind: {kinds}, took {elapsed} ms"),
+    ("network.log.cancelled", "网络模块: 请求已取消", "Network: request cancelled"),
+    ("network.log.duplicateRequest", "网络模块: 请求ID '{id}' 重复登记，取消旧请求", "Network: request ID '{id}' registered twice, cancelling the previous request"),
+    ("network.log.sendStarted", "网络模块: 开始处理发送请求 -> {ip}:{port}", "Network: handling send request -> {ip}:{port}"),
+    ("network.log.timeout", "网络模块: 使用超时时间: {timeout}ms", "Network: using timeout {timeout} ms"),
+    ("network.log.hexParsed", "网络模块: 16进制字符串解析成功, 字节数: {length}, 耗时: {elapsed}ms", "Network: hex string parsed, bytes: {length}, took {elapsed} ms"),
+    ("network.log.hexParseFailed", "网络模块: 解析16进制数据失败: {error}, 耗时: {elapsed}ms", "Network: failed to parse hex data: {error}, took {elapsed} ms"),
+    ("network.log.hexFormatStarted", "网络模块: 开始将响应转换为16进制字符串, 原始字节数: {length}", "Network: converting response to a hex string, raw bytes: {length}"),
+    ("network.log.hexFormatted", "网络模块: 转换为16进制完成, 字符串长度: {length}, 耗时: {elapsed}ms", "Network: hex conversion finished, string length: {length}, took {elapsed} ms"),
+    ("network.log.listPortsFailed", "网络模块: 枚举串口失败: {error}", "Network: failed to enumerate serial ports: {error}"),
+    ("network.log.portsFound", "网络模块: 找到 {count} 个串口", "Network: found {count} serial ports"),
+    ("network.log.portClosed", "网络模块: 已关闭串口 {port}", "Network: closed serial port {port}"),
+    ("network.log.portClearFailed", "网络模块: 清空串口 {port} 接收缓冲区失败: {error}", "Network: failed to clear the receive buffer of serial port {port}: {error}"),
+    ("network.log.serialSendStarted", "网络模块: 开始处理串口发送请求 -> {port}, 参数: {settings}, 超时: {timeout}ms", "Network: handling serial send request -> {port}, settings: {settings}, timeout: {timeout} ms"),
+    ("network.log.hexInvalid", "网络模块: 解析16进制数据失败: {error}", "Network: failed to parse hex data: {error}"),
+    ("network.log.portOpening", "网络模块: 打开串口 {port}, 波特率: {baudRate}, 校验: {parity}, 停止位: {stopBits}", "Network: opening serial port {port}, baud rate: {baudRate}, parity: {parity}, stop bits: {stopBits}"),
+    ("network.log.portOpenFailed", "网络模块: 打开串口 {port} 失败: {error}, 耗时: {elapsed}ms", "Network: failed to open serial port {port}: {error}, took {elapsed} ms"),
+    ("network.log.serverStarting", "网络模块: 启动设备接入服务 -> {address}", "Network: starting device server -> {address}"),
+    ("network.log.listenFailed", "网络模块: 监听 {address} 失败: {error}, 错误类型: {kind}", "Network: failed to listen on {address}: {error}, error kind: {kind}"),
+    ("network.log.serverStarted", "网络模块: 设备接入服务已启动, 监听: {address}", "Network: device server started, listening on {address}"),
+    ("network.log.serverStopped", "网络模块: 设备接入服务已停止 ({address})", "Network: device server stopped ({address})"),
+    ("network.log.inboundDisconnected", "网络模块: 接入设备 '{device}' 已断开: {error}", "Network: inbound device '{device}' disconnected: {error}"),
+    ("network.log.inboundUnavailable", "网络模块: 接入设备 '{device}' 连接不可用，等待重新连入: {error}", "Network: connection of inbound device '{device}' is unusable, waiting for it to reconnect: {error}"),
+    ("network.log.inboundReplaced", "网络模块: 接入设备 '{device}' 重新连入，替换旧连接", "Network: inbound device '{device}' reconnected, replacing the old connection"),
+    ("network.log.inboundConnected", "网络模块: 接入设备 '{device}' 已连入, 对端: {peer}, 识别方式: {method}", "Network: inbound device '{device}' connected, peer: {peer}, identified by: {method}"),
+    ("network.log.accepted", "网络模块: 接受设备连接 {peer}", "Network: accepted device connection {peer}"),
+    ("network.log.acceptFailed", "网络模块: 接受设备连接失败: {error}", "Network: failed to accept device connection: {error}"),
+    ("network.log.listenerEnded", "网络模块: 设备接入监听已结束", "Network: device listener ended"),
+    ("network.log.registrationFailed", "网络模块: 读取设备 {peer} 的注册包失败: {error}", "Network: failed to read the registration packet from {peer}: {error}"),
+    ("network.log.closedBeforeData", "网络模块: 设备 {peer} 未发送数据即断开", "Network: device {peer} disconnected without sending data"),
+    ("network.log.unidentified", "网络模块: 设备 {peer} 在 {timeout}ms 内未发送可识别的注册包，使用对端地址作为设备标识", "Network: device {peer} sent no recognizable registration packet within {timeout} ms, using the peer address as device ID"),
+    ("network.log.identified", "网络模块: 设备 {peer} 识别为 '{device}', 注册包: {packet}, 耗时: {elapsed}ms", "Network: device {peer} identified as '{device}', registration packet: {packet}, took {elapsed} ms"),
+    ("network.log.inboundLost", "网络模块: 设备 {address} 已断开，等待设备重新连入", "Network: device {address} disconnected, waiting for it to reconnect"),
+    ("network.log.localInfo", "网络模块: 本地网络信息: {info}", "Network: local network info: {info}"),
+    ("network.log.closeFailed", "网络模块: 关闭到 {address} 的连接失败: {error}", "Network: failed to close the connection to {address}: {error}"),
+    ("network.log.staleDiscarded", "网络模块: 丢弃 {address} 遗留的 {length} 字节数据", "Network: discarded {length} stale bytes from {address}"),
+    ("network.log.connecting", "网络模块: 开始连接到 {address}", "Network: connecting to {address}"),
+    ("network.log.tryingAddress", "网络模块: 尝试连接地址 {address}", "Network: trying address {address}"),
+    ("network.log.addressFailed", "网络模块: 连接地址 {address} 失败: {error}", "Network: failed to connect to address {address}: {error}"),
+    ("network.log.connected", "网络模块: 连接 {address} 成功, 本地端口: {local}, 远程端: {peer}, 耗时: {elapsed}ms", "Network: connected to {address}, local port: {local}, peer: {peer}, took {elapsed} ms"),
+    ("network.log.ioFailed", "网络模块: {error} (本地: {local}), 耗时: {elapsed}ms, 错误详情: {detail}, 错误类型: {kind}", "Network: {error} (local: {local}), took {elapsed} ms, details: {detail}, error kind: {kind}"),
+    ("network.log.sending", "网络模块: 开始发送数据到 {address}, 字节数: {length}", "Network: sending data to {address}, bytes: {length}"),
+    ("network.log.sent", "网络模块: 数据发送成功, 字节数: {length}, 耗时: {elapsed}ms", "Network: data sent, bytes: {length}, took {elapsed} ms"),
+    ("network.log.waiting", "网络模块: 等待接收 {address} 的响应", "Network: waiting for the response from {address}"),
+    ("network.log.peerClosed", "网络模块: 对端关闭了连接, 已接收: {length} 字节", "Network: peer closed the connection, received: {length} bytes"),
+    ("network.log.unframedDiscarded", "网络模块: 丢弃响应帧之间的 {length} 字节非协议数据（心跳包或注册包）", "Network: discarded {length} bytes of non-protocol data between response frames (heartbeat or registration packet)"),
+    ("network.log.received", "网络模块: 接收到 {length} 字节数据, 总计: {total}, 耗时: {elapsed}ms", "Network: received {length} bytes, total: {total}, took {elapsed} ms"),
+    ("network.log.extraDiscarded", "网络模块: 完整帧之后有 {length} 字节多余数据，已丢弃", "Network: discarded {length} extra bytes after the complete frames"),
+    ("network.log.framesReceived", "网络模块: 已收到 {frames}/{expected} 个响应帧", "Network: received {frames}/{expected} response frames"),
+    ("network.log.framePending", "网络模块: 响应帧尚未完整, 已接收: {received}/{expected} 字节", "Network: response frame incomplete, received {received}/{expected} bytes"),
+    ("network.log.idle", "网络模块: {address} 超过 {idle}ms 没有新数据，视为响应结束", "Network: no new data from {address} for {idle} ms, treating the response as finished"),
+    ("network.log.readFailed", "网络模块: {error} (本地: {local}), 已接收: {received} 字节, 耗时: {elapsed}ms, 错误详情: {detail}, 错误类型: {kind}", "Network: {error} (local: {local}), received: {received} bytes, took {elapsed} ms, details: {detail}, error kind: {kind}"),
+    ("network.log.exchangeFinished", "网络模块: 整个网络交互过程完成 {address}, 发送: {sent} 字节, 接收: {received} 字节, 总耗时: {elapsed}ms", "Network: exchange with {address} finished, sent: {sent} bytes, received: {received} bytes, took {elapsed} ms"),
+    ("network.log.pollStarted", "网络模块: 开始读取粮情 -> {address}, 分机地址: {subDevice}, 测温点: {points}, 预期包数量: {packets}", "Network: reading grain data -> {address}, sub-device address: {subDevice}, sensors: {points}, expected packets: {packets}"),
+    ("network.log.pollFinished", "网络模块: 粮情读取完成 {address}, 共 {packets} 包, 测温点: {sensors} 个, 补发轮数: {rounds}, 总耗时: {elapsed}ms", "Network: grain data read from {address}, {packets} packets, sensors: {sensors}, resend rounds: {rounds}, took {elapsed} ms"),
+    ("network.log.pollFailed", "网络模块: 粮情读取失败 {address}, 缺失包序号: {missing}, 补发轮数: {rounds}, 总耗时: {elapsed}ms", "Network: failed to read grain data from {address}, missing packets: {missing}, resend rounds: {rounds}, took {elapsed} ms"),
+    ("network.log.resending", "网络模块: 第 {round} 轮补发请求, 缺失包序号: {packet} ({address})", "Network: resend round {round}, missing packet: {packet} ({address})"),
+    ("network.log.packetDamaged", "网络模块: 丢弃损坏的响应包，稍后补发: {error}", "Network: discarding a damaged response packet, it will be re-requested: {error}"),
+    ("network.log.resendFailed", "网络模块: 补发包序号 {packet} 未收到有效响应: {error}", "Network: no valid response for resent packet {packet}: {error}"),
+    ("network.log.readTimeout", "网络模块: 接收数据超时: {address} (本地: {local}), 耗时: {elapsed}ms", "Network: receive timed out: {address} (local: {local}), took {elapsed} ms"),
+    ("network.log.emptyResponse", "网络模块: 设备返回了0字节数据 (连接: {local} -> {address}), 耗时: {elapsed}ms", "Network: device returned 0 bytes (connection: {local} -> {address}), took {elapsed} ms"),
+    ("network.log.frameIncomplete", "网络模块: 响应帧不完整: {address} (本地: {local}), 已接收: {received}/{expected} 字节, 耗时: {elapsed}ms, 超时: {timedOut}", "Network: incomplete response frame: {address} (local: {local}), received {received}/{expected} bytes, took {elapsed} ms, timed out: {timedOut}"),
+    ("network.log.tailDiscarded", "网络模块: 末尾 {length} 字节响应帧不完整，已丢弃", "Network: discarded the incomplete {length}-byte frame at the end"),
+    ("network.log.framesMissing", "网络模块: 只收到 {frames}/{expected} 个响应帧, 耗时: {elapsed}ms", "Network: only {frames}/{expected} response frames received, took {elapsed} ms"),
+    ("network.log.receiveFinished", "网络模块: 成功接收数据, 字节数: {length}, 耗时: {elapsed}ms", "Network: data received, bytes: {length}, took {elapsed} ms"),
+    ("network.log.verifyFailed", "网络模块: 响应帧校验失败: {error} (连接: {local} -> {address}), 接收字节数: {length}", "Network: response frame verification failed: {error} (connection: {local} -> {address}), bytes received: {length}"),
+    ("network.log.verified", "网络模块: 响应帧校验通过", "Network: response frame verified"),
+    // 协议模块
+    ("protocol.timestampTooShort", "时间字段长度不足: {length} 字节", "Timestamp field too short: {length} bytes"),
+    ("protocol.invalidBcd", "时间字段第{index}字节 {byte} 不是有效的BCD码", "Byte {index} of the timestamp field ({byte}) is not valid BCD"),
+    ("protocol.invalidTimestamp", "时间字段不是合法的日期时间: {timestamp}", "Timestamp field is not a valid date and time: {timestamp}"),
+    ("protocol.yearOutOfRange", "年份必须在2000-2099范围内，当前为: {year}", "Year must be within 2000-2099, got {year}"),
+    ("protocol.unencodableBcd", "时间字段第{index}项 {value} 无法编码为BCD码", "Item {index} of the timestamp ({value}) cannot be encoded as BCD"),
+    ("protocol.subDeviceAddressRange", "分机地址必须在{min}-{max}范围内，当前为: {address}", "Sub-device address must be within {min}-{max}, got {address}"),
+    ("protocol.unframedIgnored", "协议模块: 位置[{offset}] 之后为非协议数据，忽略", "Protocol: non-protocol data after offset [{offset}], ignored"),
+    ("protocol.unframedDiscarded", "协议模块: 位置[{offset}] 起 {length} 字节为非协议数据，已丢弃", "Protocol: {length} bytes of non-protocol data from offset [{offset}] discarded"),
+    ("protocol.addressIgnored", "协议模块: 忽略分机地址 {address} 的响应帧，期望地址: {expected}", "Protocol: ignored response frame from sub-device {address}, expected {expected}"),
+    ("protocol.packetOutOfRange", "协议模块: 忽略超出范围的包序号 {packet}，预期包数量: {expected}", "Protocol: ignored out-of-range packet index {packet}, expected packets: {expected}"),
+    ("protocol.packetDuplicate", "协议模块: 包序号 {packet} 重复，忽略", "Protocol: duplicate packet index {packet}, ignored"),
+    ("protocol.packetReceived", "协议模块: 收到第 {packet}/{expected} 包", "Protocol: received packet {packet}/{expected}"),
+    ("protocol.packetsMerged", "协议模块: 多包响应重组完成，共 {packets} 包，有效测温点: {sensors} 个", "Protocol: multi-packet response reassembled, {packets} packets, valid sensors: {sensors}"),
+    ("protocol.addressOutOfRange", "协议模块: 分机地址超出范围: {address}", "Protocol: sub-device address out of range: {address}"),
+    ("protocol.requestBuilt", "协议模块: 构建请求帧完成，分机地址: {address}, 命令码: {command}, 校验和: {checksum}", "Protocol: request frame built, sub-device address: {address}, command code: {command}, checksum: {checksum}"),
+    ("protocol.sensorsMissing", "协议模块: 分机 {address} 缺失 {count} 个测温点的数据", "Protocol: sub-device {address} is missing data for {count} sensors"),
+    ("protocol.checksumVerified", "协议模块: 响应帧校验通过，校验和: {checksum}", "Protocol: response frame verified, checksum: {checksum}"),
+    ("protocol.decoding", "协议模块: 开始解析响应帧，长度: {length} 字节", "Protocol: decoding response frame, length: {length} bytes"),
+    ("protocol.verifyFailed", "协议模块: 响应帧校验失败: {error}", "Protocol: response frame verification failed: {error}"),
+    ("protocol.timestampFailed", "协议模块: 解析时间信息失败: {error}", "Protocol: failed to parse the timestamp: {error}"),
+    ("protocol.decoded", "协议模块: 响应帧解析完成，分机地址: {address}, 包序号: {packet}, 有效测温点: {sensors} 个, 异常测温点: {faults} 个", "Protocol: response frame decoded, sub-device address: {address}, packet index: {packet}, valid sensors: {sensors}, faulty sensors: {faults}"),
+    ("protocol.sensorDataEnd", "协议模块: 位置[{offset}] 起为 FF FF，温度数据结束", "Protocol: FF FF at offset [{offset}], end of temperature data"),
+    ("protocol.sensorIdOverflow", "协议模块: 第 {packet} 包第 {index} 个测温点的编号超出范围，忽略之后的数据", "Protocol: sensor {index} of packet {packet} has an ID out of range, ignoring the rest of the data"),
+    ("protocol.sensorFault", "协议模块: 传感器 #{sensor} 状态异常: {status}, 原始值: 0x{raw}", "Protocol: sensor #{sensor} is faulty: {status}, raw value: 0x{raw}"),
+    ("protocol.environmentSkipped", "协议模块: 帧长度不足 {length} 字节，跳过环境数据解析", "Protocol: frame shorter than {length} bytes, skipping environment data"),
+    // 16进制工具
+    ("hex.invalidValueMessage", "无效的16进制值: {value}", "Invalid hex value: {value}"),
+    ("hex.parseStarted", "HEX工具: 开始解析16进制字符串，长度: {length}", "Hex: parsing hex string, length: {length}"),
+    ("hex.valueCount", "HEX工具: 分割后的16进制值数量: {count}", "Hex: {count} hex values after splitting"),
+    ("hex.parsedValue", "HEX工具: 位置[{index}] '{value}' -> {byte}", "Hex: [{index}] '{value}' -> {byte}"),
+    ("hex.omitted", "HEX工具: ... 中间值省略 ...", "Hex: ... remaining values omitted ..."),
+    ("hex.invalidValue", "HEX工具: 解析失败 - 位置[{index}] '{value}' 不是有效的16进制值: {error}", "Hex: parse failed - '{value}' at [{index}] is not a valid hex value: {error}"),
+    ("hex.parsed", "HEX工具: 16进制解析成功，共转换 {count} 个字节", "Hex: parsed successfully, {count} bytes"),
+    ("hex.formatStarted", "HEX工具: 开始将字节数组转换为16进制字符串，字节数: {count}", "Hex: converting bytes to a hex string, bytes: {count}"),
+    ("hex.formattedValue", "HEX工具: 位置[{index}] {byte} -> '{text}'", "Hex: [{index}] {byte} -> '{text}'"),
+    ("hex.formatted", "HEX工具: 字节数组转换为16进制字符串完成，结果长度: {length}", "Hex: bytes converted to a hex string, result length: {length}"),
+];
+
+lazy_static! {
+    static ref CATALOG: HashMap<&'static str, (&'static str, &'static str)> = MESSAGES
+        .iter()
+        .map(|(key, zh, en)| (*key, (*zh, *en)))
+        .collect();
+}
+
+/// 查找指定语言的消息模板
+pub fn lookup(key: &str, locale: Locale) -> Option<&'static str> {
+    CATALOG.get(key).map(|(zh, en)| match locale {
+        Locale::ZhCn => *zh,
+        Locale::EnUs => *en,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(template: &str) -> BTreeSet<&str> {
+        template
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn keys_are_unique() {
+        assert_eq!(CATALOG.len(), MESSAGES.len());
+    }
+
+    #[test]
+    fn translations_use_same_placeholders() {
+        for (key, zh, en) in MESSAGES {
+            assert_eq!(placeholders(zh), placeholders(en), "消息 {} 的占位符不一致", key);
+        }
+    }
+}
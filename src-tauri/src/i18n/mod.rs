@@ -0,0 +1,112 @@
+// 多语言消息模块，按消息标识查找当前语言的文本
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::RwLock;
+
+// 导出消息目录
+mod catalog;
+
+/// 界面与诊断信息使用的语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    /// 简体中文
+    #[default]
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+    /// 美式英语
+    #[serde(rename = "en-US")]
+    EnUs,
+}
+
+impl Locale {
+    /// 解析语言标识，兼容 "zh"、"zh-CN"、"zh_CN"、"en"、"en-US" 等写法，不区分大小写
+    pub fn parse(tag: &str) -> Option<Locale> {
+        let tag = tag.trim().to_ascii_lowercase().replace('_', "-");
+        match tag.split('-').next() {
+            Some("zh") => Some(Locale::ZhCn),
+            Some("en") => Some(Locale::EnUs),
+            _ => None,
+        }
+    }
+
+    /// 标准语言标识
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh-CN",
+            Locale::EnUs => "en-US",
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tag())
+    }
+}
+
+// 当前语言，默认简体中文
+lazy_static! {
+    static ref CURRENT_LOCALE: RwLock<Locale> = RwLock::new(Locale::default());
+}
+
+/// 设置当前语言
+pub fn set_locale(locale: Locale) {
+    match CURRENT_LOCALE.write() {
+        Ok(mut current) => *current = locale,
+        Err(poisoned) => *poisoned.into_inner() = locale,
+    }
+}
+
+/// 获取当前语言
+pub fn current_locale() -> Locale {
+    match CURRENT_LOCALE.read() {
+        Ok(current) => *current,
+        Err(poisoned) => *poisoned.into_inner(),
+    }
+}
+
+/// 按当前语言翻译消息，并替换 `{名称}` 形式的占位符
+///
+/// # 参数
+///
+/// * `key` - 消息标识，如 "network.connectRefused"
+/// * `args` - 占位符名称与取值
+///
+/// # 返回值
+///
+/// 翻译后的文本；当前语言缺少该消息时使用中文，消息不存在时返回标识本身
+pub fn translate(key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let template = lookup(key).unwrap_or(key);
+    if args.is_empty() {
+        return template.to_string();
+    }
+
+    let mut text = template.to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+/// 查找当前语言的消息模板
+pub fn lookup(key: &str) -> Option<&'static str> {
+    catalog::lookup(key, current_locale()).or_else(|| catalog::lookup(key, Locale::ZhCn))
+}
+
+/// 按当前语言翻译消息
+///
+/// 用法: `tr!("network.connectRefused", address = context.address)`
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::i18n::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate(
+            $key,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+
+pub(crate) use tr;
@@ -0,0 +1,62 @@
+// 站点台账错误定义
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::i18n::tr;
+use crate::layout::LayoutError;
+
+/// 站点台账校验或修改失败的原因
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum InventoryError {
+    /// 库点、仓房或采集器标识为空
+    EmptyId,
+    /// 库点、仓房或采集器名称为空
+    EmptyName,
+    /// 同类条目的标识重复
+    DuplicateId { id: String },
+    /// 仓房所属的库点不存在
+    UnknownDepot { id: String },
+    /// 采集器所属的仓房不存在
+    UnknownWarehouse { id: String },
+    /// 库点下仍有仓房，不能删除
+    DepotNotEmpty { id: String },
+    /// 仓房下仍有采集器，不能删除
+    WarehouseNotEmpty { id: String },
+    /// 采集器的分机地址不在1-99范围内
+    InvalidSubDeviceAddress { value: u8 },
+    /// 仓房容量为负数或不是有限数值
+    InvalidCapacity { warehouse: String },
+    /// 两个采集器使用同一链路和分机地址，读取结果无法区分所属仓房
+    DuplicateCollector { link: String, sub_device_address: u8 },
+    /// 仓房的测温点布局无效
+    Layout { warehouse: String, error: LayoutError },
+}
+
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            InventoryError::EmptyId => tr!("inventory.emptyId"),
+            InventoryError::EmptyName => tr!("inventory.emptyName"),
+            InventoryError::DuplicateId { id } => tr!("inventory.duplicateId", id = id),
+            InventoryError::UnknownDepot { id } => tr!("inventory.unknownDepot", id = id),
+            InventoryError::UnknownWarehouse { id } => tr!("inventory.unknownWarehouse", id = id),
+            InventoryError::DepotNotEmpty { id } => tr!("inventory.depotNotEmpty", id = id),
+            InventoryError::WarehouseNotEmpty { id } => tr!("inventory.warehouseNotEmpty", id = id),
+            InventoryError::InvalidSubDeviceAddress { value } => {
+                tr!("inventory.invalidSubDeviceAddress", value = value)
+            }
+            InventoryError::InvalidCapacity { warehouse } => tr!("inventory.invalidCapacity", warehouse = warehouse),
+            InventoryError::DuplicateCollector {
+                link,
+                sub_device_address,
+            } => tr!("inventory.duplicateCollector", link = link, device = sub_device_address),
+            InventoryError::Layout { warehouse, error } => {
+                tr!("inventory.layout", warehouse = warehouse, error = error)
+            }
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for InventoryError {}
@@ -0,0 +1,271 @@
+// 站点台账模块，按库点 → 仓房 → 采集器管理多个站点的设备，读取结果按采集器归属到仓房
+use chrono::Local;
+use log::info;
+
+use crate::config::{ConfigError, ConfigStore};
+use crate::i18n::tr;
+use crate::network::link::DeviceLink;
+use crate::network::PollTarget;
+
+// 导出错误定义
+pub mod error;
+// 导出台账定义模块
+pub mod model;
+
+pub use error::InventoryError;
+pub use model::{Collector, Depot, InventoryConfig, Warehouse};
+
+/// 站点台账，保存在应用配置中，可克隆后在轮询和定时任务中使用
+#[derive(Clone)]
+pub struct Inventory {
+    config: ConfigStore,
+}
+
+impl Inventory {
+    /// 使用应用配置中的站点台账
+    pub fn new(config: ConfigStore) -> Self {
+        Inventory { config }
+    }
+
+    /// 全部库点、仓房和采集器
+    pub fn config(&self) -> InventoryConfig {
+        self.config.read(|config| config.inventory.clone())
+    }
+
+    /// 新建或更新库点，标识为空时生成
+    pub fn save_depot(&self, mut depot: Depot) -> Result<Depot, ConfigError> {
+        self.config.update(|config| {
+            let depots = &mut config.inventory.depots;
+            if depot.id.trim().is_empty() {
+                depot.id = generate_id("depot", depots.iter().map(|depot| depot.id.as_str()));
+            }
+            upsert(depots, depot.clone(), |existing| existing.id == depot.id);
+            Ok(())
+        })?;
+        info!("{}", tr!("inventory.depotSaved", name = depot.name, id = depot.id));
+        Ok(depot)
+    }
+
+    /// 删除库点，库点下仍有仓房时不能删除
+    ///
+    /// # 返回值
+    ///
+    /// 库点存在时返回true
+    pub fn delete_depot(&self, id: &str) -> Result<bool, ConfigError> {
+        let mut found = false;
+        self.config.update(|config| {
+            let inventory = &mut config.inventory;
+            if inventory.warehouses.iter().any(|warehouse| warehouse.depot_id == id) {
+                return Err(InventoryError::DepotNotEmpty { id: id.to_string() }.into());
+            }
+            found = remove(&mut inventory.depots, |depot| depot.id == id);
+            Ok(())
+        })?;
+        if found {
+            info!("{}", tr!("inventory.depotDeleted", id = id));
+        }
+        Ok(found)
+    }
+
+    /// 新建或更新仓房，标识为空时生成
+    pub fn save_warehouse(&self, mut warehouse: Warehouse) -> Result<Warehouse, ConfigError> {
+        self.config.update(|config| {
+            let warehouses = &mut config.inventory.warehouses;
+            if warehouse.id.trim().is_empty() {
+                warehouse.id = generate_id("warehouse", warehouses.iter().map(|warehouse| warehouse.id.as_str()));
+            }
+            upsert(warehouses, warehouse.clone(), |existing| existing.id == warehouse.id);
+            Ok(())
+        })?;
+        info!("{}", tr!("inventory.warehouseSaved", name = warehouse.name, id = warehouse.id));
+        Ok(warehouse)
+    }
+
+    /// 删除仓房，仓房下仍有采集器时不能删除；已保存的历史数据和告警保留
+    ///
+    /// # 返回值
+    ///
+    /// 仓房存在时返回true
+    pub fn delete_warehouse(&self, id: &str) -> Result<bool, ConfigError> {
+        let mut found = false;
+        self.config.update(|config| {
+            let inventory = &mut config.inventory;
+            if inventory.collectors.iter().any(|collector| collector.warehouse_id == id) {
+                return Err(InventoryError::WarehouseNotEmpty { id: id.to_string() }.into());
+            }
+            found = remove(&mut inventory.warehouses, |warehouse| warehouse.id == id);
+            Ok(())
+        })?;
+        if found {
+            info!("{}", tr!("inventory.warehouseDeleted", id = id));
+        }
+        Ok(found)
+    }
+
+    /// 新建或更新采集器，标识为空时生成
+    pub fn save_collector(&self, mut collector: Collector) -> Result<Collector, ConfigError> {
+        self.config.update(|config| {
+            let collectors = &mut config.inventory.collectors;
+            if collector.id.trim().is_empty() {
+                collector.id = generate_id("collector", collectors.iter().map(|collector| collector.id.as_str()));
+            }
+            upsert(collectors, collector.clone(), |existing| existing.id == collector.id);
+            Ok(())
+        })?;
+        info!(
+            "{}",
+            tr!(
+                "inventory.collectorSaved",
+                name = collector.name,
+                id = collector.id,
+                link = collector.link.describe(),
+                address = collector.sub_device_address
+            )
+        );
+        Ok(collector)
+    }
+
+    /// 删除采集器
+    ///
+    /// # 返回值
+    ///
+    /// 采集器存在时返回true
+    pub fn delete_collector(&self, id: &str) -> Result<bool, ConfigError> {
+        let mut found = false;
+        self.config.update(|config| {
+            found = remove(&mut config.inventory.collectors, |collector| collector.id == id);
+            Ok(())
+        })?;
+        if found {
+            info!("{}", tr!("inventory.collectorDeleted", id = id));
+        }
+        Ok(found)
+    }
+
+    /// 生成轮询目标，粮仓为采集器所属的仓房
+    ///
+    /// # 参数
+    ///
+    /// * `warehouse_id` - 只包含该仓房的采集器，为None时包含全部采集器
+    pub fn poll_targets(&self, warehouse_id: Option<&str>) -> Vec<PollTarget> {
+        self.config.read(|config| config.inventory.poll_targets(warehouse_id))
+    }
+
+    /// 未指定粮仓的目标按链路和分机地址查找采集器，归属到采集器所属的仓房
+    ///
+    /// 台账中没有对应采集器时保持不变，保存历史数据时使用链路描述
+    pub fn attribute(&self, target: &mut PollTarget) {
+        if target.granary.as_deref().is_some_and(|granary| !granary.trim().is_empty()) {
+            return;
+        }
+        target.granary = self.granary_for(&target.link, target.sub_device_address);
+    }
+
+    /// 按链路和分机地址查找采集器所属的仓房
+    pub fn granary_for(&self, link: &DeviceLink, sub_device_address: u8) -> Option<String> {
+        self.config.read(|config| {
+            config
+                .inventory
+                .collector_at(link, sub_device_address)
+                .map(|collector| collector.warehouse_id.clone())
+        })
+    }
+}
+
+/// 替换标识相同的条目，不存在时追加
+fn upsert<T>(items: &mut Vec<T>, item: T, same: impl Fn(&T) -> bool) {
+    match items.iter_mut().find(|existing| same(existing)) {
+        Some(existing) => *existing = item,
+        None => items.push(item),
+    }
+}
+
+/// 删除符合条件的条目，返回是否有条目被删除
+fn remove<T>(items: &mut Vec<T>, matches: impl Fn(&T) -> bool) -> bool {
+    let count = items.len();
+    items.retain(|item| !matches(item));
+    items.len() != count
+}
+
+/// 生成不与现有条目重复的标识
+fn generate_id<'a>(prefix: &str, existing: impl Iterator<Item = &'a str> + Clone) -> String {
+    let base = format!("{}-{}", prefix, Local::now().format("%Y%m%d%H%M%S"));
+    let mut id = base.clone();
+    let mut suffix = 1;
+    while existing.clone().any(|other| other == id) {
+        suffix += 1;
+        id = format!("{}-{}", base, suffix);
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("inventory-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn depot() -> Depot {
+        Depot {
+            id: "d1".to_string(),
+            name: "一号库".to_string(),
+            address: None,
+        }
+    }
+
+    fn warehouse() -> Warehouse {
+        Warehouse {
+            id: "w1".to_string(),
+            depot_id: "d1".to_string(),
+            name: "1号仓".to_string(),
+            grain_type: None,
+            capacity_tonnes: None,
+            layout: None,
+        }
+    }
+
+    fn collector() -> Collector {
+        Collector {
+            id: "c1".to_string(),
+            warehouse_id: "w1".to_string(),
+            name: "1号分机".to_string(),
+            link: DeviceLink::Tcp {
+                ip: "192.168.1.10".to_string(),
+                port: 2000,
+            },
+            sub_device_address: 1,
+            total_points: None,
+        }
+    }
+
+    #[test]
+    fn refuses_to_delete_parents_with_children() {
+        let dir = temp_dir("delete");
+        let inventory = Inventory::new(ConfigStore::with_listener(&dir.join("config.json"), |_| {}));
+        inventory.save_depot(depot()).unwrap();
+        inventory.save_warehouse(warehouse()).unwrap();
+        inventory.save_collector(collector()).unwrap();
+
+        assert_eq!(
+            inventory.delete_depot("d1"),
+            Err(InventoryError::DepotNotEmpty { id: "d1".to_string() }.into())
+        );
+        assert_eq!(
+            inventory.delete_warehouse("w1"),
+            Err(InventoryError::WarehouseNotEmpty { id: "w1".to_string() }.into())
+        );
+        assert_eq!(inventory.config().warehouses.len(), 1);
+
+        assert_eq!(inventory.delete_collector("c1"), Ok(true));
+        assert_eq!(inventory.delete_warehouse("w1"), Ok(true));
+        assert_eq!(inventory.delete_depot("d1"), Ok(true));
+        assert_eq!(inventory.delete_depot("d1"), Ok(false));
+        assert_eq!(inventory.config(), InventoryConfig::default());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,280 @@
+// 站点台账定义
+use serde::{Deserialize, Serialize};
+
+use super::InventoryError;
+use crate::layout::GranaryLayout;
+use crate::network::link::DeviceLink;
+use crate::network::PollTarget;
+
+/// 库点
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Depot {
+    /// 标识，新建时为空则自动生成
+    #[serde(default)]
+    pub id: String,
+    /// 名称
+    pub name: String,
+    /// 地址
+    #[serde(default)]
+    pub address: Option<String>,
+}
+
+/// 仓房
+///
+/// 仓房标识即历史数据、告警和测温点布局中的粮仓，修改标识后之前的历史数据不再归属该仓房
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Warehouse {
+    /// 标识，新建时为空则自动生成
+    #[serde(default)]
+    pub id: String,
+    /// 所属库点
+    pub depot_id: String,
+    /// 名称
+    pub name: String,
+    /// 储粮品种
+    #[serde(default)]
+    pub grain_type: Option<String>,
+    /// 仓容（吨）
+    #[serde(default)]
+    pub capacity_tonnes: Option<f64>,
+    /// 测温点布局，为None时使用测温点布局设置中该粮仓或默认的布局
+    #[serde(default)]
+    pub layout: Option<GranaryLayout>,
+}
+
+/// 测温采集器（分机）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Collector {
+    /// 标识，新建时为空则自动生成
+    #[serde(default)]
+    pub id: String,
+    /// 所属仓房
+    pub warehouse_id: String,
+    /// 名称
+    pub name: String,
+    /// 通信方式
+    pub link: DeviceLink,
+    /// 分机地址（1-99）
+    pub sub_device_address: u8,
+    /// 测温点总数，为None时按单包读取
+    #[serde(default)]
+    pub total_points: Option<usize>,
+}
+
+/// 站点台账：库点、仓房和采集器
+///
+/// 按库点 → 仓房 → 采集器逐级归属，各级分别保存并通过所属标识关联
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InventoryConfig {
+    pub depots: Vec<Depot>,
+    pub warehouses: Vec<Warehouse>,
+    pub collectors: Vec<Collector>,
+}
+
+impl InventoryConfig {
+    /// 校验名称、标识唯一性、归属关系以及采集器的链路和分机地址
+    pub fn validate(&self) -> Result<(), InventoryError> {
+        for (index, depot) in self.depots.iter().enumerate() {
+            check_entry(&depot.id, &depot.name, self.depots[..index].iter().map(|other| &other.id))?;
+        }
+        for (index, warehouse) in self.warehouses.iter().enumerate() {
+            check_entry(
+                &warehouse.id,
+                &warehouse.name,
+                self.warehouses[..index].iter().map(|other| &other.id),
+            )?;
+            if self.depot(&warehouse.depot_id).is_none() {
+                return Err(InventoryError::UnknownDepot {
+                    id: warehouse.depot_id.clone(),
+                });
+            }
+            if warehouse.capacity_tonnes.is_some_and(|capacity| !capacity.is_finite() || capacity < 0.0) {
+                return Err(InventoryError::InvalidCapacity {
+                    warehouse: warehouse.id.clone(),
+                });
+            }
+            if let Some(layout) = &warehouse.layout {
+                layout.validate().map_err(|error| InventoryError::Layout {
+                    warehouse: warehouse.id.clone(),
+                    error,
+                })?;
+            }
+        }
+        for (index, collector) in self.collectors.iter().enumerate() {
+            let previous = &self.collectors[..index];
+            check_entry(&collector.id, &collector.name, previous.iter().map(|other| &other.id))?;
+            if self.warehouse(&collector.warehouse_id).is_none() {
+                return Err(InventoryError::UnknownWarehouse {
+                    id: collector.warehouse_id.clone(),
+                });
+            }
+            if !(1..=99).contains(&collector.sub_device_address) {
+                return Err(InventoryError::InvalidSubDeviceAddress {
+                    value: collector.sub_device_address,
+                });
+            }
+            if previous.iter().any(|other| {
+                other.sub_device_address == collector.sub_device_address && other.link.same_device(&collector.link)
+            }) {
+                return Err(InventoryError::DuplicateCollector {
+                    link: collector.link.describe(),
+                    sub_device_address: collector.sub_device_address,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// 按标识查找库点
+    pub fn depot(&self, id: &str) -> Option<&Depot> {
+        self.depots.iter().find(|depot| depot.id == id)
+    }
+
+    /// 按标识查找仓房
+    pub fn warehouse(&self, id: &str) -> Option<&Warehouse> {
+        self.warehouses.iter().find(|warehouse| warehouse.id == id)
+    }
+
+    /// 按链路和分机地址查找采集器
+    pub fn collector_at(&self, link: &DeviceLink, sub_device_address: u8) -> Option<&Collector> {
+        self.collectors
+            .iter()
+            .find(|collector| collector.sub_device_address == sub_device_address && collector.link.same_device(link))
+    }
+
+    /// 生成轮询目标，粮仓为采集器所属的仓房
+    ///
+    /// # 参数
+    ///
+    /// * `warehouse_id` - 只包含该仓房的采集器，为None时包含全部采集器
+    pub fn poll_targets(&self, warehouse_id: Option<&str>) -> Vec<PollTarget> {
+        self.collectors
+            .iter()
+            .filter(|collector| warehouse_id.is_none_or(|id| collector.warehouse_id == id))
+            .map(|collector| PollTarget {
+                link: collector.link.clone(),
+                sub_device_address: collector.sub_device_address,
+                total_points: collector.total_points,
+                granary: Some(collector.warehouse_id.clone()),
+            })
+            .collect()
+    }
+}
+
+/// 校验标识和名称不为空且标识与之前的条目不重复
+fn check_entry<'a>(id: &str, name: &str, mut previous: impl Iterator<Item = &'a String>) -> Result<(), InventoryError> {
+    if id.trim().is_empty() {
+        return Err(InventoryError::EmptyId);
+    }
+    if name.trim().is_empty() {
+        return Err(InventoryError::EmptyName);
+    }
+    if previous.any(|other| other == id) {
+        return Err(InventoryError::DuplicateId { id: id.to_string() });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn depot(id: &str) -> Depot {
+        Depot {
+            id: id.to_string(),
+            name: format!("{}库点", id),
+            address: None,
+        }
+    }
+
+    fn warehouse(id: &str, depot_id: &str) -> Warehouse {
+        Warehouse {
+            id: id.to_string(),
+            depot_id: depot_id.to_string(),
+            name: format!("{}仓", id),
+            grain_type: None,
+            capacity_tonnes: None,
+            layout: None,
+        }
+    }
+
+    fn collector(id: &str, warehouse_id: &str, ip: &str, sub_device_address: u8) -> Collector {
+        Collector {
+            id: id.to_string(),
+            warehouse_id: warehouse_id.to_string(),
+            name: format!("{}分机", id),
+            link: DeviceLink::Tcp {
+                ip: ip.to_string(),
+                port: 2000,
+            },
+            sub_device_address,
+            total_points: None,
+        }
+    }
+
+    fn inventory() -> InventoryConfig {
+        InventoryConfig {
+            depots: vec![depot("d1")],
+            warehouses: vec![warehouse("w1", "d1"), warehouse("w2", "d1")],
+            collectors: vec![collector("c1", "w1", "192.168.1.10", 1), collector("c2", "w2", "192.168.1.10", 2)],
+        }
+    }
+
+    #[test]
+    fn accepts_valid_inventory() {
+        assert_eq!(inventory().validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_unknown_parent() {
+        let mut config = inventory();
+        config.warehouses.push(warehouse("w3", "d9"));
+        assert_eq!(config.validate(), Err(InventoryError::UnknownDepot { id: "d9".to_string() }));
+
+        let mut config = inventory();
+        config.collectors.push(collector("c3", "w9", "192.168.1.11", 1));
+        assert_eq!(config.validate(), Err(InventoryError::UnknownWarehouse { id: "w9".to_string() }));
+    }
+
+    #[test]
+    fn rejects_duplicate_ids() {
+        let mut config = inventory();
+        config.depots.push(depot("d1"));
+        assert_eq!(config.validate(), Err(InventoryError::DuplicateId { id: "d1".to_string() }));
+
+        let mut config = inventory();
+        config.warehouses.push(warehouse("w2", "d1"));
+        assert_eq!(config.validate(), Err(InventoryError::DuplicateId { id: "w2".to_string() }));
+
+        let mut config = inventory();
+        config.collectors.push(collector("c1", "w1", "192.168.1.11", 1));
+        assert_eq!(config.validate(), Err(InventoryError::DuplicateId { id: "c1".to_string() }));
+    }
+
+    #[test]
+    fn rejects_collectors_sharing_link_and_address() {
+        let mut config = inventory();
+        // 同一主机的另一端口是不同设备
+        config.collectors.push(Collector {
+            link: DeviceLink::Tcp {
+                ip: "192.168.1.10".to_string(),
+                port: 2001,
+            },
+            ..collector("c3", "w2", "192.168.1.10", 1)
+        });
+        assert_eq!(config.validate(), Ok(()));
+
+        config.collectors.push(collector("c4", "w2", "192.168.1.10", 1));
+        assert_eq!(
+            config.validate(),
+            Err(InventoryError::DuplicateCollector {
+                link: "192.168.1.10:2000".to_string(),
+                sub_device_address: 1,
+            })
+        );
+    }
+}
@@ -0,0 +1,37 @@
+// 测温点布局错误定义
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::i18n::tr;
+
+/// 测温点布局校验失败的原因
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum LayoutError {
+    /// 电缆行数、列数或每根电缆的测温点数量为0
+    EmptyDimension,
+    /// 测温点总数超出编号范围
+    TooManySensors { count: usize, maximum: usize },
+    /// 跳过的位置不在布局范围内
+    PositionOutOfRange { layer: u16, row: u16, column: u16 },
+    /// 单独设置布局的粮仓名称为空
+    EmptyGranary,
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            LayoutError::EmptyDimension => tr!("layout.emptyDimension"),
+            LayoutError::TooManySensors { count, maximum } => {
+                tr!("layout.tooManySensors", count = count, maximum = maximum)
+            }
+            LayoutError::PositionOutOfRange { layer, row, column } => {
+                tr!("layout.positionOutOfRange", layer = layer, row = row, column = column)
+            }
+            LayoutError::EmptyGranary => tr!("layout.emptyGranary"),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for LayoutError {}
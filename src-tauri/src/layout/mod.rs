@@ -0,0 +1,53 @@
+// 测温点布局模块，按粮仓的电缆排列和接线顺序确定每个测温点的层、行、列坐标
+use crate::config::{ConfigError, ConfigStore};
+use crate::network::TargetResult;
+
+// 导出错误定义
+pub mod error;
+// 导出布局定义模块
+pub mod model;
+
+pub use error::LayoutError;
+pub use model::{GranaryLayout, LayoutConfig, SensorPosition};
+
+/// 测温点布局，保存在应用配置中，可克隆后在告警判定和分析中使用
+#[derive(Clone)]
+pub struct Layouts {
+    config: ConfigStore,
+}
+
+impl Layouts {
+    /// 使用应用配置中的测温点布局
+    pub fn new(config: ConfigStore) -> Self {
+        Layouts { config }
+    }
+
+    /// 全部布局
+    pub fn config(&self) -> LayoutConfig {
+        self.config.read(|config| config.layouts.clone())
+    }
+
+    /// 校验并保存全部布局，保存后立即用于之后的解析、告警判定和分析
+    pub fn save(&self, layouts: LayoutConfig) -> Result<LayoutConfig, ConfigError> {
+        let config = self.config.update(|config| {
+            config.layouts = layouts;
+            Ok(())
+        })?;
+        Ok(config.layouts)
+    }
+
+    /// 粮仓使用的布局，台账中的仓房设置了布局时使用仓房的布局，都没有单独设置时返回默认布局
+    pub fn for_granary(&self, granary: &str) -> GranaryLayout {
+        self.config.read(|config| config.layout_for(granary).clone())
+    }
+
+    /// 为批量轮询结果中的测温点填写坐标
+    ///
+    /// 目标未设置粮仓时按链路描述查找布局，与保存历史数据时使用的粮仓一致
+    pub fn locate_target(&self, result: &mut TargetResult) {
+        if let Some(frame) = &mut result.frame {
+            let granary = result.target.granary.clone().unwrap_or_else(|| result.target.link.describe());
+            self.for_granary(&granary).locate(frame);
+        }
+    }
+}
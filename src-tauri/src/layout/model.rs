@@ -0,0 +1,318 @@
+// 粮仓测温点布局，将测温点编号映射为层、行、列坐标
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use super::LayoutError;
+use crate::history::HistoryReading;
+use crate::protocol::{ResponseFrame, SensorFault, SensorReading};
+
+/// 默认的电缆行数、列数和每根电缆的测温点数量，与前端三维视图的默认值一致
+const DEFAULT_DIMENSION: u16 = 8;
+/// 测温点编号的上限
+const MAX_SENSORS: usize = u16::MAX as usize;
+
+/// 测温点在粮仓中的坐标，均从1开始
+///
+/// 层号从粮面向下递增，行号和列号为测温电缆在平面上的排列位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SensorPosition {
+    /// 层号
+    pub layer: u16,
+    /// 行号
+    pub row: u16,
+    /// 列号
+    pub column: u16,
+}
+
+/// 测温点的接线编号顺序
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WiringOrder {
+    /// 按层编号：同一层的测温点逐行逐列编完后再编下一层
+    #[default]
+    ByLayer,
+    /// 按电缆编号：一根电缆从上到下编完后再编下一根，电缆逐行逐列排列
+    ByCable,
+}
+
+/// 单个粮仓的测温点布局
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GranaryLayout {
+    /// 测温电缆的行数
+    pub cable_rows: u16,
+    /// 测温电缆的列数
+    pub cable_columns: u16,
+    /// 每根电缆的测温点数量，即层数
+    pub points_per_cable: u16,
+    /// 接线编号顺序
+    #[serde(default)]
+    pub wiring: WiringOrder,
+    /// 没有安装测温点的位置，编号时跳过
+    #[serde(default)]
+    pub skipped: Vec<SensorPosition>,
+}
+
+impl Default for GranaryLayout {
+    fn default() -> Self {
+        GranaryLayout {
+            cable_rows: DEFAULT_DIMENSION,
+            cable_columns: DEFAULT_DIMENSION,
+            points_per_cable: DEFAULT_DIMENSION,
+            wiring: WiringOrder::default(),
+            skipped: Vec::new(),
+        }
+    }
+}
+
+/// 按布局计算出的测温点编号与坐标的对应关系
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SensorMap {
+    /// 第 `i` 项为编号 `i + 1` 的测温点坐标
+    positions: Vec<SensorPosition>,
+}
+
+impl SensorMap {
+    /// 测温点编号对应的坐标，编号超出布局时返回None
+    pub fn get(&self, sensor_id: u16) -> Option<SensorPosition> {
+        let index = usize::from(sensor_id).checked_sub(1)?;
+        self.positions.get(index).copied()
+    }
+
+    /// 按编号顺序遍历测温点编号和坐标
+    pub fn iter(&self) -> impl Iterator<Item = (u16, SensorPosition)> + '_ {
+        self.positions
+            .iter()
+            .enumerate()
+            .map(|(index, position)| (index as u16 + 1, *position))
+    }
+}
+
+impl GranaryLayout {
+    /// 校验布局参数
+    pub fn validate(&self) -> Result<(), LayoutError> {
+        if self.cable_rows == 0 || self.cable_columns == 0 || self.points_per_cable == 0 {
+            return Err(LayoutError::EmptyDimension);
+        }
+        if let Some(position) = self.skipped.iter().find(|position| !self.contains(position)) {
+            return Err(LayoutError::PositionOutOfRange {
+                layer: position.layer,
+                row: position.row,
+                column: position.column,
+            });
+        }
+        let count = self.sensor_count();
+        if count > MAX_SENSORS {
+            return Err(LayoutError::TooManySensors {
+                count,
+                maximum: MAX_SENSORS,
+            });
+        }
+        Ok(())
+    }
+
+    /// 安装了测温点的位置数量
+    pub fn sensor_count(&self) -> usize {
+        let total = usize::from(self.cable_rows) * usize::from(self.cable_columns) * usize::from(self.points_per_cable);
+        let skipped: HashSet<&SensorPosition> = self.skipped.iter().filter(|position| self.contains(position)).collect();
+        total - skipped.len()
+    }
+
+    /// 按接线顺序为每个测温点编号，跳过没有安装测温点的位置
+    ///
+    /// 超出编号上限的位置不参与编号，保存前由 `validate` 拒绝这类布局
+    pub fn sensor_map(&self) -> SensorMap {
+        let skipped: HashSet<&SensorPosition> = self.skipped.iter().collect();
+        let mut positions = Vec::with_capacity(self.sensor_count().min(MAX_SENSORS));
+        let mut push = |layer, row, column| {
+            let position = SensorPosition { layer, row, column };
+            if !skipped.contains(&position) && positions.len() < MAX_SENSORS {
+                positions.push(position);
+            }
+        };
+        match self.wiring {
+            WiringOrder::ByLayer => {
+                for layer in 1..=self.points_per_cable {
+                    for row in 1..=self.cable_rows {
+                        for column in 1..=self.cable_columns {
+                            push(layer, row, column);
+                        }
+                    }
+                }
+            }
+            WiringOrder::ByCable => {
+                for row in 1..=self.cable_rows {
+                    for column in 1..=self.cable_columns {
+                        for layer in 1..=self.points_per_cable {
+                            push(layer, row, column);
+                        }
+                    }
+                }
+            }
+        }
+        SensorMap { positions }
+    }
+
+    /// 为响应帧中的测温点读数和异常测温点填写坐标
+    pub fn locate(&self, frame: &mut ResponseFrame) {
+        let map = self.sensor_map();
+        locate_sensors(&map, &mut frame.sensors, &mut frame.faults);
+    }
+
+    /// 为历史记录中的测温点读数和异常测温点填写坐标，未读取测温点时不做处理
+    pub fn locate_reading(&self, reading: &mut HistoryReading) {
+        let map = self.sensor_map();
+        locate_sensors(
+            &map,
+            reading.sensors.as_deref_mut().unwrap_or_default(),
+            reading.faults.as_deref_mut().unwrap_or_default(),
+        );
+    }
+
+    /// 位置是否在布局范围内
+    fn contains(&self, position: &SensorPosition) -> bool {
+        (1..=self.points_per_cable).contains(&position.layer)
+            && (1..=self.cable_rows).contains(&position.row)
+            && (1..=self.cable_columns).contains(&position.column)
+    }
+}
+
+/// 按编号填写测温点坐标，编号超出布局时坐标为None
+fn locate_sensors(map: &SensorMap, sensors: &mut [SensorReading], faults: &mut [SensorFault]) {
+    for sensor in sensors {
+        sensor.position = map.get(sensor.sensor_id);
+    }
+    for fault in faults {
+        fault.position = map.get(fault.sensor_id);
+    }
+}
+
+/// 全部粮仓的测温点布局
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutConfig {
+    /// 没有单独设置布局的粮仓使用的默认布局
+    #[serde(default)]
+    pub default: GranaryLayout,
+    /// 按粮仓单独设置的布局
+    #[serde(default)]
+    pub granaries: BTreeMap<String, GranaryLayout>,
+}
+
+impl LayoutConfig {
+    /// 校验默认布局和各粮仓的布局
+    pub fn validate(&self) -> Result<(), LayoutError> {
+        self.default.validate()?;
+        for (granary, layout) in &self.granaries {
+            if granary.trim().is_empty() {
+                return Err(LayoutError::EmptyGranary);
+            }
+            layout.validate()?;
+        }
+        Ok(())
+    }
+
+    /// 粮仓使用的布局，没有单独设置时返回默认布局
+    pub fn for_granary(&self, granary: &str) -> &GranaryLayout {
+        self.granaries.get(granary).unwrap_or(&self.default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(rows: u16, columns: u16, points: u16, wiring: WiringOrder) -> GranaryLayout {
+        GranaryLayout {
+            cable_rows: rows,
+            cable_columns: columns,
+            points_per_cable: points,
+            wiring,
+            skipped: Vec::new(),
+        }
+    }
+
+    fn position(layer: u16, row: u16, column: u16) -> SensorPosition {
+        SensorPosition { layer, row, column }
+    }
+
+    #[test]
+    fn numbers_by_layer() {
+        let map = layout(2, 3, 2, WiringOrder::ByLayer).sensor_map();
+
+        assert_eq!(map.get(1), Some(position(1, 1, 1)));
+        assert_eq!(map.get(2), Some(position(1, 1, 2)));
+        assert_eq!(map.get(4), Some(position(1, 2, 1)));
+        assert_eq!(map.get(7), Some(position(2, 1, 1)));
+        assert_eq!(map.get(12), Some(position(2, 2, 3)));
+        assert_eq!(map.get(13), None);
+        assert_eq!(map.get(0), None);
+    }
+
+    #[test]
+    fn numbers_by_cable() {
+        let map = layout(2, 3, 2, WiringOrder::ByCable).sensor_map();
+
+        assert_eq!(map.get(1), Some(position(1, 1, 1)));
+        assert_eq!(map.get(2), Some(position(2, 1, 1)));
+        assert_eq!(map.get(3), Some(position(1, 1, 2)));
+        assert_eq!(map.get(7), Some(position(1, 2, 1)));
+        assert_eq!(map.get(12), Some(position(2, 2, 3)));
+        assert_eq!(map.iter().count(), 12);
+    }
+
+    #[test]
+    fn skipped_positions_shift_numbering() {
+        let mut granary = layout(2, 2, 2, WiringOrder::ByCable);
+        granary.skipped = vec![position(2, 1, 1), position(1, 2, 2)];
+        granary.validate().unwrap();
+        let map = granary.sensor_map();
+
+        assert_eq!(granary.sensor_count(), 6);
+        assert_eq!(map.get(1), Some(position(1, 1, 1)));
+        // 第1根电缆的第2层没有安装测温点，编号2顺延到第2根电缆
+        assert_eq!(map.get(2), Some(position(1, 1, 2)));
+        assert_eq!(map.get(5), Some(position(2, 2, 1)));
+        assert_eq!(map.get(6), Some(position(2, 2, 2)));
+        assert_eq!(map.get(7), None);
+    }
+
+    #[test]
+    fn rejects_skipped_position_outside_layout() {
+        let mut granary = layout(2, 2, 2, WiringOrder::ByLayer);
+        granary.skipped = vec![position(3, 1, 1)];
+
+        assert_eq!(
+            granary.validate(),
+            Err(LayoutError::PositionOutOfRange {
+                layer: 3,
+                row: 1,
+                column: 1,
+            })
+        );
+        // 范围外的位置不计入跳过的数量
+        assert_eq!(granary.sensor_count(), 8);
+    }
+
+    #[test]
+    fn rejects_too_many_sensors() {
+        let mut granary = layout(256, 256, 1, WiringOrder::ByLayer);
+        assert_eq!(
+            granary.validate(),
+            Err(LayoutError::TooManySensors {
+                count: 65536,
+                maximum: MAX_SENSORS,
+            })
+        );
+
+        granary.skipped = vec![position(1, 256, 256)];
+        granary.validate().unwrap();
+        assert_eq!(granary.sensor_map().get(u16::MAX), Some(position(1, 256, 255)));
+    }
+
+    #[test]
+    fn rejects_empty_dimension() {
+        assert_eq!(layout(0, 2, 2, WiringOrder::ByLayer).validate(), Err(LayoutError::EmptyDimension));
+    }
+}
@@ -1,21 +1,56 @@
 // Tauri应用程序主模块
 
 // 导入模块
+mod alarm;
+mod analysis;
+mod bundle;
 mod commands;
+mod config;
+mod export;
+mod history;
+mod i18n;
+mod inventory;
+mod layout;
 mod network;
+pub mod protocol;
+mod report;
+mod scheduler;
 mod utils;
 mod logger;
 mod crash_logger;
 
 // 使用commands模块中的命令
-use commands::{send_hex_data, get_logs, add_log, clear_logs};
+use commands::{
+    send_hex_data, poll_grain_data, explain_network_error, decode_response_frame, build_poll_frame,
+    get_logs, add_log,
+    clear_logs, open_session, send_on_session, close_session, list_sessions, cancel_request,
+    list_serial_ports, send_hex_serial, set_locale, get_locale, start_device_server,
+    stop_device_server, get_device_server, list_inbound_devices, poll_batch, list_poll_jobs,
+    save_poll_job, delete_poll_job, run_poll_job, cancel_poll_job, list_job_runs, query_history,
+    get_history_reading, list_history_locations, record_reading, analyze_trends, list_alarm_rules,
+    save_alarm_rule, delete_alarm_rule, query_alarms, acknowledge_alarm, clear_alarm, get_sensor_layouts,
+    save_sensor_layouts, locate_sensors, get_app_config, save_app_config, validate_app_config, get_inventory,
+    save_depot, delete_depot, save_warehouse, delete_warehouse, save_collector, delete_collector,
+    list_inventory_targets, export_config_bundle, import_config_bundle, export_readings, list_report_templates,
+    render_inspection_report, save_inspection_report, SESSION_STATE_EVENT,
+    INBOUND_DEVICE_EVENT, SCHEDULER_EVENT, ALARM_EVENT, CONFIG_EVENT,
+};
+use alarm::{AlarmEngine, AlarmStore};
+use config::{ConfigEvent, ConfigSection, ConfigStore};
+use history::HistoryStore;
+use i18n::tr;
+use inventory::Inventory;
+use layout::Layouts;
+use network::{DeviceServer, RequestRegistry, SessionManager};
+use report::Reports;
+use scheduler::Scheduler;
 use tauri_plugin_log::{Target, TargetKind};
 use chrono::Local;
-use log::{info, LevelFilter};
+use log::{error, info, warn, LevelFilter};
 use std::path::PathBuf;
 use std::fs;
 use std::env;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 use dirs;
 
 // 打开日志目录的命令
@@ -103,15 +138,148 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_app::init())
         .plugin(tauri_plugin_dialog::init())
+        .setup(move |app| {
+            // 历史数据库无法打开时改用内存数据库，保证读取功能可用
+            let history = HistoryStore::open(&app_data_dir.join("history.db")).or_else(|e| {
+                error!("{}", tr!("history.openFailed", error = e));
+                HistoryStore::open_in_memory()
+            })?;
+            app.manage(history.clone());
+            // 应用配置保存在数据目录中，配置变化时推送事件到前端，历史数据设置和界面语言变化时同步生效
+            let handle = app.handle().clone();
+            let listener_history = history.clone();
+            let config = ConfigStore::with_listener(&app_data_dir.join("config.json"), move |event| {
+                let ConfigEvent::Changed { config, sections } = event;
+                if sections.contains(&ConfigSection::History) {
+                    listener_history.set_stuck_reading_count(config.history.stuck_reading_count);
+                }
+                if sections.contains(&ConfigSection::Locale) {
+                    i18n::set_locale(config.locale);
+                }
+                if let Err(e) = handle.emit(CONFIG_EVENT, event) {
+                    warn!("{}", tr!("config.eventEmitFailed", error = e));
+                }
+            });
+            history.set_stuck_reading_count(config.read(|config| config.history.stuck_reading_count));
+            i18n::set_locale(config.read(|config| config.locale));
+            app.manage(config.clone());
+            // 会话状态变化时推送事件到前端
+            let handle = app.handle().clone();
+            app.manage(SessionManager::with_listener(move |session| {
+                if let Err(e) = handle.emit(SESSION_STATE_EVENT, session) {
+                    warn!("{}", tr!("session.stateEmitFailed", error = e));
+                }
+            }));
+            // 接入设备上线或下线时推送事件到前端
+            let handle = app.handle().clone();
+            let server = DeviceServer::with_listener(move |event| {
+                if let Err(e) = handle.emit(INBOUND_DEVICE_EVENT, event) {
+                    warn!("{}", tr!("server.eventEmitFailed", error = e));
+                }
+            });
+            app.manage(server.clone());
+            // 测温点布局和站点台账保存在应用配置中，在解析、告警判定和分析时共用
+            let layouts = Layouts::new(config.clone());
+            app.manage(layouts.clone());
+            let inventory = Inventory::new(config.clone());
+            app.manage(inventory.clone());
+            // 检测报告的自定义模板保存在数据目录的模板目录中
+            app.manage(Reports::new(
+                &app_data_dir.join("report_templates"),
+                history.clone(),
+                layouts.clone(),
+                config,
+            ));
+            // 告警数据库同样在无法打开时改用内存数据库，告警事件推送到前端
+            let alarm_store = AlarmStore::open(&app_data_dir.join("alarms.db")).or_else(|e| {
+                error!("{}", tr!("alarm.openFailed", error = e));
+                AlarmStore::open_in_memory()
+            })?;
+            let handle = app.handle().clone();
+            let alarms = AlarmEngine::with_listener(alarm_store, history.clone(), layouts.clone(), move |event| {
+                if let Err(e) = handle.emit(ALARM_EVENT, event) {
+                    warn!("{}", tr!("alarm.eventEmitFailed", error = e));
+                }
+            });
+            app.manage(alarms.clone());
+            // 定时轮询在后台按计划执行，执行过程推送事件到前端
+            let handle = app.handle().clone();
+            let scheduler_dir = app_data_dir.join("scheduler");
+            let scheduler =
+                Scheduler::with_listener(&scheduler_dir, server, history, alarms, layouts, inventory, move |event| {
+                    if let Err(e) = handle.emit(SCHEDULER_EVENT, event) {
+                        warn!("{}", tr!("scheduler.eventEmitFailed", error = e));
+                    }
+                });
+            scheduler.start();
+            app.manage(scheduler);
+            app.manage(RequestRegistry::default());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             send_hex_data, 
+            poll_grain_data,
+            explain_network_error,
+            decode_response_frame,
+            build_poll_frame,
             get_logs, 
             add_log, 
             clear_logs,
+            open_session,
+            send_on_session,
+            close_session,
+            list_sessions,
+            cancel_request,
+            list_serial_ports,
+            send_hex_serial,
+            start_device_server,
+            stop_device_server,
+            get_device_server,
+            list_inbound_devices,
+            poll_batch,
+            list_poll_jobs,
+            save_poll_job,
+            delete_poll_job,
+            run_poll_job,
+            cancel_poll_job,
+            list_job_runs,
+            query_history,
+            get_history_reading,
+            list_history_locations,
+            record_reading,
+            analyze_trends,
+            list_alarm_rules,
+            save_alarm_rule,
+            delete_alarm_rule,
+            query_alarms,
+            acknowledge_alarm,
+            clear_alarm,
+            get_sensor_layouts,
+            save_sensor_layouts,
+            locate_sensors,
+            get_app_config,
+            save_app_config,
+            validate_app_config,
+            get_inventory,
+            save_depot,
+            delete_depot,
+            save_warehouse,
+            delete_warehouse,
+            save_collector,
+            delete_collector,
+            list_inventory_targets,
+            export_config_bundle,
+            import_config_bundle,
+            export_readings,
+            list_report_templates,
+            render_inspection_report,
+            save_inspection_report,
+            set_locale,
+            get_locale,
             open_log_directory
         ]);
 
-    info!("应用程序启动");
+    info!("{}", tr!("app.started"));
 
     builder
         .run(tauri::generate_context!())
@@ -7,6 +7,8 @@ use std::io::{self, BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::sync::RwLock;
 
+use crate::i18n::tr;
+
 // 初始化一个静态日志目录
 lazy_static! {
     static ref LOG_DIR: RwLock<String> = RwLock::new(String::new());
@@ -36,7 +38,7 @@ pub fn set_log_dir(dir: String) -> io::Result<()> {
         *log_dir = dir;
         Ok(())
     } else {
-        Err(io::Error::new(io::ErrorKind::Other, "无法写入日志目录"))
+        Err(io::Error::new(io::ErrorKind::Other, tr!("logger.dirWriteFailed")))
     }
 }
 
@@ -44,11 +46,11 @@ pub fn set_log_dir(dir: String) -> io::Result<()> {
 pub fn get_log_file_path() -> io::Result<PathBuf> {
     let log_dir = if let Ok(dir) = LOG_DIR.read() {
         if dir.is_empty() {
-            return Err(io::Error::new(io::ErrorKind::NotFound, "日志目录未设置"));
+            return Err(io::Error::new(io::ErrorKind::NotFound, tr!("logger.dirNotSet")));
         }
         dir.clone()
     } else {
-        return Err(io::Error::new(io::ErrorKind::Other, "无法读取日志目录"));
+        return Err(io::Error::new(io::ErrorKind::Other, tr!("logger.dirReadFailed")));
     };
 
     // 使用本地时区获取当前日期
@@ -64,11 +66,11 @@ pub fn get_log_file_path() -> io::Result<PathBuf> {
 pub fn ensure_log_dir() -> io::Result<()> {
     let log_dir = if let Ok(dir) = LOG_DIR.read() {
         if dir.is_empty() {
-            return Err(io::Error::new(io::ErrorKind::NotFound, "日志目录未设置"));
+            return Err(io::Error::new(io::ErrorKind::NotFound, tr!("logger.dirNotSet")));
         }
         dir.clone()
     } else {
-        return Err(io::Error::new(io::ErrorKind::Other, "无法读取日志目录"));
+        return Err(io::Error::new(io::ErrorKind::Other, tr!("logger.dirReadFailed")));
     };
 
     let path = Path::new(&log_dir);
@@ -188,7 +190,7 @@ pub fn clear_logs() -> io::Result<()> {
         file.set_len(0)?;
     }
 
-    info!("日志已清空");
+    info!("{}", tr!("logger.cleared"));
     Ok(())
 }
 
@@ -0,0 +1,99 @@
+// 设备地址解析模块，支持IPv4、IPv6（带或不带方括号）和主机名
+use log::{debug, error, info};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Instant;
+use tokio::net::lookup_host;
+
+use super::{ErrorContext, NetworkError, NetworkPhase};
+use crate::i18n::tr;
+
+/// 去掉IPv6地址两侧的方括号和首尾空白
+///
+/// # 参数
+///
+/// * `host` - 用户输入的主机，如 "192.168.1.10"、"[fe80::1]"、"fe80::1" 或 "device.local"
+pub fn normalize_host(host: &str) -> &str {
+    let host = host.trim();
+    host.strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .unwrap_or(host)
+}
+
+/// 格式化为 "主机:端口"，IPv6地址加方括号，用于日志和错误信息
+pub fn format_host_port(host: &str, port: u16) -> String {
+    let host = normalize_host(host);
+    if host.contains(':') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    }
+}
+
+/// 将设备地址解析为可连接的套接字地址列表
+///
+/// IP地址直接使用；主机名通过系统DNS解析，按解析结果的顺序返回全部地址
+///
+/// # 参数
+///
+/// * `host` - IP地址或主机名，IPv6地址可带方括号
+/// * `port` - 目标端口号
+/// * `local_addr_info` - 本地网络信息，用于错误诊断
+///
+/// # 返回值
+///
+/// 成功时返回至少一个地址，主机名无法解析时返回错误
+pub async fn resolve_device_address(
+    host: &str,
+    port: u16,
+    local_addr_info: &str,
+) -> Result<Vec<SocketAddr>, NetworkError> {
+    let host = normalize_host(host);
+    if host.is_empty() {
+        error!("{}", tr!("network.log.emptyAddress"));
+        return Err(NetworkError::InvalidRequest {
+            message: tr!("network.emptyAddress"),
+        });
+    }
+
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        debug!("{}", tr!("network.log.ipAddress", host = host));
+        return Ok(vec![SocketAddr::new(ip, port)]);
+    }
+
+    debug!("{}", tr!("network.log.resolving", host = host));
+    let resolve_start = Instant::now();
+    let address = format_host_port(host, port);
+    let addresses: Vec<SocketAddr> = match lookup_host((host, port)).await {
+        Ok(addresses) => addresses.collect(),
+        Err(e) => {
+            error!(
+                "{}",
+                tr!(
+                    "network.log.resolveFailed",
+                    host = host,
+                    error = e,
+                    elapsed = resolve_start.elapsed().as_millis()
+                )
+            );
+            let context = ErrorContext::new(&address, NetworkPhase::Resolve, resolve_start, local_addr_info);
+            return Err(NetworkError::Unresolvable(context.with_os_error(&e)));
+        }
+    };
+
+    if addresses.is_empty() {
+        error!("{}", tr!("network.log.resolveEmpty", host = host));
+        let context = ErrorContext::new(&address, NetworkPhase::Resolve, resolve_start, local_addr_info);
+        return Err(NetworkError::Unresolvable(context));
+    }
+
+    info!(
+        "{}",
+        tr!(
+            "network.log.resolved",
+            host = host,
+            addresses = format!("{:?}", addresses),
+            elapsed = resolve_start.elapsed().as_millis()
+        )
+    );
+    Ok(addresses)
+}
@@ -0,0 +1,79 @@
+// 网络错误排查建议模块，根据结构化错误生成面向用户的说明
+use serde::Serialize;
+
+use super::error::{ErrorContext, NetworkError};
+use crate::i18n::{lookup, tr};
+
+/// 面向用户的错误说明
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorAdvice {
+    /// 一句话描述
+    pub summary: String,
+    /// 可能原因
+    pub causes: Vec<String>,
+    /// 排查建议
+    pub suggestions: Vec<String>,
+}
+
+/// 生成网络错误的可能原因与排查建议，文本使用当前语言
+///
+/// # 参数
+///
+/// * `error` - 网络模块返回的错误
+///
+/// # 返回值
+///
+/// 包含摘要、可能原因和建议的说明
+pub fn advise(error: &NetworkError) -> ErrorAdvice {
+    let topic = match error {
+        NetworkError::BadHex { .. } => "badHex",
+        NetworkError::InvalidRequest { .. } => "invalidRequest",
+        NetworkError::Unresolvable(_) => "unresolvable",
+        NetworkError::ConnectRefused(_) => "connectRefused",
+        NetworkError::ConnectTimeout(_) => "connectTimeout",
+        NetworkError::Unreachable(_) => "unreachable",
+        NetworkError::Reset(_) | NetworkError::Closed(_) => "connectionLost",
+        NetworkError::WriteTimeout(_) => "writeTimeout",
+        NetworkError::ReadTimeout(_) => "readTimeout",
+        NetworkError::EmptyResponse(_) => "emptyResponse",
+        NetworkError::PortUnavailable(_) => "portUnavailable",
+        NetworkError::Io(_) => "io",
+        NetworkError::DamagedFrame(_) | NetworkError::IncompleteFrame { .. } => "damagedFrame",
+        NetworkError::MissingPackets { .. } => "missingPackets",
+        NetworkError::SessionNotFound { .. } => "sessionNotFound",
+        NetworkError::DeviceNotConnected { .. } => "deviceNotConnected",
+        NetworkError::Cancelled => "cancelled",
+    };
+
+    let context = error.context();
+    let address = context.map(|c| c.address.clone()).unwrap_or_default();
+    let host = context.map(host_of).unwrap_or_default();
+    let elapsed = context.map(|c| c.elapsed_ms).unwrap_or_default();
+    let lines = |kind: &str| -> Vec<String> {
+        let key = format!("advice.{}.{}", topic, kind);
+        if lookup(&key).is_none() {
+            return Vec::new();
+        }
+        tr!(&key, address = address, host = host, elapsed = elapsed)
+            .lines()
+            .map(str::to_string)
+            .collect()
+    };
+
+    ErrorAdvice {
+        summary: error.to_string(),
+        causes: lines("causes"),
+        suggestions: lines("suggestions"),
+    }
+}
+
+/// 从 "主机:端口" 中取出主机部分，用于 ping 等建议
+fn host_of(context: &ErrorContext) -> String {
+    let address = context.address.as_str();
+    let host = match address.rsplit_once(':') {
+        Some((host, port)) if port.parse::<u16>().is_ok() => host,
+        _ => address,
+    };
+    host.trim_start_matches('[').trim_end_matches(']').to_string()
+}
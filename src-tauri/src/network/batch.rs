@@ -0,0 +1,229 @@
+// 批量轮询模块，按全局和单主机并发上限同时读取多个分机
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use super::link::DeviceLink;
+use super::{DeviceServer, NetworkError, RequestControl};
+use crate::i18n::tr;
+use crate::protocol::{self, ResponseFrame};
+
+/// 默认同时进行的请求数量上限
+const DEFAULT_GLOBAL_LIMIT: usize = 8;
+/// 默认同一主机同时进行的请求数量上限
+const DEFAULT_PER_HOST_LIMIT: usize = 1;
+
+/// 批量轮询中的一个目标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollTarget {
+    /// 通信方式
+    pub link: DeviceLink,
+    /// 分机地址（1-99）
+    pub sub_device_address: u8,
+    /// 测温点总数，默认512；设置后编号在总数以内但没有数据的测温点标记为缺失
+    pub total_points: Option<usize>,
+    /// 粮仓，保存历史数据时使用，默认为链路描述
+    pub granary: Option<String>,
+}
+
+/// 批量轮询参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOptions {
+    /// 同时进行的请求数量上限，默认8
+    pub global_limit: Option<usize>,
+    /// 同一主机同时进行的请求数量上限，默认1；串口和接入设备固定为1
+    pub per_host_limit: Option<usize>,
+    /// 每个目标的超时时间（毫秒）
+    pub timeout_ms: Option<u64>,
+    /// 补发缺失包的最大轮数，默认2
+    pub max_retries: Option<u32>,
+}
+
+/// 单个目标的轮询结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetResult {
+    /// 目标在请求列表中的序号
+    pub index: usize,
+    /// 轮询目标
+    pub target: PollTarget,
+    /// 成功时的响应帧
+    pub frame: Option<ResponseFrame>,
+    /// 失败原因
+    pub error: Option<NetworkError>,
+    /// 从开始读取到结束的耗时（毫秒），不含排队等待
+    pub elapsed_ms: u64,
+}
+
+/// 批量轮询汇总
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSummary {
+    /// 目标总数
+    pub total: usize,
+    /// 成功数量
+    pub succeeded: usize,
+    /// 失败数量（含取消）
+    pub failed: usize,
+    /// 按错误类型统计的失败数量
+    pub failures_by_kind: BTreeMap<String, usize>,
+    /// 总耗时（毫秒）
+    pub elapsed_ms: u64,
+}
+
+/// 并发读取多个目标的粮情数据
+///
+/// 每个目标先占用所属主机的名额，再占用全局名额，避免等待忙碌主机时占住全局名额。
+/// 每完成一个目标调用一次 `on_result`，取消时未完成的目标以取消错误结束
+///
+/// # 参数
+///
+/// * `targets` - 轮询目标
+/// * `options` - 并发上限、超时与补发参数
+/// * `server` - 接入服务，读取接入设备时使用
+/// * `control` - 取消令牌，各目标共用
+/// * `on_result` - 单个目标完成时的回调，可补充读取结果（如保存历史数据时检测到的卡滞测温点）。
+///   回调在阻塞线程池中执行，可以直接写数据库
+///
+/// # 返回值
+///
+/// 全部目标结束后的汇总
+pub async fn poll_batch<F>(
+    targets: Vec<PollTarget>,
+    options: &BatchOptions,
+    server: &DeviceServer,
+    control: &RequestControl,
+    on_result: F,
+) -> BatchSummary
+where
+    F: Fn(&mut TargetResult) + Send + Sync + 'static,
+{
+    let batch_start = Instant::now();
+    let global_limit = options.global_limit.unwrap_or(DEFAULT_GLOBAL_LIMIT).max(1);
+    let per_host_limit = options.per_host_limit.unwrap_or(DEFAULT_PER_HOST_LIMIT).max(1);
+    let max_retries = options.max_retries.unwrap_or(2);
+    info!(
+        "{}",
+        tr!(
+            "network.log.batchStarted",
+            count = targets.len(),
+            global = global_limit,
+            perHost = per_host_limit
+        )
+    );
+
+    let global = Arc::new(Semaphore::new(global_limit));
+    let mut hosts: HashMap<String, Arc<Semaphore>> = HashMap::new();
+    let on_result = Arc::new(on_result);
+    let mut tasks = JoinSet::new();
+    let mut summary = BatchSummary {
+        total: targets.len(),
+        ..Default::default()
+    };
+
+    for (index, target) in targets.into_iter().enumerate() {
+        let (host_key, exclusive) = target.link.host_key();
+        let limit = if exclusive { 1 } else { per_host_limit };
+        let host = hosts
+            .entry(host_key)
+            .or_insert_with(|| Arc::new(Semaphore::new(limit)))
+            .clone();
+        let global = global.clone();
+        let server = server.clone();
+        let control = control.clone();
+        let on_result = on_result.clone();
+        let timeout_ms = options.timeout_ms;
+
+        tasks.spawn(async move {
+            let total_points = target.total_points.unwrap_or(protocol::response::SENSORS_PER_PACKET);
+            let mut started = Instant::now();
+            let outcome = control
+                .run(async {
+                    let _host = host.acquire_owned().await.map_err(|_| NetworkError::Cancelled)?;
+                    let _global = global.acquire_owned().await.map_err(|_| NetworkError::Cancelled)?;
+                    started = Instant::now();
+                    target
+                        .link
+                        .poll(
+                            &server,
+                            target.sub_device_address,
+                            total_points,
+                            timeout_ms,
+                            max_retries,
+                            &control,
+                        )
+                        .await
+                })
+                .await;
+
+            let (frame, error) = match outcome {
+                Ok(mut frame) => {
+                    if let Some(total_points) = target.total_points {
+                        frame.mark_missing(total_points);
+                    }
+                    (Some(frame), None)
+                }
+                Err(e) => {
+                    warn!(
+                        "{}",
+                        tr!(
+                            "network.log.batchTargetFailed",
+                            link = target.link.describe(),
+                            address = target.sub_device_address,
+                            error = e
+                        )
+                    );
+                    (None, Some(e))
+                }
+            };
+            let mut result = TargetResult {
+                index,
+                target,
+                frame,
+                error,
+                elapsed_ms: started.elapsed().as_millis() as u64,
+            };
+            // 保存历史数据、判定告警都是阻塞的SQLite操作，不能占住异步工作线程
+            tokio::task::spawn_blocking(move || {
+                on_result(&mut result);
+                result.error.map(|e| e.kind())
+            })
+            .await
+        });
+    }
+
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(Ok(None)) => summary.succeeded += 1,
+            Ok(Ok(Some(kind))) => {
+                summary.failed += 1;
+                *summary.failures_by_kind.entry(kind.to_string()).or_default() += 1;
+            }
+            Ok(Err(e)) | Err(e) => {
+                // 读取任务或结果回调异常结束时按I/O错误计入
+                warn!("{}", tr!("network.log.batchTaskAborted", error = e));
+                summary.failed += 1;
+                *summary.failures_by_kind.entry("io".to_string()).or_default() += 1;
+            }
+        }
+    }
+
+    summary.elapsed_ms = batch_start.elapsed().as_millis() as u64;
+    info!(
+        "{}",
+        tr!(
+            "network.log.batchFinished",
+            succeeded = summary.succeeded,
+            failed = summary.failed,
+            kinds = format!("{:?}", summary.failures_by_kind),
+            elapsed = summary.elapsed_ms
+        )
+    );
+    summary
+}
@@ -0,0 +1,147 @@
+// 网络请求控制模块，负责请求取消与进度上报
+use log::{debug, info};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+
+use super::NetworkError;
+use crate::i18n::tr;
+
+/// 网络请求进度
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "stage", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum NetworkProgress {
+    /// 设备地址已解析，`addresses` 为按尝试顺序排列的候选地址
+    Resolved { addresses: Vec<String> },
+    /// 已建立连接，`peer_addr` 为实际连接的地址
+    Connected { local_addr: String, peer_addr: String },
+    /// 请求已发送
+    Sent { bytes: usize },
+    /// 收到一段响应数据，`total` 为累计接收字节数
+    Received { bytes: usize, total: usize },
+}
+
+/// 进度回调
+pub type ProgressCallback = Arc<dyn Fn(&NetworkProgress) + Send + Sync>;
+
+/// 单次网络请求的控制参数：取消令牌与进度回调
+#[derive(Clone, Default)]
+pub struct RequestControl {
+    cancel: CancellationToken,
+    progress: Option<ProgressCallback>,
+}
+
+impl RequestControl {
+    /// 使用指定的取消令牌创建控制参数
+    pub fn new(cancel: CancellationToken) -> Self {
+        RequestControl {
+            cancel,
+            progress: None,
+        }
+    }
+
+    /// 设置进度回调
+    pub fn with_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&NetworkProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// 上报进度
+    pub fn report(&self, progress: NetworkProgress) {
+        if let Some(callback) = &self.progress {
+            callback(&progress);
+        }
+    }
+
+    /// 执行可取消的网络操作，取消时丢弃未完成的操作（连接随之关闭）并返回取消错误
+    pub async fn run<F, T>(&self, operation: F) -> Result<T, NetworkError>
+    where
+        F: Future<Output = Result<T, NetworkError>>,
+    {
+        tokio::select! {
+            _ = self.cancel.cancelled() => {
+                info!("{}", tr!("network.log.cancelled"));
+                Err(NetworkError::Cancelled)
+            }
+            result = operation => result,
+        }
+    }
+}
+
+/// 进行中的请求登记表，保存在Tauri托管状态中，用于按ID取消请求
+#[derive(Default)]
+pub struct RequestRegistry {
+    requests: Mutex<HashMap<String, (u64, CancellationToken)>>,
+    next_serial: AtomicU64,
+}
+
+impl RequestRegistry {
+    /// 登记一个请求，返回的登记项在释放时自动注销
+    pub fn register(&self, id: &str) -> RegisteredRequest<'_> {
+        let token = CancellationToken::new();
+        let serial = self.next_serial.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut requests) = self.requests.lock() {
+            if let Some((_, previous)) = requests.insert(id.to_string(), (serial, token.clone())) {
+                debug!("{}", tr!("network.log.duplicateRequest", id = id));
+                previous.cancel();
+            }
+        }
+        RegisteredRequest {
+            registry: self,
+            id: id.to_string(),
+            serial,
+            token,
+        }
+    }
+
+    /// 取消指定ID的请求
+    ///
+    /// # 返回值
+    ///
+    /// 请求存在时返回true
+    pub fn cancel(&self, id: &str) -> bool {
+        let requests = match self.requests.lock() {
+            Ok(requests) => requests,
+            Err(_) => return false,
+        };
+        match requests.get(id) {
+            Some((_, token)) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// 已登记的请求
+pub struct RegisteredRequest<'a> {
+    registry: &'a RequestRegistry,
+    id: String,
+    serial: u64,
+    token: CancellationToken,
+}
+
+impl RegisteredRequest<'_> {
+    /// 请求的取消令牌
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+}
+
+impl Drop for RegisteredRequest<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut requests) = self.registry.requests.lock() {
+            // 只注销自己登记的令牌，避免误删同ID的新请求
+            if requests.get(&self.id).is_some_and(|(serial, _)| *serial == self.serial) {
+                requests.remove(&self.id);
+            }
+        }
+    }
+}
@@ -0,0 +1,273 @@
+// 网络模块错误定义
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, ErrorKind};
+use std::time::Instant;
+
+use crate::i18n::tr;
+use crate::protocol::FrameError;
+
+/// 网络交互所处的阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NetworkPhase {
+    /// 解析设备地址
+    Resolve,
+    /// 建立连接
+    Connect,
+    /// 发送请求
+    Write,
+    /// 接收响应
+    Read,
+}
+
+impl fmt::Display for NetworkPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = match self {
+            NetworkPhase::Resolve => "phase.resolve",
+            NetworkPhase::Connect => "phase.connect",
+            NetworkPhase::Write => "phase.write",
+            NetworkPhase::Read => "phase.read",
+        };
+        write!(f, "{}", tr!(key))
+    }
+}
+
+/// 网络错误发生时的上下文，用于前端展示和问题排查
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorContext {
+    /// 目标地址（主机:端口）
+    pub address: String,
+    /// 出错时所处的阶段
+    pub phase: NetworkPhase,
+    /// 该阶段开始到出错经过的时间（毫秒）
+    pub elapsed_ms: u64,
+    /// 本机网络接口信息
+    pub local_interfaces: String,
+    /// 操作系统返回的原始错误信息
+    pub os_error: Option<String>,
+}
+
+impl ErrorContext {
+    /// 创建错误上下文，耗时从 `started` 开始计算
+    pub fn new(address: &str, phase: NetworkPhase, started: Instant, local_interfaces: &str) -> Self {
+        ErrorContext {
+            address: address.to_string(),
+            phase,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            local_interfaces: local_interfaces.to_string(),
+            os_error: None,
+        }
+    }
+
+    /// 附加操作系统返回的原始错误
+    pub fn with_os_error(mut self, error: &impl fmt::Display) -> Self {
+        self.os_error = Some(error.to_string());
+        self
+    }
+}
+
+/// 网络通信失败的原因
+///
+/// 只描述发生了什么，排查建议由 [`super::advice::advise`] 单独生成
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "detail", rename_all = "camelCase")]
+pub enum NetworkError {
+    /// 待发送的16进制数据格式错误
+    #[serde(rename_all = "camelCase")]
+    BadHex { message: String },
+    /// 请求参数无效，无法构建请求帧
+    #[serde(rename_all = "camelCase")]
+    InvalidRequest { message: String },
+    /// 主机名无法解析为IP地址
+    Unresolvable(ErrorContext),
+    /// 设备拒绝连接（端口没有程序监听）
+    ConnectRefused(ErrorContext),
+    /// 连接超时
+    ConnectTimeout(ErrorContext),
+    /// 网络或主机不可达
+    Unreachable(ErrorContext),
+    /// 连接被对端重置
+    Reset(ErrorContext),
+    /// 连接已被设备关闭
+    Closed(ErrorContext),
+    /// 发送数据超时
+    WriteTimeout(ErrorContext),
+    /// 在超时时间内没有收到任何数据
+    ReadTimeout(ErrorContext),
+    /// 设备关闭连接前没有返回任何数据
+    EmptyResponse(ErrorContext),
+    /// 串口不存在、被其他程序占用或参数不受支持
+    PortUnavailable(ErrorContext),
+    /// 其他操作系统I/O错误
+    Io(ErrorContext),
+    /// 收到的响应帧校验失败
+    DamagedFrame(FrameError),
+    /// 超时或连接关闭前只收到了部分响应帧
+    #[serde(rename_all = "camelCase")]
+    IncompleteFrame { received: usize, expected: usize },
+    /// 多包响应补发后仍有缺失的包
+    #[serde(rename_all = "camelCase")]
+    MissingPackets { missing: Vec<u8>, expected: usize },
+    /// 指定名称的会话不存在
+    #[serde(rename_all = "camelCase")]
+    SessionNotFound { name: String },
+    /// 指定的接入设备当前没有连入
+    #[serde(rename_all = "camelCase")]
+    DeviceNotConnected { device_id: String },
+    /// 请求被用户取消
+    Cancelled,
+}
+
+impl NetworkError {
+    /// 按I/O错误类型归类，超时错误按所处阶段区分
+    ///
+    /// # 参数
+    ///
+    /// * `error` - 操作系统返回的I/O错误
+    /// * `context` - 错误上下文，原始错误信息会附加到其中
+    pub fn from_io(error: &io::Error, context: ErrorContext) -> Self {
+        let context = context.with_os_error(error);
+        match error.kind() {
+            ErrorKind::ConnectionRefused => NetworkError::ConnectRefused(context),
+            ErrorKind::TimedOut | ErrorKind::WouldBlock => match context.phase {
+                NetworkPhase::Resolve => NetworkError::Unresolvable(context),
+                NetworkPhase::Connect => NetworkError::ConnectTimeout(context),
+                NetworkPhase::Write => NetworkError::WriteTimeout(context),
+                NetworkPhase::Read => NetworkError::ReadTimeout(context),
+            },
+            ErrorKind::NetworkUnreachable | ErrorKind::HostUnreachable => {
+                NetworkError::Unreachable(context)
+            }
+            ErrorKind::ConnectionReset | ErrorKind::ConnectionAborted | ErrorKind::BrokenPipe => {
+                NetworkError::Reset(context)
+            }
+            ErrorKind::UnexpectedEof | ErrorKind::NotConnected => NetworkError::Closed(context),
+            _ => NetworkError::Io(context),
+        }
+    }
+
+    /// 错误的上下文，与连接无关的错误返回None
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            NetworkError::Unresolvable(context)
+            | NetworkError::ConnectRefused(context)
+            | NetworkError::ConnectTimeout(context)
+            | NetworkError::Unreachable(context)
+            | NetworkError::Reset(context)
+            | NetworkError::Closed(context)
+            | NetworkError::WriteTimeout(context)
+            | NetworkError::ReadTimeout(context)
+            | NetworkError::EmptyResponse(context)
+            | NetworkError::PortUnavailable(context)
+            | NetworkError::Io(context) => Some(context),
+            _ => None,
+        }
+    }
+
+    /// 错误类型标识，与序列化后的 `kind` 字段相同，用于按类型统计
+    pub fn kind(&self) -> &'static str {
+        match self {
+            NetworkError::BadHex { .. } => "badHex",
+            NetworkError::InvalidRequest { .. } => "invalidRequest",
+            NetworkError::Unresolvable(_) => "unresolvable",
+            NetworkError::ConnectRefused(_) => "connectRefused",
+            NetworkError::ConnectTimeout(_) => "connectTimeout",
+            NetworkError::Unreachable(_) => "unreachable",
+            NetworkError::Reset(_) => "reset",
+            NetworkError::Closed(_) => "closed",
+            NetworkError::WriteTimeout(_) => "writeTimeout",
+            NetworkError::ReadTimeout(_) => "readTimeout",
+            NetworkError::EmptyResponse(_) => "emptyResponse",
+            NetworkError::PortUnavailable(_) => "portUnavailable",
+            NetworkError::Io(_) => "io",
+            NetworkError::DamagedFrame(_) => "damagedFrame",
+            NetworkError::IncompleteFrame { .. } => "incompleteFrame",
+            NetworkError::MissingPackets { .. } => "missingPackets",
+            NetworkError::SessionNotFound { .. } => "sessionNotFound",
+            NetworkError::DeviceNotConnected { .. } => "deviceNotConnected",
+            NetworkError::Cancelled => "cancelled",
+        }
+    }
+
+    /// 错误是否意味着连接已不可用，需要重新连接
+    pub fn is_connection_lost(&self) -> bool {
+        !matches!(
+            self,
+            NetworkError::BadHex { .. }
+                | NetworkError::InvalidRequest { .. }
+                | NetworkError::DamagedFrame(_)
+                | NetworkError::MissingPackets { .. }
+                | NetworkError::SessionNotFound { .. }
+                | NetworkError::DeviceNotConnected { .. }
+        )
+    }
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            NetworkError::BadHex { message } => tr!("network.badHex", message = message),
+            NetworkError::InvalidRequest { message } => {
+                tr!("network.invalidRequest", message = message)
+            }
+            NetworkError::Unresolvable(c) => tr!("network.unresolvable", address = c.address),
+            NetworkError::ConnectRefused(c) => tr!("network.connectRefused", address = c.address),
+            NetworkError::ConnectTimeout(c) => tr!(
+                "network.connectTimeout",
+                address = c.address,
+                elapsed = c.elapsed_ms
+            ),
+            NetworkError::Unreachable(c) => tr!("network.unreachable", address = c.address),
+            NetworkError::Reset(c) => tr!("network.reset", phase = c.phase, address = c.address),
+            NetworkError::Closed(c) => tr!("network.closed", phase = c.phase, address = c.address),
+            NetworkError::WriteTimeout(c) => tr!(
+                "network.writeTimeout",
+                address = c.address,
+                elapsed = c.elapsed_ms
+            ),
+            NetworkError::ReadTimeout(c) => tr!(
+                "network.readTimeout",
+                address = c.address,
+                elapsed = c.elapsed_ms
+            ),
+            NetworkError::EmptyResponse(c) => tr!("network.emptyResponse", address = c.address),
+            NetworkError::PortUnavailable(c) => tr!(
+                "network.portUnavailable",
+                address = c.address,
+                error = c.os_error.clone().unwrap_or_else(|| tr!("network.unknownError"))
+            ),
+            NetworkError::Io(c) => tr!(
+                "network.io",
+                phase = c.phase,
+                error = c.os_error.clone().unwrap_or_else(|| tr!("network.unknownError"))
+            ),
+            NetworkError::DamagedFrame(e) => tr!("network.damagedFrame", error = e),
+            NetworkError::IncompleteFrame { received, expected } => tr!(
+                "network.incompleteFrame",
+                received = received,
+                expected = expected
+            ),
+            NetworkError::MissingPackets { missing, expected } => tr!(
+                "network.missingPackets",
+                expected = expected,
+                missing = format!("{:?}", missing)
+            ),
+            NetworkError::SessionNotFound { name } => tr!("network.sessionNotFound", name = name),
+            NetworkError::DeviceNotConnected { device_id } => {
+                tr!("network.deviceNotConnected", device = device_id)
+            }
+            NetworkError::Cancelled => tr!("network.cancelled"),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+impl From<FrameError> for NetworkError {
+    fn from(e: FrameError) -> Self {
+        NetworkError::DamagedFrame(e)
+    }
+}
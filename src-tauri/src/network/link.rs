@@ -0,0 +1,90 @@
+// 设备通信方式，统一描述TCP、接入设备和串口三种链路
+use serde::{Deserialize, Serialize};
+
+use super::address::format_host_port;
+use super::serial::{poll_grain_data_serial, SerialSettings};
+use super::{poll_grain_data, DeviceServer, NetworkError, RequestControl};
+use crate::protocol::ResponseFrame;
+
+/// 设备的通信方式
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum DeviceLink {
+    /// 本机主动连接设备的TCP
+    Tcp { ip: String, port: u16 },
+    /// 设备主动连入本机接入服务
+    Inbound { device_id: String },
+    /// 串口（RS-485）
+    Serial(SerialSettings),
+}
+
+impl DeviceLink {
+    /// 链路描述，用于日志和结果展示
+    pub fn describe(&self) -> String {
+        match self {
+            DeviceLink::Tcp { ip, port } => format_host_port(ip, *port),
+            DeviceLink::Inbound { device_id } => device_id.clone(),
+            DeviceLink::Serial(settings) => settings.port_name.clone(),
+        }
+    }
+
+    /// 共用同一物理连接的链路标识，及该连接是否只能同时处理一个请求
+    ///
+    /// TCP按主机区分，同一主机的不同端口计为同一主机；串口和接入设备同一时间只能有一个请求
+    pub fn host_key(&self) -> (String, bool) {
+        match self {
+            DeviceLink::Tcp { ip, .. } => (
+                format!("tcp:{}", ip.trim().trim_start_matches('[').trim_end_matches(']').to_lowercase()),
+                false,
+            ),
+            DeviceLink::Inbound { device_id } => (format!("inbound:{}", device_id), true),
+            DeviceLink::Serial(settings) => (format!("serial:{}", settings.port_name), true),
+        }
+    }
+
+    /// 两个链路是否指向同一设备：TCP比较主机和端口，接入设备比较设备标识，串口比较端口名称
+    pub fn same_device(&self, other: &DeviceLink) -> bool {
+        match (self, other) {
+            (DeviceLink::Tcp { port, .. }, DeviceLink::Tcp { port: other_port, .. }) => {
+                port == other_port && self.host_key() == other.host_key()
+            }
+            _ => self.host_key() == other.host_key(),
+        }
+    }
+
+    /// 通过该链路读取粮情数据
+    ///
+    /// # 参数
+    ///
+    /// * `server` - 接入服务，读取接入设备时使用
+    /// * `sub_device_address` - 分机地址（1-99）
+    /// * `total_points` - 测温点总数，用于计算响应包数量
+    /// * `timeout_ms` - 超时时间（毫秒）
+    /// * `max_retries` - 补发缺失包的最大轮数
+    /// * `control` - 取消令牌与进度回调
+    pub async fn poll(
+        &self,
+        server: &DeviceServer,
+        sub_device_address: u8,
+        total_points: usize,
+        timeout_ms: Option<u64>,
+        max_retries: u32,
+        control: &RequestControl,
+    ) -> Result<ResponseFrame, NetworkError> {
+        match self {
+            DeviceLink::Tcp { ip, port } => {
+                poll_grain_data(ip, *port, sub_device_address, total_points, timeout_ms, max_retries, control)
+                    .await
+            }
+            DeviceLink::Inbound { device_id } => {
+                server
+                    .poll(device_id, sub_device_address, total_points, timeout_ms, max_retries, control)
+                    .await
+            }
+            DeviceLink::Serial(settings) => {
+                poll_grain_data_serial(settings, sub_device_address, total_points, timeout_ms, max_retries, control)
+                    .await
+            }
+        }
+    }
+}
@@ -0,0 +1,353 @@
+// 串口（RS-485/RS-232）通信链路
+use async_trait::async_trait;
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use serialport::{ClearBuffer, DataBits, Parity, SerialPort, SerialPortType, StopBits};
+use std::io::{self, ErrorKind};
+use std::time::{Duration, Instant};
+
+use super::transport::{poll_grain, request_once, Transport};
+use super::{ErrorContext, NetworkError, NetworkPhase, RequestControl};
+use crate::i18n::tr;
+use crate::protocol::ResponseFrame;
+use crate::utils::hex_utils::{format_bytes_to_hex, hex_string_to_bytes};
+
+/// 串口校验位
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SerialParity {
+    /// 无校验
+    #[default]
+    None,
+    /// 奇校验
+    Odd,
+    /// 偶校验
+    Even,
+}
+
+/// 串口停止位
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SerialStopBits {
+    /// 1位停止位
+    #[default]
+    One,
+    /// 2位停止位
+    Two,
+}
+
+/// 串口参数，数据位固定为8位
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerialSettings {
+    /// 串口名称，如 "COM3" 或 "/dev/ttyUSB0"
+    pub port_name: String,
+    /// 波特率，默认9600
+    #[serde(default = "default_baud_rate")]
+    pub baud_rate: u32,
+    /// 校验位
+    #[serde(default)]
+    pub parity: SerialParity,
+    /// 停止位
+    #[serde(default)]
+    pub stop_bits: SerialStopBits,
+    /// 字节间隔超时（毫秒），收到数据后超过该时间没有新数据即视为响应结束
+    #[serde(default = "default_inter_byte_timeout_ms")]
+    pub inter_byte_timeout_ms: u64,
+}
+
+fn default_baud_rate() -> u32 {
+    9600
+}
+
+fn default_inter_byte_timeout_ms() -> u64 {
+    100
+}
+
+/// 系统中可用的串口
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerialPortDescription {
+    /// 串口名称
+    pub name: String,
+    /// 串口类型: usb、pci、bluetooth 或 unknown
+    pub kind: String,
+    /// USB设备的厂商与产品描述
+    pub description: Option<String>,
+}
+
+/// 列出系统中可用的串口
+///
+/// # 返回值
+///
+/// 成功时返回串口列表，无法枚举串口时返回错误
+pub fn list_serial_ports() -> Result<Vec<SerialPortDescription>, NetworkError> {
+    let ports = serialport::available_ports().map_err(|e| {
+        error!("{}", tr!("network.log.listPortsFailed", error = e));
+        let context = ErrorContext::new("-", NetworkPhase::Connect, Instant::now(), "");
+        NetworkError::PortUnavailable(context.with_os_error(&e))
+    })?;
+
+    let ports: Vec<SerialPortDescription> = ports
+        .into_iter()
+        .map(|port| {
+            let (kind, description) = match port.port_type {
+                SerialPortType::UsbPort(usb) => {
+                    let description = [usb.manufacturer, usb.product]
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    let description = if description.is_empty() {
+                        format!("USB {:04X}:{:04X}", usb.vid, usb.pid)
+                    } else {
+                        description
+                    };
+                    ("usb", Some(description))
+                }
+                SerialPortType::PciPort => ("pci", None),
+                SerialPortType::BluetoothPort => ("bluetooth", None),
+                SerialPortType::Unknown => ("unknown", None),
+            };
+            SerialPortDescription {
+                name: port.port_name,
+                kind: kind.to_string(),
+                description,
+            }
+        })
+        .collect();
+
+    debug!("{}", tr!("network.log.portsFound", count = ports.len()));
+    Ok(ports)
+}
+
+/// 串口通信链路
+///
+/// 串口读写是阻塞操作，每次读写在后台线程中执行，完成后将串口交还给链路
+pub struct SerialTransport {
+    settings: SerialSettings,
+    port: Option<Box<dyn SerialPort>>,
+}
+
+impl SerialTransport {
+    /// 创建尚未打开的串口链路
+    pub fn new(settings: SerialSettings) -> Self {
+        SerialTransport {
+            settings,
+            port: None,
+        }
+    }
+
+    /// 在后台线程中使用串口，操作被取消时串口随后台任务结束而关闭
+    async fn with_port<R, F>(&mut self, operation: F) -> io::Result<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut dyn SerialPort) -> io::Result<R> + Send + 'static,
+    {
+        let mut port = self.port.take().ok_or(ErrorKind::NotConnected)?;
+        let (port, result) = tauri::async_runtime::spawn_blocking(move || {
+            let result = operation(port.as_mut());
+            (port, result)
+        })
+        .await
+        .map_err(io::Error::other)?;
+        self.port = Some(port);
+        result
+    }
+}
+
+#[async_trait]
+impl Transport for SerialTransport {
+    fn describe(&self) -> String {
+        self.settings.port_name.clone()
+    }
+
+    fn is_connected(&self) -> bool {
+        self.port.is_some()
+    }
+
+    async fn connect(&mut self, timeout_ms: u64, _control: &RequestControl) -> Result<(), NetworkError> {
+        if self.port.is_some() {
+            return Ok(());
+        }
+
+        let settings = self.settings.clone();
+        let opened = tauri::async_runtime::spawn_blocking(move || open_port(&settings, timeout_ms))
+            .await
+            .map_err(|e| {
+                let context = ErrorContext::new(&self.settings.port_name, NetworkPhase::Connect, Instant::now(), "");
+                NetworkError::Io(context.with_os_error(&e))
+            })?;
+        self.port = Some(opened?);
+        Ok(())
+    }
+
+    async fn close(&mut self) {
+        if self.port.take().is_some() {
+            debug!("{}", tr!("network.log.portClosed", port = self.settings.port_name));
+        }
+    }
+
+    async fn write_raw(&mut self, data: &[u8], wait: Duration) -> io::Result<()> {
+        let data = data.to_vec();
+        self.with_port(move |port| {
+            port.set_timeout(wait)?;
+            port.write_all(&data)?;
+            port.flush()
+        })
+        .await
+    }
+
+    async fn read_raw(&mut self, buffer: &mut [u8], wait: Duration) -> io::Result<usize> {
+        let capacity = buffer.len();
+        let received = self
+            .with_port(move |port| {
+                port.set_timeout(wait)?;
+                let mut chunk = vec![0u8; capacity];
+                let bytes_read = port.read(&mut chunk)?;
+                chunk.truncate(bytes_read);
+                Ok(chunk)
+            })
+            .await?;
+        buffer[..received.len()].copy_from_slice(&received);
+        Ok(received.len())
+    }
+
+    fn idle_timeout(&self) -> Option<Duration> {
+        Some(Duration::from_millis(self.settings.inter_byte_timeout_ms.max(1)))
+    }
+
+    fn discard_stale_data(&mut self) -> Result<(), NetworkError> {
+        // RS-485总线上可能残留其他分机的应答，发送前清空接收缓冲区
+        if let Some(port) = self.port.as_mut() {
+            if let Err(e) = port.clear(ClearBuffer::Input) {
+                warn!(
+                    "{}",
+                    tr!("network.log.portClearFailed", port = self.settings.port_name, error = e)
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 通过串口发送16进制数据并接收响应
+///
+/// # 参数
+///
+/// * `settings` - 串口参数
+/// * `data` - 16进制数据字符串，以空格分隔
+/// * `timeout_ms` - 打开串口、发送和等待响应各自的超时时间（毫秒）
+/// * `control` - 取消令牌与进度回调
+///
+/// # 返回值
+///
+/// 成功时返回响应的16进制字符串，与TCP通信的返回格式相同
+pub async fn send_hex_serial(
+    settings: &SerialSettings,
+    data: &str,
+    timeout_ms: Option<u64>,
+    control: &RequestControl,
+) -> Result<String, NetworkError> {
+    let actual_timeout = timeout_ms.unwrap_or(5000);
+    debug!(
+        "{}",
+        tr!(
+            "network.log.serialSendStarted",
+            port = settings.port_name,
+            settings = format!("{:?}", settings),
+            timeout = actual_timeout
+        )
+    );
+
+    let request = hex_string_to_bytes(data).map_err(|e| {
+        error!("{}", tr!("network.log.hexInvalid", error = e));
+        NetworkError::BadHex { message: e }
+    })?;
+
+    let mut transport = SerialTransport::new(settings.clone());
+    let response_data = control
+        .run(request_once(&mut transport, &request, actual_timeout, control))
+        .await?;
+
+    Ok(format_bytes_to_hex(&response_data))
+}
+
+/// 通过串口读取粮情数据，支持多包响应
+///
+/// # 参数
+///
+/// * `settings` - 串口参数
+/// * `sub_device_address` - 分机地址（1-99）
+/// * `total_points` - 测温点总数，用于计算响应包数量
+/// * `timeout_ms` - 超时时间（毫秒）
+/// * `max_retries` - 补发缺失包的最大轮数
+/// * `control` - 取消令牌与进度回调
+pub async fn poll_grain_data_serial(
+    settings: &SerialSettings,
+    sub_device_address: u8,
+    total_points: usize,
+    timeout_ms: Option<u64>,
+    max_retries: u32,
+    control: &RequestControl,
+) -> Result<ResponseFrame, NetworkError> {
+    let actual_timeout = timeout_ms.unwrap_or(5000);
+    let mut transport = SerialTransport::new(settings.clone());
+    let result = control
+        .run(poll_grain(
+            &mut transport,
+            sub_device_address,
+            total_points,
+            actual_timeout,
+            max_retries,
+            control,
+        ))
+        .await;
+    transport.close().await;
+    result
+}
+
+/// 按串口参数打开串口
+fn open_port(settings: &SerialSettings, actual_timeout: u64) -> Result<Box<dyn SerialPort>, NetworkError> {
+    info!(
+        "{}",
+        tr!(
+            "network.log.portOpening",
+            port = settings.port_name,
+            baudRate = settings.baud_rate,
+            parity = format!("{:?}", settings.parity),
+            stopBits = format!("{:?}", settings.stop_bits)
+        )
+    );
+    let open_start = Instant::now();
+
+    let parity = match settings.parity {
+        SerialParity::None => Parity::None,
+        SerialParity::Odd => Parity::Odd,
+        SerialParity::Even => Parity::Even,
+    };
+    let stop_bits = match settings.stop_bits {
+        SerialStopBits::One => StopBits::One,
+        SerialStopBits::Two => StopBits::Two,
+    };
+
+    serialport::new(&settings.port_name, settings.baud_rate)
+        .data_bits(DataBits::Eight)
+        .parity(parity)
+        .stop_bits(stop_bits)
+        .timeout(Duration::from_millis(actual_timeout))
+        .open()
+        .map_err(|e| {
+            error!(
+                "{}",
+                tr!(
+                    "network.log.portOpenFailed",
+                    port = settings.port_name,
+                    error = e,
+                    elapsed = open_start.elapsed().as_millis()
+                )
+            );
+            let context = ErrorContext::new(&settings.port_name, NetworkPhase::Connect, open_start, "");
+            NetworkError::PortUnavailable(context.with_os_error(&e))
+        })
+}
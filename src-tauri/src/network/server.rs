@@ -0,0 +1,592 @@
+// 设备接入服务模块，接受主动连入本机的设备（如4G DTU采集器）
+use chrono::Local;
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncReadExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::timeout_at;
+use tokio_util::sync::CancellationToken;
+
+use super::address::format_host_port;
+use super::tcp::TcpTransport;
+use super::transport::{exchange, poll_grain, Transport};
+use super::{ErrorContext, NetworkError, NetworkPhase, RequestControl, SendResult};
+use crate::i18n::tr;
+use crate::protocol::framing::starts_with_response_header;
+use crate::protocol::{ResponseFrame, ADDRESS_OFFSET};
+use crate::utils::hex_utils::{format_bytes_to_hex, hex_string_to_bytes};
+
+/// 等待设备发送注册包的默认时间（毫秒）
+const DEFAULT_IDENTIFY_TIMEOUT_MS: u64 = 30000;
+/// 注册包分段到达时，两段之间的最长间隔（毫秒）
+const REGISTRATION_IDLE_MS: u64 = 200;
+/// 注册包的最大长度，超过部分不参与识别
+const MAX_REGISTRATION_LENGTH: usize = 256;
+
+/// 设备标识的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IdentifiedBy {
+    /// 设备连入后发送的注册包或心跳包
+    Registration,
+    /// 设备连入后发送的第一个 AA B0 响应帧中的分机地址
+    SubDeviceAddress,
+    /// 超时未收到可识别的数据，使用设备的对端地址
+    PeerAddress,
+}
+
+/// 接入设备信息，返回给前端并随接入事件推送
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InboundDeviceInfo {
+    /// 设备标识，轮询时使用
+    pub device_id: String,
+    /// 设备的对端地址
+    pub peer_addr: String,
+    /// 设备标识的来源
+    pub identified_by: IdentifiedBy,
+    /// 按 AA B0 帧识别时的分机地址
+    pub sub_device_address: Option<u8>,
+    /// 设备连入的时间
+    pub connected_at: String,
+}
+
+/// 接入设备上线或下线事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum InboundDeviceEvent {
+    /// 设备已连入并完成识别
+    Connected { device: InboundDeviceInfo },
+    /// 设备连接已断开或被新的连接替换
+    Disconnected { device_id: String },
+}
+
+/// 接入服务运行状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerInfo {
+    /// 实际监听的地址
+    pub local_addr: String,
+    /// 等待设备注册包的时间（毫秒）
+    pub identify_timeout_ms: u64,
+    /// 当前连入的设备数量
+    pub device_count: usize,
+}
+
+/// 接入事件回调
+type EventListener = Box<dyn Fn(&InboundDeviceEvent) + Send + Sync>;
+
+/// 已连入的设备
+struct InboundDevice {
+    info: InboundDeviceInfo,
+    /// 连接序号，用于区分同一设备先后建立的连接
+    serial: u64,
+    transport: TcpTransport,
+}
+
+/// 设备表中的一项，连接序号和设备信息放在锁外，设备正在收发时也能读取
+struct DeviceEntry {
+    serial: u64,
+    info: InboundDeviceInfo,
+    device: Arc<tokio::sync::Mutex<InboundDevice>>,
+}
+
+/// 正在运行的监听
+struct RunningServer {
+    local_addr: SocketAddr,
+    identify_timeout_ms: u64,
+    stop: CancellationToken,
+}
+
+/// 监听任务与请求共用的状态
+struct ServerShared {
+    devices: Mutex<HashMap<String, DeviceEntry>>,
+    next_serial: AtomicU64,
+    listener: Option<EventListener>,
+}
+
+/// 设备接入服务，保存在Tauri托管状态中
+///
+/// 在指定端口接受设备连入，按注册包或首个响应帧识别设备，之后通过设备标识轮询。
+/// 同一设备上的请求串行执行。克隆得到的句柄共用同一服务
+#[derive(Clone)]
+pub struct DeviceServer {
+    shared: Arc<ServerShared>,
+    running: Arc<Mutex<Option<RunningServer>>>,
+}
+
+impl Default for DeviceServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeviceServer {
+    /// 创建不推送接入事件的接入服务
+    pub fn new() -> Self {
+        Self::build(None)
+    }
+
+    /// 创建接入服务，设备上线或下线时调用 `listener`
+    pub fn with_listener<F>(listener: F) -> Self
+    where
+        F: Fn(&InboundDeviceEvent) + Send + Sync + 'static,
+    {
+        Self::build(Some(Box::new(listener)))
+    }
+
+    fn build(listener: Option<EventListener>) -> Self {
+        DeviceServer {
+            shared: Arc::new(ServerShared {
+                devices: Mutex::new(HashMap::new()),
+                next_serial: AtomicU64::new(0),
+                listener,
+            }),
+            running: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// 启动监听，已在运行时先停止旧的监听
+    ///
+    /// # 参数
+    ///
+    /// * `bind_address` - 监听地址，如 "0.0.0.0" 或 "::"
+    /// * `port` - 监听端口
+    /// * `identify_timeout_ms` - 设备连入后等待注册包的时间（毫秒）
+    ///
+    /// # 返回值
+    ///
+    /// 成功时返回运行状态，端口被占用等情况返回错误
+    pub async fn start(
+        &self,
+        bind_address: &str,
+        port: u16,
+        identify_timeout_ms: Option<u64>,
+    ) -> Result<ServerInfo, NetworkError> {
+        self.stop();
+
+        let address = format_host_port(bind_address, port);
+        let identify_timeout_ms = identify_timeout_ms.unwrap_or(DEFAULT_IDENTIFY_TIMEOUT_MS);
+        info!("{}", tr!("network.log.serverStarting", address = address));
+        let bind_start = Instant::now();
+
+        let host = bind_address.trim().trim_start_matches('[').trim_end_matches(']');
+        let listener = TcpListener::bind((host, port)).await.map_err(|e| {
+            let context = ErrorContext::new(&address, NetworkPhase::Connect, bind_start, "");
+            let error = NetworkError::from_io(&e, context);
+            error!(
+                "{}",
+                tr!(
+                    "network.log.listenFailed",
+                    address = address,
+                    error = e,
+                    kind = format!("{:?}", e.kind())
+                )
+            );
+            error
+        })?;
+        let local_addr = listener.local_addr().map_err(|e| {
+            NetworkError::from_io(&e, ErrorContext::new(&address, NetworkPhase::Connect, bind_start, ""))
+        })?;
+
+        let stop = CancellationToken::new();
+        tauri::async_runtime::spawn(accept_loop(
+            self.shared.clone(),
+            listener,
+            identify_timeout_ms,
+            stop.clone(),
+        ));
+        *self.lock_running() = Some(RunningServer {
+            local_addr,
+            identify_timeout_ms,
+            stop,
+        });
+
+        info!("{}", tr!("network.log.serverStarted", address = local_addr));
+        Ok(ServerInfo {
+            local_addr: local_addr.to_string(),
+            identify_timeout_ms,
+            device_count: self.shared.lock_devices().len(),
+        })
+    }
+
+    /// 停止监听并断开全部接入设备
+    ///
+    /// # 返回值
+    ///
+    /// 停止前是否在运行
+    pub fn stop(&self) -> bool {
+        let Some(running) = self.lock_running().take() else {
+            return false;
+        };
+        running.stop.cancel();
+
+        let devices: Vec<_> = self.shared.lock_devices().drain().collect();
+        for (device_id, _) in devices {
+            self.shared.notify(&InboundDeviceEvent::Disconnected { device_id });
+        }
+        info!("{}", tr!("network.log.serverStopped", address = running.local_addr));
+        true
+    }
+
+    /// 当前运行状态，未运行时返回None
+    pub fn info(&self) -> Option<ServerInfo> {
+        self.lock_running().as_ref().map(|running| ServerInfo {
+            local_addr: running.local_addr.to_string(),
+            identify_timeout_ms: running.identify_timeout_ms,
+            device_count: self.shared.lock_devices().len(),
+        })
+    }
+
+    /// 列出当前连入的设备，同时清理已断开的连接
+    pub fn list(&self) -> Vec<InboundDeviceInfo> {
+        let devices: Vec<_> = self
+            .shared
+            .lock_devices()
+            .values()
+            .map(|entry| (entry.serial, entry.info.clone(), entry.device.clone()))
+            .collect();
+
+        let mut list = Vec::new();
+        for (serial, info, device) in devices {
+            // 正在收发的设备被占用时，跳过检测，避免列表查询被长时间阻塞
+            if let Ok(mut device) = device.try_lock() {
+                if let Err(e) = device.transport.discard_stale_data() {
+                    debug!(
+                        "{}",
+                        tr!("network.log.inboundDisconnected", device = info.device_id, error = e)
+                    );
+                    self.shared.remove(&info.device_id, serial);
+                    continue;
+                }
+            }
+            list.push(info);
+        }
+        list.sort_by(|a, b| a.device_id.cmp(&b.device_id));
+        list
+    }
+
+    /// 向接入设备发送16进制数据并读取响应
+    ///
+    /// # 参数
+    ///
+    /// * `device_id` - 设备标识
+    /// * `data` - 16进制数据字符串，以空格分隔
+    /// * `timeout_ms` - 发送和接收超时时间（毫秒）
+    /// * `control` - 取消令牌与进度回调
+    ///
+    /// # 返回值
+    ///
+    /// 成功时返回响应的16进制字符串和设备连接的地址
+    pub async fn send(
+        &self,
+        device_id: &str,
+        data: &str,
+        timeout_ms: Option<u64>,
+        control: &RequestControl,
+    ) -> Result<SendResult, NetworkError> {
+        let request = hex_string_to_bytes(data).map_err(|e| {
+            error!("{}", tr!("network.log.hexInvalid", error = e));
+            NetworkError::BadHex { message: e }
+        })?;
+        let actual_timeout = timeout_ms.unwrap_or(5000);
+
+        let device = self.shared.get(device_id)?;
+        let mut device = device.lock().await;
+        let result = control
+            .run(exchange(&mut device.transport, &request, actual_timeout, control))
+            .await;
+        let peer_addr = device.transport.peer_addr();
+        self.after_request(&mut device, &result).await;
+        result.map(|response| SendResult {
+            response: format_bytes_to_hex(&response),
+            peer_addr,
+        })
+    }
+
+    /// 读取接入设备的粮情数据，支持多包响应
+    ///
+    /// # 参数
+    ///
+    /// * `device_id` - 设备标识
+    /// * `sub_device_address` - 分机地址（1-99）
+    /// * `total_points` - 测温点总数，用于计算响应包数量
+    /// * `timeout_ms` - 超时时间（毫秒）
+    /// * `max_retries` - 补发缺失包的最大轮数
+    /// * `control` - 取消令牌与进度回调
+    pub async fn poll(
+        &self,
+        device_id: &str,
+        sub_device_address: u8,
+        total_points: usize,
+        timeout_ms: Option<u64>,
+        max_retries: u32,
+        control: &RequestControl,
+    ) -> Result<ResponseFrame, NetworkError> {
+        let actual_timeout = timeout_ms.unwrap_or(5000);
+        let device = self.shared.get(device_id)?;
+        let mut device = device.lock().await;
+        let result = control
+            .run(poll_grain(
+                &mut device.transport,
+                sub_device_address,
+                total_points,
+                actual_timeout,
+                max_retries,
+                control,
+            ))
+            .await;
+        self.after_request(&mut device, &result).await;
+        result
+    }
+
+    /// 请求失败且连接已不可用时移除设备，等待设备重新连入
+    async fn after_request<T>(&self, device: &mut InboundDevice, result: &Result<T, NetworkError>) {
+        let Err(e) = result else {
+            return;
+        };
+        // 取消时响应可能只收到一半，连接不能再复用
+        if e.is_connection_lost() {
+            warn!(
+                "{}",
+                tr!("network.log.inboundUnavailable", device = device.info.device_id, error = e)
+            );
+            device.transport.close().await;
+            self.shared.remove(&device.info.device_id, device.serial);
+        }
+    }
+
+    fn lock_running(&self) -> std::sync::MutexGuard<'_, Option<RunningServer>> {
+        match self.running.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl ServerShared {
+    fn lock_devices(&self) -> std::sync::MutexGuard<'_, HashMap<String, DeviceEntry>> {
+        match self.devices.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn get(&self, device_id: &str) -> Result<Arc<tokio::sync::Mutex<InboundDevice>>, NetworkError> {
+        self.lock_devices()
+            .get(device_id)
+            .map(|entry| entry.device.clone())
+            .ok_or_else(|| NetworkError::DeviceNotConnected {
+                device_id: device_id.to_string(),
+            })
+    }
+
+    /// 登记识别完成的设备，同一标识的旧连接被替换
+    fn register(&self, info: InboundDeviceInfo, stream: TcpStream) {
+        let device_id = info.device_id.clone();
+        let label = format!("{} ({})", device_id, info.peer_addr);
+        let serial = self.next_serial.fetch_add(1, Ordering::Relaxed);
+        let device = InboundDevice {
+            info: info.clone(),
+            serial,
+            transport: TcpTransport::accepted(stream, &label),
+        };
+
+        let previous = self.lock_devices().insert(
+            device_id.clone(),
+            DeviceEntry {
+                serial,
+                info: info.clone(),
+                device: Arc::new(tokio::sync::Mutex::new(device)),
+            },
+        );
+        if previous.is_some() {
+            warn!("{}", tr!("network.log.inboundReplaced", device = device_id));
+            self.notify(&InboundDeviceEvent::Disconnected {
+                device_id: device_id.clone(),
+            });
+        }
+        info!(
+            "{}",
+            tr!(
+                "network.log.inboundConnected",
+                device = device_id,
+                peer = info.peer_addr,
+                method = format!("{:?}", info.identified_by)
+            )
+        );
+        self.notify(&InboundDeviceEvent::Connected { device: info });
+    }
+
+    /// 移除设备，设备已被新连接替换时保留新连接
+    fn remove(&self, device_id: &str, serial: u64) {
+        let mut devices = self.lock_devices();
+        if devices.get(device_id).map(|entry| entry.serial) != Some(serial) {
+            return;
+        }
+        devices.remove(device_id);
+        drop(devices);
+        self.notify(&InboundDeviceEvent::Disconnected {
+            device_id: device_id.to_string(),
+        });
+    }
+
+    fn notify(&self, event: &InboundDeviceEvent) {
+        if let Some(listener) = &self.listener {
+            listener(event);
+        }
+    }
+}
+
+/// 接受设备连入，每个连接单独识别
+async fn accept_loop(
+    shared: Arc<ServerShared>,
+    listener: TcpListener,
+    identify_timeout_ms: u64,
+    stop: CancellationToken,
+) {
+    loop {
+        let accepted = tokio::select! {
+            _ = stop.cancelled() => break,
+            accepted = listener.accept() => accepted,
+        };
+        match accepted {
+            Ok((stream, peer_addr)) => {
+                debug!("{}", tr!("network.log.accepted", peer = peer_addr));
+                let shared = shared.clone();
+                let stop = stop.clone();
+                tauri::async_runtime::spawn(async move {
+                    tokio::select! {
+                        _ = stop.cancelled() => {}
+                        _ = identify_connection(shared, stream, peer_addr, identify_timeout_ms) => {}
+                    }
+                });
+            }
+            Err(e) => {
+                // 文件描述符耗尽等临时错误，稍后继续接受连接
+                warn!("{}", tr!("network.log.acceptFailed", error = e));
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+    }
+    debug!("{}", tr!("network.log.listenerEnded"));
+}
+
+/// 读取设备连入后发送的第一个数据包并登记设备
+async fn identify_connection(
+    shared: Arc<ServerShared>,
+    mut stream: TcpStream,
+    peer_addr: SocketAddr,
+    identify_timeout_ms: u64,
+) {
+    let identify_start = Instant::now();
+    let deadline = tokio::time::Instant::from_std(identify_start + Duration::from_millis(identify_timeout_ms));
+    let mut packet = Vec::new();
+    let mut buffer = [0u8; MAX_REGISTRATION_LENGTH];
+    let mut closed = false;
+
+    // 第一段数据最长等待识别超时时间，之后的分段间隔超过 REGISTRATION_IDLE_MS 即视为注册包结束
+    loop {
+        let wait_until = if packet.is_empty() {
+            deadline
+        } else {
+            deadline.min(tokio::time::Instant::now() + Duration::from_millis(REGISTRATION_IDLE_MS))
+        };
+        match timeout_at(wait_until, stream.read(&mut buffer)).await {
+            Err(_) => break,
+            Ok(Ok(0)) => {
+                closed = true;
+                break;
+            }
+            Ok(Ok(bytes_read)) => {
+                packet.extend_from_slice(&buffer[..bytes_read]);
+                if packet.len() >= MAX_REGISTRATION_LENGTH {
+                    break;
+                }
+            }
+            Ok(Err(e)) => {
+                warn!("{}", tr!("network.log.registrationFailed", peer = peer_addr, error = e));
+                return;
+            }
+        }
+    }
+
+    if closed && packet.is_empty() {
+        debug!("{}", tr!("network.log.closedBeforeData", peer = peer_addr));
+        return;
+    }
+
+    let (device_id, identified_by, sub_device_address) = match identify_packet(&packet) {
+        Some(identity) => identity,
+        None => {
+            warn!(
+                "{}",
+                tr!("network.log.unidentified", peer = peer_addr, timeout = identify_timeout_ms)
+            );
+            (peer_addr.to_string(), IdentifiedBy::PeerAddress, None)
+        }
+    };
+    debug!(
+        "{}",
+        tr!(
+            "network.log.identified",
+            peer = peer_addr,
+            device = device_id,
+            packet = format_bytes_to_hex(&packet),
+            elapsed = identify_start.elapsed().as_millis()
+        )
+    );
+
+    shared.register(
+        InboundDeviceInfo {
+            device_id,
+            peer_addr: peer_addr.to_string(),
+            identified_by,
+            sub_device_address,
+            connected_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        },
+        stream,
+    );
+}
+
+/// 根据设备连入后的第一个数据包确定设备标识
+///
+/// AA B0 响应帧按分机地址识别；可打印的注册包（如DTU的设备编号、IMEI）按去除首尾空白后的文本识别，
+/// 其他注册包按16进制内容识别
+///
+/// # 返回值
+///
+/// 设备标识、识别方式和分机地址，数据包为空时返回None
+fn identify_packet(packet: &[u8]) -> Option<(String, IdentifiedBy, Option<u8>)> {
+    if starts_with_response_header(packet) && packet.len() > ADDRESS_OFFSET {
+        let sub_device_address = packet[ADDRESS_OFFSET];
+        return Some((
+            format!("sub-device-{}", sub_device_address),
+            IdentifiedBy::SubDeviceAddress,
+            Some(sub_device_address),
+        ));
+    }
+
+    let trimmed = packet
+        .iter()
+        .position(|b| !b.is_ascii_whitespace() && *b != 0)
+        .map(|start| {
+            let end = packet
+                .iter()
+                .rposition(|b| !b.is_ascii_whitespace() && *b != 0)
+                .unwrap_or(start);
+            &packet[start..=end]
+        })?;
+
+    let device_id = if trimmed.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+        String::from_utf8_lossy(trimmed).to_string()
+    } else {
+        format_bytes_to_hex(trimmed)
+    };
+    Some((device_id, IdentifiedBy::Registration, None))
+}
@@ -0,0 +1,438 @@
+// 设备长连接会话管理模块
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Local;
+
+use super::tcp::TcpTransport;
+use super::transport::Transport;
+use super::{NetworkError, RequestControl};
+use crate::i18n::tr;
+use crate::utils::hex_utils::{format_bytes_to_hex, hex_string_to_bytes};
+
+/// 单次发送时最多重连的次数
+const MAX_RECONNECT_ATTEMPTS: u32 = 3;
+/// 重连退避的初始等待时间（毫秒）
+const RECONNECT_BASE_DELAY_MS: u64 = 500;
+/// 重连退避的最长等待时间（毫秒）
+const RECONNECT_MAX_DELAY_MS: u64 = 8000;
+
+/// 会话连接状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionState {
+    /// 正在建立连接
+    Connecting,
+    /// 已连接，空闲
+    Idle,
+    /// 正在收发数据
+    Busy,
+    /// 连接已断开，下次发送时重连
+    Broken,
+}
+
+/// 会话信息，返回给前端并随状态变化事件推送
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionInfo {
+    /// 会话名称
+    pub name: String,
+    /// 设备IP地址或主机名
+    pub ip: String,
+    /// 设备端口号
+    pub port: u16,
+    /// 当前连接状态
+    pub state: SessionState,
+    /// 最近一次连续重连的次数
+    pub reconnect_attempts: u32,
+    /// 最近一次建立连接的时间
+    pub connected_at: Option<String>,
+    /// 最近一次实际连接的地址（主机名解析后的IP和端口）
+    pub peer_addr: Option<String>,
+    /// 最近一次错误信息
+    pub last_error: Option<String>,
+}
+
+/// 会话状态变化回调
+type StateListener = Box<dyn Fn(&SessionInfo) + Send + Sync>;
+
+/// 单个设备会话
+struct Session {
+    info: SessionInfo,
+    /// 会话信息快照，状态变化时更新，列出会话时无需等待进行中的请求
+    snapshot: Arc<Mutex<SessionInfo>>,
+    timeout_ms: u64,
+    transport: TcpTransport,
+    /// 会话已关闭或被同名会话替换，排队中的请求不再重连
+    closed: bool,
+}
+
+/// 会话表中的一项
+struct SessionEntry {
+    snapshot: Arc<Mutex<SessionInfo>>,
+    session: Arc<tokio::sync::Mutex<Session>>,
+}
+
+/// 设备长连接会话管理器，保存在Tauri托管状态中
+///
+/// 每个会话持有一个命名的TCP连接，同一会话上的请求串行执行
+pub struct SessionManager {
+    sessions: Mutex<HashMap<String, SessionEntry>>,
+    listener: Option<StateListener>,
+}
+
+impl Default for SessionManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionManager {
+    /// 创建不推送状态事件的会话管理器
+    pub fn new() -> Self {
+        SessionManager {
+            sessions: Mutex::new(HashMap::new()),
+            listener: None,
+        }
+    }
+
+    /// 创建会话管理器，会话状态变化时调用 `listener`
+    pub fn with_listener<F>(listener: F) -> Self
+    where
+        F: Fn(&SessionInfo) + Send + Sync + 'static,
+    {
+        SessionManager {
+            sessions: Mutex::new(HashMap::new()),
+            listener: Some(Box::new(listener)),
+        }
+    }
+
+    /// 打开命名会话并立即建立连接，同名会话已存在时先关闭旧连接（旧会话正在收发时等待当前请求结束）
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 会话名称
+    /// * `ip` - 设备IP地址或主机名，IPv6地址可带方括号
+    /// * `port` - 设备端口号
+    /// * `timeout_ms` - 连接和收发超时时间（毫秒）
+    ///
+    /// # 返回值
+    ///
+    /// 成功时返回会话信息，首次连接失败时返回错误（会话保留为断开状态）
+    pub async fn open(
+        &self,
+        name: &str,
+        ip: &str,
+        port: u16,
+        timeout_ms: Option<u64>,
+    ) -> Result<SessionInfo, NetworkError> {
+        info!("{}", tr!("session.opening", name = name, ip = ip, port = port));
+
+        let info = SessionInfo {
+            name: name.to_string(),
+            ip: ip.to_string(),
+            port,
+            state: SessionState::Connecting,
+            reconnect_attempts: 0,
+            connected_at: None,
+            peer_addr: None,
+            last_error: None,
+        };
+        let snapshot = Arc::new(Mutex::new(info.clone()));
+        let session = Arc::new(tokio::sync::Mutex::new(Session {
+            info,
+            snapshot: snapshot.clone(),
+            timeout_ms: timeout_ms.unwrap_or(5000),
+            transport: TcpTransport::new(ip, port),
+            closed: false,
+        }));
+
+        // 先锁住新会话再放入会话表，连接完成前到达的请求排队等待
+        let mut session_guard = session.lock().await;
+        let previous = self.lock_sessions().insert(
+            name.to_string(),
+            SessionEntry {
+                snapshot,
+                session: session.clone(),
+            },
+        );
+        if let Some(previous) = previous {
+            warn!("{}", tr!("session.replaced", name = name));
+            let mut previous = previous.session.lock().await;
+            previous.closed = true;
+            previous.transport.close().await;
+        }
+
+        self.connect(&mut session_guard).await?;
+        Ok(session_guard.info.clone())
+    }
+
+    /// 在命名会话上发送16进制数据并读取响应
+    ///
+    /// 连接已断开时按指数退避自动重连；空闲连接在发送时发现已被对端关闭的，重连后重发一次。
+    /// 请求被取消时丢弃当前连接，下次发送时重连
+    ///
+    /// # 返回值
+    ///
+    /// 成功时返回响应的16进制字符串
+    pub async fn send(
+        &self,
+        name: &str,
+        data: &str,
+        control: &RequestControl,
+    ) -> Result<String, NetworkError> {
+        let session = self.get(name)?;
+        let mut session = session.lock().await;
+        if session.closed {
+            // 等待期间会话已被关闭或替换
+            return Err(NetworkError::SessionNotFound {
+                name: name.to_string(),
+            });
+        }
+
+        let request = hex_string_to_bytes(data).map_err(|e| {
+            error!("{}", tr!("session.hexInvalid", error = e));
+            NetworkError::BadHex { message: e }
+        })?;
+
+        let mut resent = false;
+        loop {
+            if !session.transport.is_connected() || session.info.state == SessionState::Broken {
+                let reconnected = control.run(self.reconnect_with_backoff(&mut session, control)).await;
+                if let Err(NetworkError::Cancelled) = reconnected {
+                    self.set_state(&mut session, SessionState::Broken);
+                }
+                reconnected?;
+            }
+
+            self.set_state(&mut session, SessionState::Busy);
+            match control.run(self.exchange(&mut session, &request, control)).await {
+                Ok(response) => {
+                    self.set_state(&mut session, SessionState::Idle);
+                    return Ok(format_bytes_to_hex(&response));
+                }
+                Err(NetworkError::Cancelled) => {
+                    // 取消时响应可能只收到一半，连接不能再复用
+                    session.transport.close().await;
+                    self.set_state(&mut session, SessionState::Broken);
+                    return Err(NetworkError::Cancelled);
+                }
+                Err(e) => {
+                    session.info.last_error = Some(e.to_string());
+                    if !e.is_connection_lost() {
+                        self.set_state(&mut session, SessionState::Idle);
+                        return Err(e);
+                    }
+
+                    session.transport.close().await;
+                    self.set_state(&mut session, SessionState::Broken);
+                    if resent {
+                        return Err(e);
+                    }
+                    warn!("{}", tr!("session.resending", name = name, error = e));
+                    resent = true;
+                }
+            }
+        }
+    }
+
+    /// 关闭命名会话
+    pub async fn close(&self, name: &str) -> Result<(), NetworkError> {
+        let entry = self
+            .lock_sessions()
+            .remove(name)
+            .ok_or_else(|| NetworkError::SessionNotFound {
+                name: name.to_string(),
+            })?;
+
+        let mut session = entry.session.lock().await;
+        session.closed = true;
+        session.transport.close().await;
+        self.set_state(&mut session, SessionState::Broken);
+        info!("{}", tr!("session.closed", name = name));
+        Ok(())
+    }
+
+    /// 列出全部会话，正在连接或收发的会话返回最近一次状态变化时的信息
+    pub fn list(&self) -> Vec<SessionInfo> {
+        self.lock_sessions()
+            .values()
+            .map(|entry| {
+                entry
+                    .snapshot
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .clone()
+            })
+            .collect()
+    }
+
+    fn lock_sessions(&self) -> std::sync::MutexGuard<'_, HashMap<String, SessionEntry>> {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn get(&self, name: &str) -> Result<Arc<tokio::sync::Mutex<Session>>, NetworkError> {
+        self.lock_sessions()
+            .get(name)
+            .map(|entry| entry.session.clone())
+            .ok_or_else(|| NetworkError::SessionNotFound {
+                name: name.to_string(),
+            })
+    }
+
+    /// 建立连接并更新会话状态
+    async fn connect(&self, session: &mut Session) -> Result<(), NetworkError> {
+        self.connect_with(session, &RequestControl::default()).await
+    }
+
+    /// 建立连接并更新会话状态，连接进度通过 `control` 上报
+    async fn connect_with(
+        &self,
+        session: &mut Session,
+        control: &RequestControl,
+    ) -> Result<(), NetworkError> {
+        self.set_state(session, SessionState::Connecting);
+        session.transport.close().await;
+        match session.transport.connect(session.timeout_ms, control).await {
+            Ok(()) => {
+                session.info.peer_addr = session.transport.peer_addr();
+                session.info.reconnect_attempts = 0;
+                session.info.connected_at = Some(Local::now().format("%Y-%m-%d %H:%M:%S").to_string());
+                session.info.last_error = None;
+                self.set_state(session, SessionState::Idle);
+                Ok(())
+            }
+            Err(e) => {
+                session.info.last_error = Some(e.to_string());
+                self.set_state(session, SessionState::Broken);
+                Err(e)
+            }
+        }
+    }
+
+    /// 按指数退避重连，超过最大次数后返回最后一次的错误
+    async fn reconnect_with_backoff(
+        &self,
+        session: &mut Session,
+        control: &RequestControl,
+    ) -> Result<(), NetworkError> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            session.info.reconnect_attempts = attempt;
+            debug!("{}", tr!("session.reconnecting", name = session.info.name, attempt = attempt));
+
+            match self.connect_with(session, control).await {
+                Ok(()) => {
+                    info!(
+                        "{}",
+                        tr!("session.reconnected", name = session.info.name, attempt = attempt)
+                    );
+                    return Ok(());
+                }
+                Err(e) if attempt >= MAX_RECONNECT_ATTEMPTS => {
+                    error!(
+                        "{}",
+                        tr!(
+                            "session.reconnectFailed",
+                            name = session.info.name,
+                            attempt = attempt,
+                            error = e
+                        )
+                    );
+                    return Err(e);
+                }
+                Err(e) => {
+                    let delay = (RECONNECT_BASE_DELAY_MS << (attempt - 1)).min(RECONNECT_MAX_DELAY_MS);
+                    warn!(
+                        "{}",
+                        tr!(
+                            "session.reconnectRetry",
+                            name = session.info.name,
+                            delay = delay,
+                            error = e
+                        )
+                    );
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                }
+            }
+        }
+    }
+
+    /// 在当前连接上完成一次请求-响应
+    async fn exchange(
+        &self,
+        session: &mut Session,
+        request: &[u8],
+        control: &RequestControl,
+    ) -> Result<Vec<u8>, NetworkError> {
+        let timeout_ms = session.timeout_ms;
+        let transport = &mut session.transport;
+        transport.discard_stale_data()?;
+        transport.send(request, timeout_ms, control).await?;
+        transport.receive_frame(timeout_ms, 1, control).await
+    }
+
+    /// 更新会话状态和信息快照，状态变化时通知监听者
+    fn set_state(&self, session: &mut Session, state: SessionState) {
+        let changed = session.info.state != state;
+        if changed {
+            debug!(
+                "{}",
+                tr!(
+                    "session.stateChanged",
+                    name = session.info.name,
+                    from = format!("{:?}", session.info.state),
+                    to = format!("{:?}", state)
+                )
+            );
+            session.info.state = state;
+        }
+        *session
+            .snapshot
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = session.info.clone();
+        if changed {
+            if let Some(listener) = &self.listener {
+                listener(&session.info);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn lists_busy_session() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        // 设备接受连接但不响应，使请求一直处于收发状态
+        let _device = tokio::spawn(async move {
+            let (_stream, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+
+        let manager = Arc::new(SessionManager::new());
+        manager.open("dtu", "127.0.0.1", port, Some(1000)).await.unwrap();
+
+        let sender = manager.clone();
+        let request = tokio::spawn(async move {
+            sender.send("dtu", "AA A0", &RequestControl::default()).await
+        });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let sessions = manager.list();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].state, SessionState::Busy);
+
+        request.abort();
+    }
+}
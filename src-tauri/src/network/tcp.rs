@@ -0,0 +1,256 @@
+// TCP通信链路，支持本机主动连接设备和设备主动连入两种方式
+use async_trait::async_trait;
+use log::{debug, error, info, warn};
+use std::io::{self, ErrorKind};
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+use super::address::{format_host_port, resolve_device_address};
+use super::transport::Transport;
+use super::{get_local_network_info, ErrorContext, NetworkError, NetworkPhase, NetworkProgress, RequestControl};
+use crate::i18n::tr;
+
+/// TCP通信链路
+pub struct TcpTransport {
+    host: String,
+    port: u16,
+    address: String,
+    local_addr_info: String,
+    stream: Option<TcpStream>,
+    /// 设备主动连入的连接，断开后只能等待设备重新连入
+    inbound: bool,
+}
+
+impl TcpTransport {
+    /// 创建尚未连接的TCP链路
+    ///
+    /// # 参数
+    ///
+    /// * `host` - 设备IP地址或主机名，IPv6地址可带方括号
+    /// * `port` - 设备端口号
+    pub fn new(host: &str, port: u16) -> Self {
+        TcpTransport {
+            host: host.to_string(),
+            port,
+            address: format_host_port(host, port),
+            local_addr_info: String::new(),
+            stream: None,
+            inbound: false,
+        }
+    }
+
+    /// 包装设备主动连入的连接
+    ///
+    /// # 参数
+    ///
+    /// * `stream` - 接入服务接受的连接
+    /// * `label` - 链路描述，用于日志和错误上下文
+    pub fn accepted(stream: TcpStream, label: &str) -> Self {
+        let (host, port) = match stream.peer_addr() {
+            Ok(addr) => (addr.ip().to_string(), addr.port()),
+            Err(_) => (String::new(), 0),
+        };
+        TcpTransport {
+            host,
+            port,
+            address: label.to_string(),
+            local_addr_info: get_local_network_info(),
+            stream: Some(stream),
+            inbound: true,
+        }
+    }
+
+    /// 实际连接的地址（主机名解析后的IP和端口），未连接时返回None
+    pub fn peer_addr(&self) -> Option<String> {
+        self.stream
+            .as_ref()
+            .and_then(|stream| stream.peer_addr().ok())
+            .map(|addr| addr.to_string())
+    }
+}
+
+#[async_trait]
+impl Transport for TcpTransport {
+    fn describe(&self) -> String {
+        self.address.clone()
+    }
+
+    fn local_info(&self) -> &str {
+        &self.local_addr_info
+    }
+
+    fn is_connected(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    async fn connect(&mut self, timeout_ms: u64, control: &RequestControl) -> Result<(), NetworkError> {
+        if self.stream.is_some() {
+            return Ok(());
+        }
+        if self.inbound {
+            warn!("{}", tr!("network.log.inboundLost", address = self.address));
+            let context = ErrorContext::new(&self.address, NetworkPhase::Connect, Instant::now(), &self.local_addr_info);
+            return Err(NetworkError::Closed(context));
+        }
+
+        // 获取本地网络信息
+        self.local_addr_info = get_local_network_info();
+        debug!("{}", tr!("network.log.localInfo", info = self.local_addr_info));
+
+        let stream = connect_device(&self.host, self.port, timeout_ms, &self.local_addr_info, control).await?;
+        self.stream = Some(stream);
+        Ok(())
+    }
+
+    async fn close(&mut self) {
+        if let Some(mut stream) = self.stream.take() {
+            if let Err(e) = stream.shutdown().await {
+                debug!("{}", tr!("network.log.closeFailed", address = self.address, error = e));
+            }
+        }
+    }
+
+    async fn write_raw(&mut self, data: &[u8], wait: Duration) -> io::Result<()> {
+        let stream = self.stream.as_mut().ok_or(ErrorKind::NotConnected)?;
+        timeout(wait, stream.write_all(data))
+            .await
+            .unwrap_or_else(|_| Err(io::Error::new(ErrorKind::TimedOut, "write timed out")))
+    }
+
+    async fn read_raw(&mut self, buffer: &mut [u8], wait: Duration) -> io::Result<usize> {
+        let stream = self.stream.as_mut().ok_or(ErrorKind::NotConnected)?;
+        timeout(wait, stream.read(buffer))
+            .await
+            .unwrap_or_else(|_| Err(io::Error::new(ErrorKind::TimedOut, "read timed out")))
+    }
+
+    fn discard_stale_data(&mut self) -> Result<(), NetworkError> {
+        let Some(stream) = self.stream.as_mut() else {
+            return Ok(());
+        };
+        let started = Instant::now();
+        let context = || ErrorContext::new(&self.address, NetworkPhase::Write, started, &self.local_addr_info);
+        let mut buffer = [0u8; 1024];
+        loop {
+            match stream.try_read(&mut buffer) {
+                Ok(0) => break Err(NetworkError::Closed(context())),
+                Ok(bytes_read) => {
+                    warn!(
+                        "{}",
+                        tr!(
+                            "network.log.staleDiscarded",
+                            address = self.address,
+                            length = bytes_read
+                        )
+                    );
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break Ok(()),
+                Err(e) => break Err(NetworkError::from_io(&e, context())),
+            }
+        }
+    }
+}
+
+/// 连接到设备
+///
+/// # 参数
+///
+/// * `ip` - 目标IP地址或主机名，IPv6地址可带方括号
+/// * `port` - 目标端口号
+/// * `actual_timeout` - 每个候选地址的连接超时时间（毫秒）
+/// * `local_addr_info` - 本地网络信息，用于错误诊断
+/// * `control` - 进度回调
+///
+/// # 返回值
+///
+/// 成功时返回连接（实际连接的地址见 `peer_addr`），失败时返回附带排查建议的错误
+pub async fn connect_device(
+    ip: &str,
+    port: u16,
+    actual_timeout: u64,
+    local_addr_info: &str,
+    control: &RequestControl,
+) -> Result<TcpStream, NetworkError> {
+    // 连接到指定的IP和端口
+    let address = format_host_port(ip, port);
+    info!("{}", tr!("network.log.connecting", address = address));
+    let connect_start = Instant::now();
+
+    // 主机名解析出多个地址时按顺序逐个尝试，全部失败时按最后一个错误给出建议
+    let socket_addrs = resolve_device_address(ip, port, local_addr_info).await?;
+    control.report(NetworkProgress::Resolved {
+        addresses: socket_addrs.iter().map(SocketAddr::to_string).collect(),
+    });
+
+    let mut connect_result = Err(std::io::Error::new(
+        ErrorKind::AddrNotAvailable,
+        "no address resolved",
+    ));
+    for socket_addr in &socket_addrs {
+        debug!("{}", tr!("network.log.tryingAddress", address = socket_addr));
+        connect_result = timeout(
+            Duration::from_millis(actual_timeout),
+            TcpStream::connect(socket_addr),
+        )
+        .await
+        .unwrap_or_else(|_| Err(std::io::Error::new(ErrorKind::TimedOut, "connection timed out")));
+
+        match &connect_result {
+            Ok(_) => break,
+            Err(e) => warn!(
+                "{}",
+                tr!("network.log.addressFailed", address = socket_addr, error = e)
+            ),
+        }
+    }
+
+    let stream = match connect_result {
+        Ok(stream) => {
+            let elapsed = connect_start.elapsed();
+            let local_addr = match stream.local_addr() {
+                Ok(addr) => addr.to_string(),
+                Err(_) => tr!("network.log.unknownAddress"),
+            };
+            let peer_addr = match stream.peer_addr() {
+                Ok(addr) => addr.to_string(),
+                Err(_) => tr!("network.log.unknownAddress"),
+            };
+            info!(
+                "{}",
+                tr!(
+                    "network.log.connected",
+                    address = address,
+                    local = local_addr,
+                    peer = peer_addr,
+                    elapsed = elapsed.as_millis()
+                )
+            );
+            control.report(NetworkProgress::Connected {
+                local_addr,
+                peer_addr,
+            });
+            stream
+        }
+        Err(e) => {
+            let context = ErrorContext::new(&address, NetworkPhase::Connect, connect_start, local_addr_info);
+            let error = NetworkError::from_io(&e, context);
+            error!(
+                "{}",
+                tr!(
+                    "network.log.ioFailed",
+                    error = error,
+                    local = local_addr_info,
+                    elapsed = connect_start.elapsed().as_millis(),
+                    detail = e,
+                    kind = format!("{:?}", e.kind())
+                )
+            );
+            return Err(error);
+        }
+    };
+
+    Ok(stream)
+}
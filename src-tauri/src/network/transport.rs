@@ -0,0 +1,706 @@
+// 通信链路抽象，TCP、串口等链路共用帧识别、补发、日志和耗时统计
+use async_trait::async_trait;
+use log::{debug, error, info, trace, warn};
+use std::io::{self, ErrorKind};
+use std::time::{Duration, Instant};
+
+use super::{ErrorContext, NetworkError, NetworkPhase, NetworkProgress, RequestControl};
+use crate::i18n::tr;
+use crate::protocol::framing::{
+    discard_unframed, frame_status, split_frames, starts_with_response_header, FrameStatus,
+};
+use crate::protocol::{self, packet_count_for, PacketAssembler, RequestFrame, ResponseFrame};
+
+/// 与设备之间的通信链路
+///
+/// 实现方只需提供连接、原始读写和关闭，请求发送与响应帧接收由默认方法统一完成
+#[async_trait]
+pub trait Transport: Send {
+    /// 链路描述（"主机:端口" 或串口名称），用于日志和错误上下文
+    fn describe(&self) -> String;
+
+    /// 本地诊断信息，附加到错误上下文中
+    fn local_info(&self) -> &str {
+        ""
+    }
+
+    /// 是否已建立连接
+    fn is_connected(&self) -> bool;
+
+    /// 建立连接，已连接时直接返回
+    ///
+    /// # 参数
+    ///
+    /// * `timeout_ms` - 连接超时时间（毫秒）
+    /// * `control` - 进度回调
+    async fn connect(&mut self, timeout_ms: u64, control: &RequestControl) -> Result<(), NetworkError>;
+
+    /// 关闭连接，之后可重新连接
+    async fn close(&mut self);
+
+    /// 写入全部数据，超时返回 `TimedOut` 错误
+    async fn write_raw(&mut self, data: &[u8], wait: Duration) -> io::Result<()>;
+
+    /// 读取一段数据，最多等待 `wait`；返回0表示对端关闭连接，超时返回 `TimedOut` 错误
+    async fn read_raw(&mut self, buffer: &mut [u8], wait: Duration) -> io::Result<usize>;
+
+    /// 收到数据后等待后续数据的最长时间，超过即视为响应结束；None表示一直等到接收超时
+    fn idle_timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// 发送请求前丢弃上次交互遗留的数据，同时检测连接是否仍然可用
+    fn discard_stale_data(&mut self) -> Result<(), NetworkError> {
+        Ok(())
+    }
+
+    /// 发送请求数据
+    ///
+    /// # 参数
+    ///
+    /// * `data` - 请求字节
+    /// * `timeout_ms` - 发送超时时间（毫秒）
+    /// * `control` - 进度回调
+    async fn send(
+        &mut self,
+        data: &[u8],
+        timeout_ms: u64,
+        control: &RequestControl,
+    ) -> Result<(), NetworkError> {
+        let address = self.describe();
+        debug!("{}", tr!("network.log.sending", address = address, length = data.len()));
+        let send_start = Instant::now();
+
+        if !self.is_connected() {
+            let context = ErrorContext::new(&address, NetworkPhase::Write, send_start, self.local_info());
+            return Err(NetworkError::Closed(context));
+        }
+
+        if let Err(e) = self.write_raw(data, Duration::from_millis(timeout_ms)).await {
+            let context = ErrorContext::new(&address, NetworkPhase::Write, send_start, self.local_info());
+            let error = NetworkError::from_io(&e, context);
+            error!(
+                "{}",
+                tr!(
+                    "network.log.ioFailed",
+                    error = error,
+                    local = self.local_info(),
+                    elapsed = send_start.elapsed().as_millis(),
+                    detail = e,
+                    kind = format!("{:?}", e.kind())
+                )
+            );
+            return Err(error);
+        }
+
+        info!(
+            "{}",
+            tr!("network.log.sent", length = data.len(), elapsed = send_start.elapsed().as_millis())
+        );
+        control.report(NetworkProgress::Sent { bytes: data.len() });
+        Ok(())
+    }
+
+    /// 接收响应帧
+    ///
+    /// 与 `receive_packets` 相同，粮情响应帧需全部通过校验
+    ///
+    /// # 参数
+    ///
+    /// * `timeout_ms` - 接收超时时间（毫秒）
+    /// * `expected_frames` - 预期的响应帧数量
+    /// * `control` - 进度回调
+    ///
+    /// # 返回值
+    ///
+    /// 成功时返回原始响应字节，所有响应帧均已通过校验
+    async fn receive_frame(
+        &mut self,
+        timeout_ms: u64,
+        expected_frames: usize,
+        control: &RequestControl,
+    ) -> Result<Vec<u8>, NetworkError> {
+        let response_data = self.receive_packets(timeout_ms, expected_frames, control).await?;
+        verify_frames(&response_data, &self.describe(), self.local_info())?;
+        Ok(response_data)
+    }
+
+    /// 接收响应帧，不校验帧内容，由调用方逐帧校验
+    ///
+    /// 收到 `expected_frames` 个完整的响应帧后立即返回；非协议数据以 EF EF 结尾、
+    /// 对端关闭连接、超过空闲时间没有新数据或超时时返回已收到的数据
+    ///
+    /// # 参数
+    ///
+    /// * `timeout_ms` - 接收超时时间（毫秒）
+    /// * `expected_frames` - 预期的响应帧数量
+    /// * `control` - 进度回调
+    ///
+    /// # 返回值
+    ///
+    /// 成功时返回原始响应字节，末尾不完整的帧和最后一帧之后的非协议数据已去除
+    async fn receive_packets(
+        &mut self,
+        timeout_ms: u64,
+        expected_frames: usize,
+        control: &RequestControl,
+    ) -> Result<Vec<u8>, NetworkError> {
+        let address = self.describe();
+        debug!("{}", tr!("network.log.waiting", address = address));
+        let recv_start = Instant::now();
+        let deadline = recv_start + Duration::from_millis(timeout_ms);
+        let idle_timeout = self.idle_timeout();
+        let mut buffer = vec![0u8; 4096]; // 增大缓冲区以处理大型响应
+        let mut response_data = Vec::new();
+        let mut timed_out = false;
+
+        loop {
+            // 收到数据前按接收超时等待，之后按空闲时间判断响应结束
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let wait = match idle_timeout {
+                Some(idle) if !response_data.is_empty() => idle.min(remaining),
+                _ => remaining,
+            };
+            if wait.is_zero() {
+                timed_out = true;
+                break;
+            }
+
+            match self.read_raw(&mut buffer, wait).await {
+                Ok(0) => {
+                    debug!("{}", tr!("network.log.peerClosed", length = response_data.len()));
+                    break;
+                }
+                Ok(bytes_read) => {
+                    response_data.extend_from_slice(&buffer[0..bytes_read]);
+                    control.report(NetworkProgress::Received {
+                        bytes: bytes_read,
+                        total: response_data.len(),
+                    });
+                    // 接入设备可能在轮询过程中发送心跳包或注册包，丢弃后从下一个帧头继续识别
+                    let discarded = discard_unframed(&mut response_data);
+                    if discarded > 0 {
+                        debug!("{}", tr!("network.log.unframedDiscarded", length = discarded));
+                    }
+                    debug!(
+                        "{}",
+                        tr!(
+                            "network.log.received",
+                            length = bytes_read,
+                            total = response_data.len(),
+                            elapsed = recv_start.elapsed().as_millis()
+                        )
+                    );
+
+                    match frame_status(&response_data) {
+                        FrameStatus::Complete { length, frames } if frames >= expected_frames => {
+                            if length < response_data.len() {
+                                warn!(
+                                    "{}",
+                                    tr!(
+                                        "network.log.extraDiscarded",
+                                        length = response_data.len() - length
+                                    )
+                                );
+                                response_data.truncate(length);
+                            }
+                            break;
+                        }
+                        FrameStatus::Unframed { trailer_seen: true } => break,
+                        FrameStatus::Complete { frames, .. } => {
+                            trace!(
+                                "{}",
+                                tr!(
+                                    "network.log.framesReceived",
+                                    frames = frames,
+                                    expected = expected_frames
+                                )
+                            );
+                        }
+                        FrameStatus::Incomplete { expected, .. } => {
+                            trace!(
+                                "{}",
+                                tr!(
+                                    "network.log.framePending",
+                                    received = response_data.len(),
+                                    expected = expected
+                                )
+                            );
+                        }
+                        FrameStatus::Unframed { trailer_seen: false } => {}
+                    }
+                }
+                Err(e) if matches!(e.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock) => {
+                    if response_data.is_empty() || idle_timeout.is_none() {
+                        timed_out = true;
+                    } else {
+                        debug!(
+                            "{}",
+                            tr!("network.log.idle", address = address, idle = wait.as_millis())
+                        );
+                    }
+                    break;
+                }
+                Err(e) => {
+                    let context = ErrorContext::new(&address, NetworkPhase::Read, recv_start, self.local_info());
+                    let error = NetworkError::from_io(&e, context);
+                    error!(
+                        "{}",
+                        tr!(
+                            "network.log.readFailed",
+                            error = error,
+                            local = self.local_info(),
+                            received = response_data.len(),
+                            elapsed = recv_start.elapsed().as_millis(),
+                            detail = e,
+                            kind = format!("{:?}", e.kind())
+                        )
+                    );
+                    return Err(error);
+                }
+            }
+        }
+
+        finish_response(
+            response_data,
+            expected_frames,
+            timed_out,
+            recv_start,
+            &address,
+            self.local_info(),
+        )
+    }
+}
+
+/// 建立连接、发送一次请求并接收一个响应帧，完成后关闭连接
+///
+/// # 参数
+///
+/// * `transport` - 通信链路
+/// * `request` - 请求字节
+/// * `timeout_ms` - 连接、发送和接收各自的超时时间（毫秒）
+/// * `control` - 进度回调
+///
+/// # 返回值
+///
+/// 成功时返回原始响应字节
+pub async fn request_once(
+    transport: &mut dyn Transport,
+    request: &[u8],
+    timeout_ms: u64,
+    control: &RequestControl,
+) -> Result<Vec<u8>, NetworkError> {
+    let result = exchange(transport, request, timeout_ms, control).await;
+    transport.close().await;
+    result
+}
+
+/// 在链路上完成一次请求-响应，未连接时先建立连接，完成后保持连接
+///
+/// # 参数
+///
+/// * `transport` - 通信链路
+/// * `request` - 请求字节
+/// * `timeout_ms` - 连接、发送和接收各自的超时时间（毫秒）
+/// * `control` - 进度回调
+///
+/// # 返回值
+///
+/// 成功时返回原始响应字节
+pub async fn exchange(
+    transport: &mut dyn Transport,
+    request: &[u8],
+    timeout_ms: u64,
+    control: &RequestControl,
+) -> Result<Vec<u8>, NetworkError> {
+    let exchange_start = Instant::now();
+    transport.connect(timeout_ms, control).await?;
+    transport.discard_stale_data()?;
+    transport.send(request, timeout_ms, control).await?;
+    let response_data = transport.receive_frame(timeout_ms, 1, control).await?;
+
+    info!(
+        "{}",
+        tr!(
+            "network.log.exchangeFinished",
+            address = transport.describe(),
+            sent = request.len(),
+            received = response_data.len(),
+            elapsed = exchange_start.elapsed().as_millis()
+        )
+    );
+    Ok(response_data)
+}
+
+/// 通过指定链路读取粮情数据，支持超过512个测温点的多包响应
+///
+/// 在同一连接上发送读取请求并按包序号重组响应，缺失的包会单独补发请求，完成后保持连接
+///
+/// # 参数
+///
+/// * `transport` - 通信链路
+/// * `sub_device_address` - 分机地址（1-99）
+/// * `total_points` - 测温点总数，用于计算响应包数量
+/// * `timeout_ms` - 超时时间（毫秒）
+/// * `max_retries` - 补发缺失包的最大轮数
+/// * `control` - 进度回调
+///
+/// # 返回值
+///
+/// 成功时返回合并后的响应帧，仍有缺失的包时返回缺失的包序号
+pub async fn poll_grain(
+    transport: &mut dyn Transport,
+    sub_device_address: u8,
+    total_points: usize,
+    timeout_ms: u64,
+    max_retries: u32,
+    control: &RequestControl,
+) -> Result<ResponseFrame, NetworkError> {
+    let address = transport.describe();
+    let expected_packets = packet_count_for(total_points);
+    debug!(
+        "{}",
+        tr!(
+            "network.log.pollStarted",
+            address = address,
+            subDevice = sub_device_address,
+            points = total_points,
+            packets = expected_packets
+        )
+    );
+
+    let poll_start = Instant::now();
+    let mut assembler = PacketAssembler::new(sub_device_address, expected_packets).map_err(|e| {
+        NetworkError::InvalidRequest {
+            message: e.to_string(),
+        }
+    })?;
+    let attempt = poll_packets(
+        transport,
+        sub_device_address,
+        timeout_ms,
+        max_retries,
+        &mut assembler,
+        control,
+    )
+    .await?;
+
+    let missing = assembler.missing();
+    let expected = assembler.expected_packets();
+    match assembler.assemble() {
+        Some(frame) => {
+            info!(
+                "{}",
+                tr!(
+                    "network.log.pollFinished",
+                    address = address,
+                    packets = frame.packet_count,
+                    sensors = frame.sensors.len(),
+                    rounds = attempt,
+                    elapsed = poll_start.elapsed().as_millis()
+                )
+            );
+            Ok(frame)
+        }
+        None => {
+            error!(
+                "{}",
+                tr!(
+                    "network.log.pollFailed",
+                    address = address,
+                    missing = format!("{:?}", missing),
+                    rounds = attempt,
+                    elapsed = poll_start.elapsed().as_millis()
+                )
+            );
+            Err(NetworkError::MissingPackets { missing, expected })
+        }
+    }
+}
+
+/// 在同一连接上读取全部响应包，返回补发的轮数
+async fn poll_packets(
+    transport: &mut dyn Transport,
+    sub_device_address: u8,
+    timeout_ms: u64,
+    max_retries: u32,
+    assembler: &mut PacketAssembler,
+    control: &RequestControl,
+) -> Result<u32, NetworkError> {
+    let request = RequestFrame::new(sub_device_address)
+        .build()
+        .map_err(|message| NetworkError::InvalidRequest { message })?;
+
+    transport.connect(timeout_ms, control).await?;
+    transport.discard_stale_data()?;
+    transport.send(&request, timeout_ms, control).await?;
+    // 损坏的包与没有收到的包一样由下面的补发重新读取
+    let response_data = transport
+        .receive_packets(timeout_ms, assembler.expected_packets(), control)
+        .await?;
+    push_packets(assembler, &response_data);
+
+    let mut attempt = 0;
+    while !assembler.is_complete() && attempt < max_retries {
+        attempt += 1;
+        for packet_index in assembler.missing() {
+            warn!(
+                "{}",
+                tr!(
+                    "network.log.resending",
+                    round = attempt,
+                    packet = packet_index,
+                    address = transport.describe()
+                )
+            );
+            let request = RequestFrame::new(sub_device_address)
+                .packet_index(packet_index)
+                .build()
+                .map_err(|message| NetworkError::InvalidRequest { message })?;
+            transport.send(&request, timeout_ms, control).await?;
+
+            match transport.receive_packets(timeout_ms, 1, control).await {
+                Ok(response_data) => push_packets(assembler, &response_data),
+                Err(e) => {
+                    warn!("{}", tr!("network.log.resendFailed", packet = packet_index, error = e));
+                }
+            }
+        }
+    }
+
+    Ok(attempt)
+}
+
+/// 检查接收结束时的响应数据
+///
+/// 没有数据时按是否超时返回错误；末尾不完整的帧在已有完整帧时丢弃，否则返回帧不完整错误
+///
+/// # 参数
+///
+/// * `response_data` - 接收到的全部字节
+/// * `expected_frames` - 预期的响应帧数量
+/// * `timed_out` - 接收是否因超时结束
+/// * `recv_start` - 开始接收的时间
+/// * `address` - 设备地址，用于日志记录
+/// * `local_addr_info` - 本地网络信息，用于错误诊断
+fn finish_response(
+    mut response_data: Vec<u8>,
+    expected_frames: usize,
+    timed_out: bool,
+    recv_start: Instant,
+    address: &str,
+    local_addr_info: &str,
+) -> Result<Vec<u8>, NetworkError> {
+    let elapsed = recv_start.elapsed();
+    if response_data.is_empty() {
+        let context = ErrorContext::new(address, NetworkPhase::Read, recv_start, local_addr_info);
+        if timed_out {
+            error!(
+                "{}",
+                tr!(
+                    "network.log.readTimeout",
+                    address = address,
+                    local = local_addr_info,
+                    elapsed = elapsed.as_millis()
+                )
+            );
+            return Err(NetworkError::ReadTimeout(context));
+        }
+        warn!(
+            "{}",
+            tr!(
+                "network.log.emptyResponse",
+                local = local_addr_info,
+                address = address,
+                elapsed = elapsed.as_millis()
+            )
+        );
+        return Err(NetworkError::EmptyResponse(context));
+    }
+
+    match frame_status(&response_data) {
+        FrameStatus::Incomplete {
+            complete_length: 0,
+            expected,
+        } => {
+            error!(
+                "{}",
+                tr!(
+                    "network.log.frameIncomplete",
+                    address = address,
+                    local = local_addr_info,
+                    received = response_data.len(),
+                    expected = expected,
+                    elapsed = elapsed.as_millis(),
+                    timedOut = timed_out
+                )
+            );
+            return Err(NetworkError::IncompleteFrame {
+                received: response_data.len(),
+                expected,
+            });
+        }
+        FrameStatus::Incomplete {
+            complete_length, ..
+        } => {
+            // 多包响应中已收到的完整帧仍然有效，缺失的包由调用方补发
+            warn!(
+                "{}",
+                tr!("network.log.tailDiscarded", length = response_data.len() - complete_length)
+            );
+            response_data.truncate(complete_length);
+        }
+        FrameStatus::Complete { length, frames } => {
+            if frames < expected_frames {
+                warn!(
+                    "{}",
+                    tr!(
+                        "network.log.framesMissing",
+                        frames = frames,
+                        expected = expected_frames,
+                        elapsed = elapsed.as_millis()
+                    )
+                );
+            }
+            // 最后一帧之后的心跳包等非协议数据不属于响应
+            if length > 0 && length < response_data.len() {
+                warn!(
+                    "{}",
+                    tr!("network.log.extraDiscarded", length = response_data.len() - length)
+                );
+                response_data.truncate(length);
+            }
+        }
+        FrameStatus::Unframed { .. } => {}
+    }
+
+    info!(
+        "{}",
+        tr!(
+            "network.log.receiveFinished",
+            length = response_data.len(),
+            elapsed = elapsed.as_millis()
+        )
+    );
+
+    Ok(response_data)
+}
+
+/// 校验响应中的每个粮情响应帧，避免将损坏的数据当作温度显示
+///
+/// # 参数
+///
+/// * `response_data` - 接收到的响应字节
+/// * `address` - 设备地址，用于日志记录
+/// * `local_addr_info` - 本地网络信息，用于日志记录
+fn verify_frames(response_data: &[u8], address: &str, local_addr_info: &str) -> Result<(), NetworkError> {
+    if !starts_with_response_header(response_data) {
+        return Ok(());
+    }
+    for frame in split_frames(response_data) {
+        if let Err(e) = protocol::verify_response_frame(frame) {
+            error!(
+                "{}",
+                tr!(
+                    "network.log.verifyFailed",
+                    error = e,
+                    local = local_addr_info,
+                    address = address,
+                    length = response_data.len()
+                )
+            );
+            return Err(NetworkError::DamagedFrame(e));
+        }
+    }
+    debug!("{}", tr!("network.log.verified"));
+    Ok(())
+}
+
+/// 把收到的响应帧加入重组器，损坏的帧丢弃，对应的包按缺失补发
+fn push_packets(assembler: &mut PacketAssembler, response_data: &[u8]) {
+    for frame in split_frames(response_data) {
+        if let Err(e) = assembler.push(frame) {
+            warn!("{}", tr!("network.log.packetDamaged", error = e));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::test_data::{sample_response, with_checksum};
+    use crate::protocol::response::SENSOR_DATA_OFFSET;
+    use crate::protocol::PACKET_INDEX_OFFSET;
+    use std::collections::VecDeque;
+
+    /// 按请求的包序号返回预设响应的模拟链路
+    struct MockTransport {
+        /// 每次请求后返回的数据
+        replies: VecDeque<Vec<u8>>,
+        /// 尚未读取的数据
+        pending: Vec<u8>,
+        /// 收到的请求中的包序号
+        requested: Vec<u8>,
+    }
+
+    #[async_trait]
+    impl Transport for MockTransport {
+        fn describe(&self) -> String {
+            "mock".to_string()
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn connect(&mut self, _timeout_ms: u64, _control: &RequestControl) -> Result<(), NetworkError> {
+            Ok(())
+        }
+
+        async fn close(&mut self) {}
+
+        async fn write_raw(&mut self, data: &[u8], _wait: Duration) -> io::Result<()> {
+            self.requested.push(data[PACKET_INDEX_OFFSET]);
+            if let Some(reply) = self.replies.pop_front() {
+                self.pending.extend_from_slice(&reply);
+            }
+            Ok(())
+        }
+
+        async fn read_raw(&mut self, buffer: &mut [u8], _wait: Duration) -> io::Result<usize> {
+            if self.pending.is_empty() {
+                return Err(io::Error::new(ErrorKind::TimedOut, "no data"));
+            }
+            let length = self.pending.len().min(buffer.len());
+            buffer[..length].copy_from_slice(&self.pending[..length]);
+            self.pending.drain(..length);
+            Ok(length)
+        }
+    }
+
+    fn packet(packet_index: u8) -> Vec<u8> {
+        let mut frame = sample_response();
+        frame[PACKET_INDEX_OFFSET] = packet_index;
+        with_checksum(frame)
+    }
+
+    #[tokio::test]
+    async fn resends_damaged_packet() {
+        // 第2包的第一个测温点读数被改动，校验不通过
+        let mut damaged = packet(1);
+        damaged[SENSOR_DATA_OFFSET] ^= 0xFF;
+        let mut transport = MockTransport {
+            replies: VecDeque::from([[packet(0), damaged].concat(), packet(1)]),
+            pending: Vec::new(),
+            requested: Vec::new(),
+        };
+
+        let frame = poll_grain(&mut transport, 1, 1024, 100, 2, &RequestControl::default())
+            .await
+            .unwrap();
+
+        assert_eq!(transport.requested, vec![0, 1]);
+        assert_eq!(frame.packet_count, 2);
+        assert!(frame.sensors.iter().any(|sensor| sensor.sensor_id == 513));
+    }
+}
@@ -0,0 +1,70 @@
+// 帧校验和计算模块
+use serde::{Deserialize, Serialize};
+
+/// 设备系列使用的校验算法
+///
+/// 请求帧（AA A0）使用8位累加和，响应帧（AA B0）使用CRC-16/MODBUS，
+/// 低字节在前存放在帧尾 EF EF 之前
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChecksumAlgorithm {
+    /// 8位累加和
+    Sum8,
+    /// CRC-16/MODBUS（多项式0xA001反射，初值0xFFFF）
+    Crc16Modbus,
+}
+
+impl ChecksumAlgorithm {
+    /// 计算校验值
+    pub fn compute(&self, bytes: &[u8]) -> u16 {
+        match self {
+            ChecksumAlgorithm::Sum8 => sum8(bytes) as u16,
+            ChecksumAlgorithm::Crc16Modbus => crc16_modbus(bytes),
+        }
+    }
+
+    /// 校验值在帧中占用的字节数
+    pub fn width(&self) -> usize {
+        match self {
+            ChecksumAlgorithm::Sum8 => 1,
+            ChecksumAlgorithm::Crc16Modbus => 2,
+        }
+    }
+}
+
+/// 计算8位累加和校验（所有字节相加后取低8位）
+///
+/// # 参数
+///
+/// * `bytes` - 参与校验的字节
+///
+/// # 返回值
+///
+/// 累加和的低8位
+pub fn sum8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |acc, byte| acc.wrapping_add(*byte))
+}
+
+/// 计算CRC-16/MODBUS校验
+///
+/// # 参数
+///
+/// * `bytes` - 参与校验的字节
+///
+/// # 返回值
+///
+/// 16位CRC值，写入帧时低字节在前
+pub fn crc16_modbus(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for byte in bytes {
+        crc ^= *byte as u16;
+        for _ in 0..8 {
+            if crc & 0x0001 != 0 {
+                crc = (crc >> 1) ^ 0xA001;
+            } else {
+                crc >>= 1;
+            }
+        }
+    }
+    crc
+}
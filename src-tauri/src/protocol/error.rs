@@ -0,0 +1,68 @@
+// 协议帧错误定义
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::checksum::ChecksumAlgorithm;
+use crate::i18n::tr;
+
+/// 帧校验或解析失败的原因
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum FrameError {
+    /// 输入不是有效的16进制数据
+    InvalidHex { message: String },
+    /// 帧长度不足
+    TooShort { length: usize, minimum: usize },
+    /// 包头不是预期的帧类型
+    InvalidHeader { found: [u8; 2], expected: [u8; 2] },
+    /// 帧尾不是 EF EF
+    MissingTrailer { offset: usize, found: [u8; 2] },
+    /// 校验和不一致，`expected` 为按数据计算出的值，`actual` 为帧中携带的值
+    ChecksumMismatch {
+        algorithm: ChecksumAlgorithm,
+        expected: u16,
+        actual: u16,
+        offset: usize,
+    },
+    /// 预期的响应包数量超过包序号字段能表示的范围
+    TooManyPackets { expected: usize, maximum: usize },
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            FrameError::InvalidHex { message } => tr!("frame.invalidHex", message = message),
+            FrameError::TooShort { length, minimum } => {
+                tr!("frame.tooShort", length = length, minimum = minimum)
+            }
+            FrameError::InvalidHeader { found, expected } => tr!(
+                "frame.invalidHeader",
+                found = format!("{:02X} {:02X}", found[0], found[1]),
+                expected = format!("{:02X} {:02X}", expected[0], expected[1])
+            ),
+            FrameError::MissingTrailer { offset, found } => tr!(
+                "frame.missingTrailer",
+                offset = offset,
+                found = format!("{:02X} {:02X}", found[0], found[1])
+            ),
+            FrameError::ChecksumMismatch {
+                algorithm,
+                expected,
+                actual,
+                offset,
+            } => tr!(
+                "frame.checksumMismatch",
+                offset = offset,
+                algorithm = format!("{:?}", algorithm),
+                expected = format!("{:04X}", expected),
+                actual = format!("{:04X}", actual)
+            ),
+            FrameError::TooManyPackets { expected, maximum } => {
+                tr!("frame.tooManyPackets", expected = expected, maximum = maximum)
+            }
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for FrameError {}
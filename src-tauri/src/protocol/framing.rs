@@ -0,0 +1,254 @@
+// 帧边界识别模块，用于判断接收缓冲区中是否已收到完整的响应帧
+use log::trace;
+
+use super::response::{verify_response_frame, FRAME_TRAILER, RESPONSE_FRAME_LENGTH, RESPONSE_MIN_LENGTH};
+use super::{FRAME_START, RESPONSE_FRAME_TYPE};
+use crate::i18n::tr;
+
+/// 接收缓冲区的帧状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameStatus {
+    /// 最后一个响应帧尚未接收完整，`complete_length` 为此前完整帧占用的字节数，
+    /// `expected` 为收完当前帧所需的缓冲区长度
+    Incomplete { complete_length: usize, expected: usize },
+    /// 缓冲区前 `length` 个字节由 `frames` 个完整的响应帧组成
+    Complete { length: usize, frames: usize },
+    /// 缓冲区不是AA B0响应帧，`trailer_seen` 表示是否已以 EF EF 结尾
+    Unframed { trailer_seen: bool },
+}
+
+/// 判断缓冲区是否以AA B0响应帧开头（不足2字节时按已收到的部分判断）
+pub fn starts_with_response_header(buffer: &[u8]) -> bool {
+    match buffer {
+        [] => false,
+        [first] => *first == FRAME_START,
+        [first, second, ..] => *first == FRAME_START && *second == RESPONSE_FRAME_TYPE,
+    }
+}
+
+/// 查找以缓冲区开头的响应帧的结束位置
+///
+/// 在前1068字节内查找以 EF EF 结尾且校验和正确的最短帧，较短的帧之后紧跟下一帧时也能正确分开；
+/// 标准帧长度1068字节为上限，收满后仍未找到校验正确的帧尾时按1068字节视为帧结束（是否损坏由校验决定）
+///
+/// # 返回值
+///
+/// 找到完整帧时返回帧长度，否则返回None
+pub fn find_frame_end(buffer: &[u8]) -> Option<usize> {
+    let limit = buffer.len().min(RESPONSE_FRAME_LENGTH);
+    let verified = (RESPONSE_MIN_LENGTH..=limit)
+        .filter(|end| buffer[end - FRAME_TRAILER.len()..*end] == FRAME_TRAILER)
+        .find(|end| verify_response_frame(&buffer[..*end]).is_ok());
+    match verified {
+        Some(end) => Some(end),
+        None if buffer.len() >= RESPONSE_FRAME_LENGTH => Some(RESPONSE_FRAME_LENGTH),
+        None => None,
+    }
+}
+
+/// 检查接收缓冲区的帧状态
+///
+/// # 参数
+///
+/// * `buffer` - 当前已接收到的全部字节
+///
+/// # 返回值
+///
+/// 缓冲区中帧的接收状态
+pub fn frame_status(buffer: &[u8]) -> FrameStatus {
+    if !starts_with_response_header(buffer) {
+        return FrameStatus::Unframed {
+            trailer_seen: buffer.ends_with(&FRAME_TRAILER),
+        };
+    }
+
+    let mut offset = 0;
+    let mut frames = 0;
+    while offset < buffer.len() {
+        let rest = &buffer[offset..];
+        if !starts_with_response_header(rest) {
+            // 完整帧之后跟随的非协议数据不影响已收到的帧
+            trace!("{}", tr!("protocol.unframedIgnored", offset = offset));
+            break;
+        }
+        match find_frame_end(rest) {
+            Some(length) => {
+                offset += length;
+                frames += 1;
+            }
+            None => {
+                return FrameStatus::Incomplete {
+                    complete_length: offset,
+                    expected: offset + RESPONSE_FRAME_LENGTH,
+                }
+            }
+        }
+    }
+
+    FrameStatus::Complete {
+        length: offset,
+        frames,
+    }
+}
+
+/// 丢弃响应帧之前和帧之间的非协议数据，如接入设备在轮询过程中发送的心跳包或注册包
+///
+/// 只丢弃之后还有 AA B0 帧头的数据，没有帧头的数据（如非协议命令的响应）保持不变
+///
+/// # 返回值
+///
+/// 丢弃的字节数
+pub fn discard_unframed(buffer: &mut Vec<u8>) -> usize {
+    let mut offset = 0;
+    let mut discarded = 0;
+    while offset < buffer.len() {
+        let rest = &buffer[offset..];
+        if starts_with_response_header(rest) {
+            match find_frame_end(rest) {
+                Some(length) => {
+                    offset += length;
+                    continue;
+                }
+                None => break,
+            }
+        }
+        let Some(start) = rest
+            .windows(2)
+            .position(|pair| pair == [FRAME_START, RESPONSE_FRAME_TYPE])
+        else {
+            break;
+        };
+        trace!("{}", tr!("protocol.unframedDiscarded", offset = offset, length = start));
+        buffer.drain(offset..offset + start);
+        discarded += start;
+    }
+    discarded
+}
+
+/// 将缓冲区拆分为连续的响应帧，末尾不完整的部分单独作为最后一项返回
+pub fn split_frames(buffer: &[u8]) -> Vec<&[u8]> {
+    let mut frames = Vec::new();
+    let mut offset = 0;
+    while offset < buffer.len() {
+        let rest = &buffer[offset..];
+        let length = match find_frame_end(rest) {
+            Some(length) if starts_with_response_header(rest) => length,
+            _ => rest.len(),
+        };
+        frames.push(&rest[..length]);
+        offset += length;
+    }
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::test_data::{sample_response, with_checksum};
+
+    /// 只带2个测温点的短帧
+    fn short_frame() -> Vec<u8> {
+        let sample = sample_response();
+        let mut frame = sample[..14].to_vec();
+        frame.extend_from_slice(&[0x00, 0x00, 0xEF, 0xEF]);
+        with_checksum(frame)
+    }
+
+    #[test]
+    fn finds_standard_frame_end() {
+        let mut buffer = sample_response();
+        assert_eq!(find_frame_end(&buffer), Some(RESPONSE_FRAME_LENGTH));
+        assert_eq!(find_frame_end(&buffer[..RESPONSE_FRAME_LENGTH - 1]), None);
+        buffer.extend_from_slice(&sample_response()[..100]);
+        assert_eq!(find_frame_end(&buffer), Some(RESPONSE_FRAME_LENGTH));
+    }
+
+    #[test]
+    fn short_frame_followed_by_next_frame() {
+        let short = short_frame();
+        let mut buffer = short.clone();
+        buffer.extend_from_slice(&sample_response());
+
+        assert_eq!(find_frame_end(&buffer), Some(short.len()));
+        assert_eq!(
+            frame_status(&buffer),
+            FrameStatus::Complete {
+                length: short.len() + RESPONSE_FRAME_LENGTH,
+                frames: 2,
+            }
+        );
+        let frames = split_frames(&buffer);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0], short.as_slice());
+        assert_eq!(frames[1], sample_response().as_slice());
+    }
+
+    #[test]
+    fn damaged_frame_ends_at_standard_length() {
+        let mut buffer = sample_response();
+        buffer[20] ^= 0x01;
+        assert_eq!(find_frame_end(&buffer), Some(RESPONSE_FRAME_LENGTH));
+        assert_eq!(
+            frame_status(&buffer),
+            FrameStatus::Complete {
+                length: RESPONSE_FRAME_LENGTH,
+                frames: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn incomplete_second_frame() {
+        let mut buffer = sample_response();
+        buffer.extend_from_slice(&sample_response()[..500]);
+        assert_eq!(
+            frame_status(&buffer),
+            FrameStatus::Incomplete {
+                complete_length: RESPONSE_FRAME_LENGTH,
+                expected: 2 * RESPONSE_FRAME_LENGTH,
+            }
+        );
+    }
+
+    #[test]
+    fn discards_heartbeat_before_and_between_frames() {
+        let heartbeat = b"HB:DTU-0001".to_vec();
+        let mut buffer = heartbeat.clone();
+        buffer.extend_from_slice(&sample_response());
+        buffer.extend_from_slice(&heartbeat);
+        buffer.extend_from_slice(&sample_response()[..100]);
+
+        assert_eq!(discard_unframed(&mut buffer), 2 * heartbeat.len());
+        assert_eq!(buffer.len(), RESPONSE_FRAME_LENGTH + 100);
+        assert_eq!(
+            frame_status(&buffer),
+            FrameStatus::Incomplete {
+                complete_length: RESPONSE_FRAME_LENGTH,
+                expected: 2 * RESPONSE_FRAME_LENGTH,
+            }
+        );
+    }
+
+    #[test]
+    fn keeps_data_without_frame_header() {
+        let mut buffer = b"HB:DTU-0001".to_vec();
+        assert_eq!(discard_unframed(&mut buffer), 0);
+        assert_eq!(buffer, b"HB:DTU-0001");
+
+        // 完整帧之后还没有下一帧帧头的数据保持不变
+        let mut buffer = sample_response();
+        buffer.extend_from_slice(b"HB");
+        buffer.push(FRAME_START);
+        assert_eq!(discard_unframed(&mut buffer), 0);
+        assert_eq!(buffer.len(), RESPONSE_FRAME_LENGTH + 3);
+    }
+
+    #[test]
+    fn non_protocol_data_is_unframed() {
+        assert_eq!(frame_status(b"REG:0001"), FrameStatus::Unframed { trailer_seen: false });
+        assert_eq!(
+            frame_status(&[0x01, 0x02, 0xEF, 0xEF]),
+            FrameStatus::Unframed { trailer_seen: true }
+        );
+    }
+}
@@ -0,0 +1,163 @@
+// 粮情协议模块，负责设备通信帧的编解码
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+use crate::utils::bcd_utils::{bcd_to_decimal, decimal_to_bcd};
+
+// 导出校验和计算模块
+pub mod checksum;
+// 导出帧错误定义
+pub mod error;
+// 导出帧边界识别模块
+pub mod framing;
+// 导出多包响应重组模块
+pub mod multipacket;
+// 导出请求帧构建模块
+pub mod request;
+// 导出响应帧解析模块
+pub mod response;
+// 单元测试使用的示例帧
+#[cfg(test)]
+pub(crate) mod test_data;
+
+pub use checksum::ChecksumAlgorithm;
+pub use error::FrameError;
+pub use multipacket::{packet_count_for, PacketAssembler};
+pub use request::RequestFrame;
+pub use response::{
+    decode_response, verify_response_frame, EnvironmentData, ResponseFrame, SensorFault, SensorReading,
+    SensorStatus,
+};
+
+/// 帧起始字节
+pub const FRAME_START: u8 = 0xAA;
+/// 请求帧（主机 -> 设备）的帧类型字节
+pub const REQUEST_FRAME_TYPE: u8 = 0xA0;
+/// 响应帧（设备 -> 主机）的帧类型字节
+pub const RESPONSE_FRAME_TYPE: u8 = 0xB0;
+
+/// 时间字段在帧中的起始位置（第2-7字节，年月日时分秒）
+pub const TIMESTAMP_OFFSET: usize = 2;
+/// 数据包序号在帧中的位置（从0开始，单包响应为0）
+///
+/// 依据贝博协议帧格式（见 README「数据格式」）：时间字段之后的第8字节在请求帧和单包响应中
+/// 固定为00，多包响应用该字节区分包序号，补发请求也在该字节填写要补发的包序号。
+/// 现有示例数据（`src/utils/dataSample.js`）只有单包帧，接入新型号设备时应以厂商协议文档核对
+pub const PACKET_INDEX_OFFSET: usize = 8;
+/// 分机地址在帧中的位置
+pub const ADDRESS_OFFSET: usize = 9;
+
+/// 帧中携带的BCD时间戳
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameTimestamp {
+    /// 完整年份（协议只传输后两位，按20xx年处理）
+    pub year: u16,
+    /// 月
+    pub month: u8,
+    /// 日
+    pub day: u8,
+    /// 时
+    pub hour: u8,
+    /// 分
+    pub minute: u8,
+    /// 秒
+    pub second: u8,
+}
+
+impl FrameTimestamp {
+    /// 从6个BCD字节（年月日时分秒）解析时间戳
+    ///
+    /// # 参数
+    ///
+    /// * `bytes` - 至少6个字节的BCD时间数据
+    ///
+    /// # 返回值
+    ///
+    /// 成功时返回时间戳，字节不是有效BCD码或日期不合法时返回错误信息
+    pub fn from_bcd(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() < 6 {
+            return Err(tr!("protocol.timestampTooShort", length = bytes.len()));
+        }
+
+        let mut values = [0u8; 6];
+        for (index, byte) in bytes[..6].iter().enumerate() {
+            values[index] = bcd_to_decimal(*byte)
+                .ok_or_else(|| {
+                    tr!("protocol.invalidBcd", index = index, byte = format!("{:02X}", byte))
+                })?;
+        }
+
+        let timestamp = FrameTimestamp {
+            year: 2000 + values[0] as u16,
+            month: values[1],
+            day: values[2],
+            hour: values[3],
+            minute: values[4],
+            second: values[5],
+        };
+
+        if timestamp.to_naive().is_none() {
+            return Err(tr!("protocol.invalidTimestamp", timestamp = timestamp.format()));
+        }
+
+        Ok(timestamp)
+    }
+
+    /// 从chrono的NaiveDateTime创建时间戳
+    pub fn from_naive(datetime: &NaiveDateTime) -> Self {
+        FrameTimestamp {
+            year: datetime.year() as u16,
+            month: datetime.month() as u8,
+            day: datetime.day() as u8,
+            hour: datetime.hour() as u8,
+            minute: datetime.minute() as u8,
+            second: datetime.second() as u8,
+        }
+    }
+
+    /// 编码为6个BCD字节（年月日时分秒），年份只保留后两位
+    ///
+    /// # 返回值
+    ///
+    /// 成功时返回BCD字节，年份不在2000-2099范围内时返回错误信息
+    pub fn to_bcd(&self) -> Result<[u8; 6], String> {
+        if !(2000..=2099).contains(&self.year) {
+            return Err(tr!("protocol.yearOutOfRange", year = self.year));
+        }
+
+        let values = [
+            (self.year - 2000) as u8,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second,
+        ];
+        let mut bytes = [0u8; 6];
+        for (index, value) in values.iter().enumerate() {
+            bytes[index] = decimal_to_bcd(*value)
+                .ok_or_else(|| tr!("protocol.unencodableBcd", index = index, value = value))?;
+        }
+        Ok(bytes)
+    }
+
+    /// 转换为chrono的NaiveDateTime，日期不合法时返回None
+    pub fn to_naive(&self) -> Option<NaiveDateTime> {
+        NaiveDate::from_ymd_opt(self.year as i32, self.month as u32, self.day as u32)?.and_hms_opt(
+            self.hour as u32,
+            self.minute as u32,
+            self.second as u32,
+        )
+    }
+
+    /// 格式化为 "YYYY-MM-DD HH:MM:SS"
+    pub fn format(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
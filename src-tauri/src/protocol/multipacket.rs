@@ -0,0 +1,213 @@
+// 多包响应重组模块，超过512个测温点的粮仓会分多个响应帧上报
+use log::{debug, warn};
+use std::collections::BTreeMap;
+
+use super::error::FrameError;
+use super::response::{decode_response, ResponseFrame, SENSORS_PER_PACKET};
+use crate::i18n::tr;
+
+/// 单次读取最多的响应包数量，测温点编号为u16，包数再多时编号会超出范围
+pub const MAX_PACKETS: usize = u16::MAX as usize / SENSORS_PER_PACKET;
+
+/// 根据测温点总数计算响应包数量，至少为1
+pub fn packet_count_for(total_points: usize) -> usize {
+    total_points.div_ceil(SENSORS_PER_PACKET).max(1)
+}
+
+/// 多包响应重组器
+///
+/// 按包序号保存已收到的响应帧，可查询缺失的包并在收齐后合并为一个结果
+#[derive(Debug)]
+pub struct PacketAssembler {
+    sub_device_address: u8,
+    expected_packets: usize,
+    packets: BTreeMap<u8, ResponseFrame>,
+}
+
+impl PacketAssembler {
+    /// 创建重组器
+    ///
+    /// # 参数
+    ///
+    /// * `sub_device_address` - 请求的分机地址，其他地址的帧会被忽略
+    /// * `expected_packets` - 预期的响应包数量
+    ///
+    /// # 返回值
+    ///
+    /// 包数量超过测温点编号可表示的 `MAX_PACKETS` 个时返回错误
+    pub fn new(sub_device_address: u8, expected_packets: usize) -> Result<Self, FrameError> {
+        if expected_packets > MAX_PACKETS {
+            return Err(FrameError::TooManyPackets {
+                expected: expected_packets,
+                maximum: MAX_PACKETS,
+            });
+        }
+
+        Ok(PacketAssembler {
+            sub_device_address,
+            expected_packets: expected_packets.max(1),
+            packets: BTreeMap::new(),
+        })
+    }
+
+    /// 加入一个收到的响应帧
+    ///
+    /// # 返回值
+    ///
+    /// 帧被接收时返回true，分机地址不符、包序号超出范围或重复时返回false，帧损坏时返回错误
+    pub fn push(&mut self, bytes: &[u8]) -> Result<bool, FrameError> {
+        let frame = decode_response(bytes)?;
+
+        if frame.sub_device_address != self.sub_device_address {
+            warn!(
+                "{}",
+                tr!(
+                    "protocol.addressIgnored",
+                    address = frame.sub_device_address,
+                    expected = self.sub_device_address
+                )
+            );
+            return Ok(false);
+        }
+        if frame.packet_index as usize >= self.expected_packets {
+            warn!(
+                "{}",
+                tr!(
+                    "protocol.packetOutOfRange",
+                    packet = frame.packet_index,
+                    expected = self.expected_packets
+                )
+            );
+            return Ok(false);
+        }
+        if self.packets.contains_key(&frame.packet_index) {
+            debug!("{}", tr!("protocol.packetDuplicate", packet = frame.packet_index));
+            return Ok(false);
+        }
+
+        debug!(
+            "{}",
+            tr!(
+                "protocol.packetReceived",
+                packet = frame.packet_index as usize + 1,
+                expected = self.expected_packets
+            )
+        );
+        self.packets.insert(frame.packet_index, frame);
+        Ok(true)
+    }
+
+    /// 预期的响应包数量
+    pub fn expected_packets(&self) -> usize {
+        self.expected_packets
+    }
+
+    /// 尚未收到的包序号
+    pub fn missing(&self) -> Vec<u8> {
+        (0..self.expected_packets)
+            .map(|index| index as u8)
+            .filter(|index| !self.packets.contains_key(index))
+            .collect()
+    }
+
+    /// 是否已收齐全部响应包
+    pub fn is_complete(&self) -> bool {
+        self.packets.len() == self.expected_packets
+    }
+
+    /// 按包序号合并全部响应包
+    ///
+    /// 时间取自第0包，环境数据取第一个带有该项数据的包
+    ///
+    /// # 返回值
+    ///
+    /// 收齐时返回合并后的响应帧，否则返回None
+    pub fn assemble(self) -> Option<ResponseFrame> {
+        if !self.is_complete() {
+            return None;
+        }
+
+        let mut packets = self.packets.into_values();
+        let mut merged = packets.next()?;
+        for packet in packets {
+            merged.sensors.extend(packet.sensors);
+            merged.faults.extend(packet.faults);
+            merged.raw_length += packet.raw_length;
+            merged.raw.extend(packet.raw);
+            merged.packet_count += packet.packet_count;
+
+            let environment = &mut merged.environment;
+            environment.indoor_temperature = environment
+                .indoor_temperature
+                .or(packet.environment.indoor_temperature);
+            environment.indoor_humidity =
+                environment.indoor_humidity.or(packet.environment.indoor_humidity);
+            environment.outdoor_temperature = environment
+                .outdoor_temperature
+                .or(packet.environment.outdoor_temperature);
+            environment.outdoor_humidity = environment
+                .outdoor_humidity
+                .or(packet.environment.outdoor_humidity);
+        }
+
+        debug!(
+            "{}",
+            tr!(
+                "protocol.packetsMerged",
+                packets = merged.packet_count,
+                sensors = merged.sensors.len()
+            )
+        );
+        Some(merged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::test_data::{sample_response, with_checksum};
+    use crate::protocol::PACKET_INDEX_OFFSET;
+
+    fn sample_packet(packet_index: u8) -> Vec<u8> {
+        let mut frame = sample_response();
+        frame[PACKET_INDEX_OFFSET] = packet_index;
+        with_checksum(frame)
+    }
+
+    #[test]
+    fn counts_packets_per_512_sensors() {
+        assert_eq!(packet_count_for(0), 1);
+        assert_eq!(packet_count_for(512), 1);
+        assert_eq!(packet_count_for(513), 2);
+    }
+
+    #[test]
+    fn rejects_more_packets_than_sensor_ids_can_address() {
+        assert_eq!(
+            PacketAssembler::new(1, MAX_PACKETS + 1).unwrap_err(),
+            FrameError::TooManyPackets {
+                expected: MAX_PACKETS + 1,
+                maximum: MAX_PACKETS,
+            }
+        );
+
+        let assembler = PacketAssembler::new(1, MAX_PACKETS).unwrap();
+        let missing = assembler.missing();
+        assert_eq!(missing.len(), MAX_PACKETS);
+        assert_eq!(missing.last(), Some(&126));
+    }
+
+    #[test]
+    fn assembles_packets_received_out_of_order() {
+        let mut assembler = PacketAssembler::new(1, 2).unwrap();
+        assert!(assembler.push(&sample_packet(1)).unwrap());
+        assert_eq!(assembler.missing(), vec![0]);
+        assert!(!assembler.push(&sample_packet(1)).unwrap());
+        assert!(!assembler.push(&sample_packet(2)).unwrap());
+        assert!(assembler.push(&sample_packet(0)).unwrap());
+
+        let frame = assembler.assemble().unwrap();
+        assert_eq!(frame.packet_count, 2);
+        assert_eq!(frame.sensors.len(), 400);
+    }
+}
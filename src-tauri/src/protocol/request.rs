@@ -0,0 +1,152 @@
+// AA A0 请求帧构建模块
+use chrono::{Local, NaiveDateTime};
+use log::{debug, warn};
+
+use super::checksum::ChecksumAlgorithm;
+use super::{
+    FrameTimestamp, ADDRESS_OFFSET, FRAME_START, PACKET_INDEX_OFFSET, REQUEST_FRAME_TYPE,
+    TIMESTAMP_OFFSET,
+};
+use super::response::FRAME_TRAILER;
+use crate::i18n::tr;
+
+/// 读取粮情数据的命令码
+pub const POLL_COMMAND: u8 = 0xA0;
+/// 请求帧固定长度
+pub const REQUEST_FRAME_LENGTH: usize = 28;
+/// 命令码在请求帧中的位置
+pub const COMMAND_OFFSET: usize = 10;
+/// 命令码后重复的分机地址位置
+pub const COMMAND_ADDRESS_OFFSET: usize = 11;
+/// 校验和在请求帧中的位置，校验范围为此前的全部字节
+pub const REQUEST_CHECKSUM_OFFSET: usize = 25;
+/// 请求帧使用的校验算法
+pub const REQUEST_CHECKSUM: ChecksumAlgorithm = ChecksumAlgorithm::Sum8;
+/// 填充字节
+const PADDING_BYTE: u8 = 0xFF;
+
+/// 合法的分机地址范围
+pub const SUB_DEVICE_ADDRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=99;
+
+/// 请求帧构建器
+///
+/// 帧格式: `AA A0 YY MM DD HH mm SS NN XX CC XX FF ... FF SS EF EF`，NN为请求的包序号
+#[derive(Debug, Clone)]
+pub struct RequestFrame {
+    sub_device_address: u8,
+    timestamp: NaiveDateTime,
+    command: u8,
+    packet_index: u8,
+}
+
+impl RequestFrame {
+    /// 创建指定分机地址的读取请求，时间默认为当前本地时间
+    pub fn new(sub_device_address: u8) -> Self {
+        RequestFrame {
+            sub_device_address,
+            timestamp: Local::now().naive_local(),
+            command: POLL_COMMAND,
+            packet_index: 0,
+        }
+    }
+
+    /// 设置帧中携带的时间
+    pub fn timestamp(mut self, timestamp: NaiveDateTime) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// 设置命令码
+    pub fn command(mut self, command: u8) -> Self {
+        self.command = command;
+        self
+    }
+
+    /// 设置请求的数据包序号，用于补发多包响应中缺失的包
+    pub fn packet_index(mut self, packet_index: u8) -> Self {
+        self.packet_index = packet_index;
+        self
+    }
+
+    /// 生成完整的请求帧字节
+    ///
+    /// # 返回值
+    ///
+    /// 成功时返回28字节的请求帧，分机地址或时间超出协议范围时返回错误信息
+    pub fn build(&self) -> Result<Vec<u8>, String> {
+        if !SUB_DEVICE_ADDRESS_RANGE.contains(&self.sub_device_address) {
+            warn!("{}", tr!("protocol.addressOutOfRange", address = self.sub_device_address));
+            return Err(tr!(
+                "protocol.subDeviceAddressRange",
+                min = SUB_DEVICE_ADDRESS_RANGE.start(),
+                max = SUB_DEVICE_ADDRESS_RANGE.end(),
+                address = self.sub_device_address
+            ));
+        }
+
+        let timestamp_bcd = FrameTimestamp::from_naive(&self.timestamp).to_bcd()?;
+
+        let mut frame = vec![PADDING_BYTE; REQUEST_FRAME_LENGTH];
+        frame[0] = FRAME_START;
+        frame[1] = REQUEST_FRAME_TYPE;
+        frame[TIMESTAMP_OFFSET..TIMESTAMP_OFFSET + 6].copy_from_slice(&timestamp_bcd);
+        frame[PACKET_INDEX_OFFSET] = self.packet_index;
+        frame[ADDRESS_OFFSET] = self.sub_device_address;
+        frame[COMMAND_OFFSET] = self.command;
+        frame[COMMAND_ADDRESS_OFFSET] = self.sub_device_address;
+        frame[REQUEST_CHECKSUM_OFFSET] = REQUEST_CHECKSUM.compute(&frame[..REQUEST_CHECKSUM_OFFSET]) as u8;
+        frame[REQUEST_FRAME_LENGTH - 2..].copy_from_slice(&FRAME_TRAILER);
+
+        debug!(
+            "{}",
+            tr!(
+                "protocol.requestBuilt",
+                address = self.sub_device_address,
+                command = format!("{:02X}", self.command),
+                checksum = format!("{:02X}", frame[REQUEST_CHECKSUM_OFFSET])
+            )
+        );
+
+        Ok(frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::test_data::SAMPLE_SEND_DATA;
+    use crate::utils::hex_utils::hex_string_to_bytes;
+
+    fn sample_time() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2018-08-23 16:55:36", "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn builds_sample_request() {
+        let frame = RequestFrame::new(1).timestamp(sample_time()).build().unwrap();
+        assert_eq!(frame, hex_string_to_bytes(SAMPLE_SEND_DATA).unwrap());
+        assert_eq!(frame.len(), REQUEST_FRAME_LENGTH);
+        assert_eq!(frame[REQUEST_CHECKSUM_OFFSET], 0xC3);
+        assert_eq!(
+            frame[REQUEST_CHECKSUM_OFFSET] as u16,
+            REQUEST_CHECKSUM.compute(&frame[..REQUEST_CHECKSUM_OFFSET])
+        );
+    }
+
+    #[test]
+    fn writes_packet_index_and_address() {
+        let frame = RequestFrame::new(12).timestamp(sample_time()).packet_index(2).build().unwrap();
+        assert_eq!(frame[PACKET_INDEX_OFFSET], 2);
+        assert_eq!(frame[ADDRESS_OFFSET], 12);
+        assert_eq!(frame[COMMAND_OFFSET], POLL_COMMAND);
+        assert_eq!(frame[COMMAND_ADDRESS_OFFSET], 12);
+        assert_eq!(frame[REQUEST_FRAME_LENGTH - 2..], FRAME_TRAILER);
+    }
+
+    #[test]
+    fn rejects_sub_device_address_out_of_range() {
+        assert!(RequestFrame::new(0).build().is_err());
+        assert!(RequestFrame::new(100).build().is_err());
+        assert!(RequestFrame::new(99).build().is_ok());
+    }
+}
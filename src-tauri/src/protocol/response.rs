@@ -0,0 +1,527 @@
+// AA B0 粮情响应帧解析模块
+use log::{debug, trace, warn};
+use serde::{Deserialize, Serialize};
+
+use super::checksum::ChecksumAlgorithm;
+use super::error::FrameError;
+use super::{
+    FrameTimestamp, ADDRESS_OFFSET, FRAME_START, PACKET_INDEX_OFFSET, RESPONSE_FRAME_TYPE,
+    TIMESTAMP_OFFSET,
+};
+use crate::i18n::tr;
+use crate::layout::SensorPosition;
+
+/// 温度数据在帧中的起始位置
+pub const SENSOR_DATA_OFFSET: usize = 10;
+/// 环境数据（内湿）在帧中的起始位置，同时也是温度数据区的结束位置
+pub const ENVIRONMENT_OFFSET: usize = 1034;
+/// 内温（高字节在前）在帧中的位置
+pub const INDOOR_TEMPERATURE_OFFSET: usize = 1035;
+/// 外湿在帧中的位置
+pub const OUTDOOR_HUMIDITY_OFFSET: usize = 1052;
+/// 外温（高字节在前）在帧中的位置
+pub const OUTDOOR_TEMPERATURE_OFFSET: usize = 1053;
+/// 包含完整环境数据所需的最小帧长度
+pub const ENVIRONMENT_MIN_LENGTH: usize = 1055;
+/// 标准响应帧长度（最多512个测温点）
+pub const RESPONSE_FRAME_LENGTH: usize = 1068;
+/// 每个响应帧最多携带的测温点数量
+pub const SENSORS_PER_PACKET: usize = (ENVIRONMENT_OFFSET - SENSOR_DATA_OFFSET) / 2;
+/// 帧尾字节
+pub const FRAME_TRAILER: [u8; 2] = [0xEF, 0xEF];
+/// 响应帧使用的校验算法
+pub const RESPONSE_CHECKSUM: ChecksumAlgorithm = ChecksumAlgorithm::Crc16Modbus;
+/// 响应帧的最小长度（帧头、时间、分机地址、校验和与帧尾）
+pub const RESPONSE_MIN_LENGTH: usize = SENSOR_DATA_OFFSET + 4;
+
+/// 测温点温度分辨率（每个单位0.0625°C）
+const SENSOR_RESOLUTION: f64 = 0.0625;
+/// 环境温度分辨率（每个单位0.1°C）
+const ENVIRONMENT_RESOLUTION: f64 = 0.1;
+/// 合理的测温点温度范围，超出范围的值视为异常
+const SENSOR_VALID_RANGE: std::ops::RangeInclusive<f64> = -100.0..=100.0;
+/// 测温点短路时采集器上报的原始值
+const SHORT_CIRCUIT_RAW: u16 = 0x8000;
+
+/// 单个测温点的读数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SensorReading {
+    /// 测温点编号，从1开始，多包响应中按包序号连续编号
+    pub sensor_id: u16,
+    /// 温度值（°C）
+    pub temperature: f64,
+    /// 测温点坐标，按粮仓的测温点布局填写，未填写时为null
+    #[serde(default)]
+    pub position: Option<SensorPosition>,
+}
+
+/// 测温点状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SensorStatus {
+    /// 读数正常
+    Valid,
+    /// 断路：温度数据区中间出现 FF FF，通常是传感器或测温电缆断线
+    OpenCircuit,
+    /// 短路：原始值为 0x8000
+    ShortCircuit,
+    /// 温度超出 -100~100°C
+    OutOfRange,
+    /// 卡滞：连续多次读数完全相同
+    Stuck,
+    /// 缺失：编号在测温点总数以内但响应中没有数据
+    Missing,
+}
+
+/// 状态异常的测温点
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SensorFault {
+    /// 测温点编号
+    pub sensor_id: u16,
+    /// 异常类型
+    pub status: SensorStatus,
+    /// 原始值，断路、短路和超出量程时有值
+    pub raw: Option<u16>,
+    /// 温度值（°C），超出量程和卡滞时有值
+    pub temperature: Option<f64>,
+    /// 测温点坐标，按粮仓的测温点布局填写，未填写时为null
+    #[serde(default)]
+    pub position: Option<SensorPosition>,
+}
+
+/// 仓内外环境数据，缺失或为FF时对应字段为None
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentData {
+    /// 仓内温度（°C）
+    pub indoor_temperature: Option<f64>,
+    /// 仓内湿度（%RH）
+    pub indoor_humidity: Option<u8>,
+    /// 仓外温度（°C）
+    pub outdoor_temperature: Option<f64>,
+    /// 仓外湿度（%RH）
+    pub outdoor_humidity: Option<u8>,
+}
+
+/// 解析后的AA B0粮情响应帧
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseFrame {
+    /// 设备上报的时间，BCD码无效时为None
+    pub timestamp: Option<FrameTimestamp>,
+    /// 分机地址
+    pub sub_device_address: u8,
+    /// 数据包序号，重组后的多包响应为0
+    pub packet_index: u8,
+    /// 组成本结果的数据包数量
+    pub packet_count: usize,
+    /// 有效的测温点读数，卡滞的测温点仍保留读数
+    pub sensors: Vec<SensorReading>,
+    /// 状态异常的测温点，按测温点编号排列
+    #[serde(default)]
+    pub faults: Vec<SensorFault>,
+    /// 仓内外环境数据
+    pub environment: EnvironmentData,
+    /// 原始帧长度（字节）
+    pub raw_length: usize,
+    /// 原始帧数据，多包响应按包序号拼接；只在后端保存历史数据时使用，不发送给前端
+    #[serde(skip)]
+    pub raw: Vec<u8>,
+}
+
+impl ResponseFrame {
+    /// 把编号在测温点总数以内、既没有读数也没有异常记录的测温点标记为缺失
+    ///
+    /// # 参数
+    ///
+    /// * `total_points` - 分机配置的测温点总数
+    pub fn mark_missing(&mut self, total_points: usize) {
+        let total = u16::try_from(total_points).unwrap_or(u16::MAX);
+        let present: std::collections::HashSet<u16> = self
+            .sensors
+            .iter()
+            .map(|sensor| sensor.sensor_id)
+            .chain(self.faults.iter().map(|fault| fault.sensor_id))
+            .collect();
+        let missing: Vec<SensorFault> = (1..=total)
+            .filter(|sensor_id| !present.contains(sensor_id))
+            .map(|sensor_id| SensorFault {
+                sensor_id,
+                status: SensorStatus::Missing,
+                raw: None,
+                temperature: None,
+                position: None,
+            })
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+        debug!(
+            "{}",
+            tr!("protocol.sensorsMissing", address = self.sub_device_address, count = missing.len())
+        );
+        self.faults.extend(missing);
+        self.faults.sort_by_key(|fault| fault.sensor_id);
+    }
+}
+
+/// 校验AA B0响应帧的包头、帧尾和CRC-16/MODBUS校验和
+///
+/// # 参数
+///
+/// * `bytes` - 设备返回的原始字节
+///
+/// # 返回值
+///
+/// 帧完整时返回Ok，否则返回包含出错位置的结构化错误
+pub fn verify_response_frame(bytes: &[u8]) -> Result<(), FrameError> {
+    if bytes.len() < RESPONSE_MIN_LENGTH {
+        return Err(FrameError::TooShort {
+            length: bytes.len(),
+            minimum: RESPONSE_MIN_LENGTH,
+        });
+    }
+
+    if bytes[0] != FRAME_START || bytes[1] != RESPONSE_FRAME_TYPE {
+        return Err(FrameError::InvalidHeader {
+            found: [bytes[0], bytes[1]],
+            expected: [FRAME_START, RESPONSE_FRAME_TYPE],
+        });
+    }
+
+    let trailer_offset = bytes.len() - FRAME_TRAILER.len();
+    if bytes[trailer_offset..] != FRAME_TRAILER {
+        return Err(FrameError::MissingTrailer {
+            offset: trailer_offset,
+            found: [bytes[trailer_offset], bytes[trailer_offset + 1]],
+        });
+    }
+
+    let checksum_offset = trailer_offset - RESPONSE_CHECKSUM.width();
+    let expected = RESPONSE_CHECKSUM.compute(&bytes[..checksum_offset]);
+    let actual = u16::from_le_bytes([bytes[checksum_offset], bytes[checksum_offset + 1]]);
+    if expected != actual {
+        return Err(FrameError::ChecksumMismatch {
+            algorithm: RESPONSE_CHECKSUM,
+            expected,
+            actual,
+            offset: checksum_offset,
+        });
+    }
+
+    trace!("{}", tr!("protocol.checksumVerified", checksum = format!("{:04X}", actual)));
+    Ok(())
+}
+
+/// 解析AA B0粮情响应帧
+///
+/// # 参数
+///
+/// * `bytes` - 设备返回的原始字节
+///
+/// # 返回值
+///
+/// 成功时返回解析后的响应帧，帧不完整或校验失败时返回结构化错误
+pub fn decode_response(bytes: &[u8]) -> Result<ResponseFrame, FrameError> {
+    debug!("{}", tr!("protocol.decoding", length = bytes.len()));
+
+    if let Err(e) = verify_response_frame(bytes) {
+        warn!("{}", tr!("protocol.verifyFailed", error = e));
+        return Err(e);
+    }
+
+    let timestamp = match FrameTimestamp::from_bcd(&bytes[TIMESTAMP_OFFSET..TIMESTAMP_OFFSET + 6]) {
+        Ok(timestamp) => Some(timestamp),
+        Err(e) => {
+            // 时间解析失败时仍继续解析其他数据
+            warn!("{}", tr!("protocol.timestampFailed", error = e));
+            None
+        }
+    };
+    let sub_device_address = bytes[ADDRESS_OFFSET];
+    let packet_index = bytes[PACKET_INDEX_OFFSET];
+
+    let (sensors, faults) = decode_sensors(bytes, packet_index);
+    let environment = decode_environment(bytes);
+
+    debug!(
+        "{}",
+        tr!(
+            "protocol.decoded",
+            address = sub_device_address,
+            packet = packet_index,
+            sensors = sensors.len(),
+            faults = faults.len()
+        )
+    );
+
+    Ok(ResponseFrame {
+        timestamp,
+        sub_device_address,
+        packet_index,
+        packet_count: 1,
+        sensors,
+        faults,
+        environment,
+        raw_length: bytes.len(),
+        raw: bytes.to_vec(),
+    })
+}
+
+/// 解析温度数据区，分出有效读数和异常测温点
+///
+/// 末尾连续的 FF FF 视为数据结束，之后还有数据的 FF FF 视为该测温点断路
+fn decode_sensors(bytes: &[u8], packet_index: u8) -> (Vec<SensorReading>, Vec<SensorFault>) {
+    // 最后4个字节是校验和与帧尾，不属于温度数据
+    let payload_end = bytes.len() - RESPONSE_CHECKSUM.width() - FRAME_TRAILER.len();
+    let data_end = payload_end.min(ENVIRONMENT_OFFSET);
+
+    let mut sensors = Vec::new();
+    let mut faults = Vec::new();
+    if data_end <= SENSOR_DATA_OFFSET {
+        return (sensors, faults);
+    }
+
+    let pairs: Vec<&[u8]> = bytes[SENSOR_DATA_OFFSET..data_end].chunks_exact(2).collect();
+    let count = pairs.iter().rposition(|pair| *pair != [0xFF, 0xFF]).map_or(0, |last| last + 1);
+    if count < pairs.len() {
+        trace!("{}", tr!("protocol.sensorDataEnd", offset = SENSOR_DATA_OFFSET + count * 2));
+    }
+
+    for (index, pair) in pairs[..count].iter().enumerate() {
+        // 超出u16的编号会与前面包的测温点重复，之后的数据不再解析
+        let Ok(sensor_id) = u16::try_from(packet_index as usize * SENSORS_PER_PACKET + index + 1) else {
+            warn!("{}", tr!("protocol.sensorIdOverflow", packet = packet_index, index = index));
+            break;
+        };
+        let raw = u16::from_le_bytes([pair[0], pair[1]]);
+        let temperature = raw as i16 as f64 * SENSOR_RESOLUTION;
+
+        let status = if raw == 0xFFFF {
+            SensorStatus::OpenCircuit
+        } else if raw == SHORT_CIRCUIT_RAW {
+            SensorStatus::ShortCircuit
+        } else if !SENSOR_VALID_RANGE.contains(&temperature) {
+            SensorStatus::OutOfRange
+        } else {
+            sensors.push(SensorReading {
+                sensor_id,
+                temperature,
+                position: None,
+            });
+            continue;
+        };
+
+        warn!(
+            "{}",
+            tr!(
+                "protocol.sensorFault",
+                sensor = sensor_id,
+                status = format!("{:?}", status),
+                raw = format!("{:04X}", raw)
+            )
+        );
+        faults.push(SensorFault {
+            sensor_id,
+            status,
+            raw: Some(raw),
+            temperature: (status == SensorStatus::OutOfRange).then_some(temperature),
+            position: None,
+        });
+    }
+
+    (sensors, faults)
+}
+
+/// 解析仓内外温湿度，帧长度不足时返回空的环境数据
+fn decode_environment(bytes: &[u8]) -> EnvironmentData {
+    if bytes.len() < ENVIRONMENT_MIN_LENGTH {
+        debug!("{}", tr!("protocol.environmentSkipped", length = ENVIRONMENT_MIN_LENGTH));
+        return EnvironmentData::default();
+    }
+
+    EnvironmentData {
+        indoor_temperature: decode_environment_temperature(bytes, INDOOR_TEMPERATURE_OFFSET),
+        indoor_humidity: decode_humidity(bytes[ENVIRONMENT_OFFSET]),
+        outdoor_temperature: decode_environment_temperature(bytes, OUTDOOR_TEMPERATURE_OFFSET),
+        outdoor_humidity: decode_humidity(bytes[OUTDOOR_HUMIDITY_OFFSET]),
+    }
+}
+
+/// 解析单字节湿度，FF表示无数据
+fn decode_humidity(byte: u8) -> Option<u8> {
+    if byte == 0xFF {
+        None
+    } else {
+        Some(byte)
+    }
+}
+
+/// 解析高字节在前的双字节环境温度，FF FF表示无数据
+fn decode_environment_temperature(bytes: &[u8], offset: usize) -> Option<f64> {
+    let pair = [bytes[offset], bytes[offset + 1]];
+    if pair == [0xFF, 0xFF] {
+        return None;
+    }
+    let raw = i16::from_be_bytes(pair);
+    Some((raw as f64 * ENVIRONMENT_RESOLUTION * 10.0).round() / 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::checksum::crc16_modbus;
+    use crate::protocol::REQUEST_FRAME_TYPE;
+    use crate::protocol::test_data::{sample_response, with_checksum};
+
+    #[test]
+    fn decodes_sample_response() {
+        let frame = decode_response(&sample_response()).unwrap();
+
+        assert_eq!(
+            frame.timestamp,
+            Some(FrameTimestamp {
+                year: 2018,
+                month: 8,
+                day: 23,
+                hour: 16,
+                minute: 55,
+                second: 36,
+            })
+        );
+        assert_eq!(frame.sub_device_address, 1);
+        assert_eq!(frame.packet_index, 0);
+        assert_eq!(frame.raw_length, RESPONSE_FRAME_LENGTH);
+        // 第201个测温点起为 FF FF，温度数据结束，不计为断路
+        assert_eq!(frame.sensors.len(), 200);
+        assert!(frame.faults.is_empty());
+        assert_eq!(frame.sensors[0].sensor_id, 1);
+        assert_eq!(frame.sensors[0].temperature, 18.9375);
+        assert_eq!(frame.sensors[1].temperature, 14.5625);
+        assert_eq!(frame.sensors[199].sensor_id, 200);
+        assert_eq!(frame.sensors[199].temperature, 5.625);
+        assert_eq!(
+            frame.environment,
+            EnvironmentData {
+                indoor_temperature: Some(20.2),
+                indoor_humidity: Some(59),
+                outdoor_temperature: Some(25.0),
+                outdoor_humidity: Some(25),
+            }
+        );
+    }
+
+    #[test]
+    fn sample_trailer_is_crc16_modbus() {
+        let bytes = sample_response();
+        assert_eq!(bytes[RESPONSE_FRAME_LENGTH - 4..], [0x7A, 0xB7, 0xEF, 0xEF]);
+        assert_eq!(crc16_modbus(&bytes[..RESPONSE_FRAME_LENGTH - 4]), 0xB77A);
+        assert_eq!(verify_response_frame(&bytes), Ok(()));
+    }
+
+    #[test]
+    fn classifies_sensor_faults() {
+        let mut bytes = sample_response();
+        // 第2个测温点断路，第3个短路，第4个超出量程（0x0800 = 128°C）
+        bytes[SENSOR_DATA_OFFSET + 2..SENSOR_DATA_OFFSET + 4].copy_from_slice(&[0xFF, 0xFF]);
+        bytes[SENSOR_DATA_OFFSET + 4..SENSOR_DATA_OFFSET + 6].copy_from_slice(&[0x00, 0x80]);
+        bytes[SENSOR_DATA_OFFSET + 6..SENSOR_DATA_OFFSET + 8].copy_from_slice(&[0x00, 0x08]);
+        let frame = decode_response(&with_checksum(bytes)).unwrap();
+
+        assert_eq!(frame.sensors.len(), 197);
+        let faults: Vec<(u16, SensorStatus)> = frame.faults.iter().map(|fault| (fault.sensor_id, fault.status)).collect();
+        assert_eq!(
+            faults,
+            [
+                (2, SensorStatus::OpenCircuit),
+                (3, SensorStatus::ShortCircuit),
+                (4, SensorStatus::OutOfRange),
+            ]
+        );
+        assert_eq!(frame.faults[2].temperature, Some(128.0));
+    }
+
+    #[test]
+    fn missing_environment_bytes_are_none() {
+        let mut bytes = sample_response();
+        bytes[ENVIRONMENT_OFFSET] = 0xFF;
+        bytes[OUTDOOR_TEMPERATURE_OFFSET..OUTDOOR_TEMPERATURE_OFFSET + 2].copy_from_slice(&[0xFF, 0xFF]);
+        let frame = decode_response(&with_checksum(bytes)).unwrap();
+
+        assert_eq!(frame.environment.indoor_humidity, None);
+        assert_eq!(frame.environment.outdoor_temperature, None);
+        assert_eq!(frame.environment.indoor_temperature, Some(20.2));
+    }
+
+    #[test]
+    fn rejects_short_frame() {
+        let bytes = sample_response();
+        assert_eq!(
+            decode_response(&bytes[..RESPONSE_MIN_LENGTH - 1]),
+            Err(FrameError::TooShort {
+                length: RESPONSE_MIN_LENGTH - 1,
+                minimum: RESPONSE_MIN_LENGTH,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_frame() {
+        let bytes = sample_response();
+        assert!(matches!(
+            decode_response(&bytes[..RESPONSE_FRAME_LENGTH - 1]),
+            Err(FrameError::MissingTrailer { offset, .. }) if offset == RESPONSE_FRAME_LENGTH - 3
+        ));
+    }
+
+    #[test]
+    fn rejects_wrong_header() {
+        let mut bytes = sample_response();
+        bytes[1] = REQUEST_FRAME_TYPE;
+        assert_eq!(
+            decode_response(&bytes),
+            Err(FrameError::InvalidHeader {
+                found: [FRAME_START, REQUEST_FRAME_TYPE],
+                expected: [FRAME_START, RESPONSE_FRAME_TYPE],
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_bad_checksum() {
+        let mut bytes = sample_response();
+        bytes[SENSOR_DATA_OFFSET] ^= 0x01;
+        match decode_response(&bytes) {
+            Err(FrameError::ChecksumMismatch {
+                algorithm,
+                expected,
+                actual,
+                offset,
+            }) => {
+                assert_eq!(algorithm, ChecksumAlgorithm::Crc16Modbus);
+                assert_eq!(actual, 0xB77A);
+                assert_ne!(expected, actual);
+                assert_eq!(offset, RESPONSE_FRAME_LENGTH - 4);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn stops_at_sensor_id_limit() {
+        let mut frame = sample_response();
+        frame[PACKET_INDEX_OFFSET] = 127;
+        for pair in frame[SENSOR_DATA_OFFSET..ENVIRONMENT_OFFSET].chunks_exact_mut(2) {
+            pair.copy_from_slice(&[0x2F, 0x01]);
+        }
+        let frame = decode_response(&with_checksum(frame)).unwrap();
+
+        // 第128包的第1个测温点编号为 127 × 512 + 1，最后一个编号 65536 超出u16
+        assert_eq!(frame.sensors.first().map(|sensor| sensor.sensor_id), Some(65025));
+        assert_eq!(frame.sensors.last().map(|sensor| sensor.sensor_id), Some(u16::MAX));
+        assert_eq!(frame.sensors.len(), SENSORS_PER_PACKET - 1);
+        assert!(frame.faults.is_empty());
+    }
+}
@@ -0,0 +1,48 @@
+// 检测报告错误定义
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::history::HistoryError;
+use crate::i18n::tr;
+
+/// 生成检测报告失败的原因
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ReportError {
+    /// 没有符合条件的记录
+    NoReadings,
+    /// 读取历史数据失败
+    History { error: HistoryError },
+    /// 报告模板不存在
+    UnknownTemplate { name: String },
+    /// 报告模板格式错误
+    InvalidTemplate { name: String, message: String },
+    /// 读取模板或写入文件失败
+    Storage { path: String, message: String },
+    /// 生成报告的后台任务异常结束
+    Task { message: String },
+}
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ReportError::NoReadings => tr!("report.noReadings"),
+            ReportError::History { error } => error.to_string(),
+            ReportError::UnknownTemplate { name } => tr!("report.unknownTemplate", name = name),
+            ReportError::InvalidTemplate { name, message } => {
+                tr!("report.invalidTemplate", name = name, error = message)
+            }
+            ReportError::Storage { path, message } => tr!("report.storage", path = path, error = message),
+            ReportError::Task { message } => tr!("report.task", error = message),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+impl From<HistoryError> for ReportError {
+    fn from(error: HistoryError) -> Self {
+        ReportError::History { error }
+    }
+}
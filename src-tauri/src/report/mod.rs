@@ -0,0 +1,264 @@
+// 检测报告模块，按模板把一次读取或一段时间内的读数生成可打印的粮情检测报告（HTML）
+//
+// 报告按A4纸排版，每次读取一页，在浏览器中打印或另存为PDF即可归档
+use chrono::Local;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config::ConfigStore;
+use crate::export::{self, ExportQuery};
+use crate::history::HistoryStore;
+use crate::i18n::tr;
+use crate::layout::Layouts;
+
+// 导出错误定义
+pub mod error;
+// 导出模板上下文定义
+pub mod model;
+// 导出模板渲染模块
+pub mod template;
+
+pub use error::ReportError;
+pub use model::{Inspection, ReportContext};
+
+/// 内置的标准粮情检测报告模板名称，模板目录中的同名文件可以替换内置模板
+pub const STANDARD_TEMPLATE: &str = "standard";
+
+/// 内置的标准粮情检测报告模板
+const STANDARD_TEMPLATE_CONTENT: &str = include_str!("templates/inspection.html");
+
+/// 模板文件扩展名
+const TEMPLATE_EXTENSION: &str = "html";
+
+/// 检测报告的生成条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportRequest {
+    /// 报告范围，设置 `readingId` 时只包含该次读取，否则按其余条件包含一段时间内的读取
+    #[serde(flatten)]
+    pub range: ExportQuery,
+    /// 检测人，填写在签字栏
+    pub inspector: Option<String>,
+    /// 检测结论或备注
+    pub remarks: Option<String>,
+    /// 模板名称，为None时使用标准模板
+    pub template: Option<String>,
+}
+
+/// 报告模板
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportTemplate {
+    /// 名称，即模板目录中不带扩展名的文件名
+    pub name: String,
+    /// 是否为模板目录中的文件，为false时是内置模板
+    pub custom: bool,
+}
+
+/// 生成的报告文件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportSummary {
+    /// 文件路径
+    pub path: String,
+    /// 使用的模板
+    pub template: String,
+    /// 包含的检测次数
+    pub inspection_count: usize,
+}
+
+/// 检测报告生成器，保存在Tauri托管状态中
+#[derive(Clone)]
+pub struct Reports {
+    dir: PathBuf,
+    history: HistoryStore,
+    layouts: Layouts,
+    config: ConfigStore,
+}
+
+impl Reports {
+    /// 创建报告生成器
+    ///
+    /// # 参数
+    ///
+    /// * `dir` - 模板目录，其中的 `*.html` 文件为自定义模板，目录不存在时只使用内置模板
+    /// * `history` - 历史数据库
+    /// * `layouts` - 测温点布局
+    /// * `config` - 应用配置，报告中的仓房信息取自站点台账，分布图按界面显示设置着色
+    pub fn new(dir: &Path, history: HistoryStore, layouts: Layouts, config: ConfigStore) -> Self {
+        Reports {
+            dir: dir.to_path_buf(),
+            history,
+            layouts,
+            config,
+        }
+    }
+
+    /// 可用的模板，内置模板在前，其余按名称排列
+    pub fn templates(&self) -> Result<Vec<ReportTemplate>, ReportError> {
+        let mut custom = Vec::new();
+        match fs::read_dir(&self.dir) {
+            Ok(entries) => {
+                for entry in entries {
+                    let path = entry.map_err(|e| storage_error(&self.dir, e))?.path();
+                    if path.extension().and_then(|extension| extension.to_str()) != Some(TEMPLATE_EXTENSION) {
+                        continue;
+                    }
+                    if let Some(name) = path.file_stem().and_then(|name| name.to_str()) {
+                        custom.push(name.to_string());
+                    }
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(storage_error(&self.dir, e)),
+        }
+        custom.sort();
+
+        let mut templates = vec![ReportTemplate {
+            name: STANDARD_TEMPLATE.to_string(),
+            custom: custom.iter().any(|name| name == STANDARD_TEMPLATE),
+        }];
+        templates.extend(
+            custom
+                .into_iter()
+                .filter(|name| name != STANDARD_TEMPLATE)
+                .map(|name| ReportTemplate { name, custom: true }),
+        );
+        Ok(templates)
+    }
+
+    /// 整理报告内容
+    pub fn context(&self, request: &ReportRequest) -> Result<ReportContext, ReportError> {
+        let readings = export::load_readings(&self.history, &self.layouts, &request.range)?;
+        if readings.is_empty() {
+            return Err(ReportError::NoReadings);
+        }
+        let (inventory, display) = self.config.read(|config| (config.inventory.clone(), config.display.clone()));
+        let inspections: Vec<Inspection> = readings
+            .iter()
+            .map(|(reading, layout)| Inspection::new(reading, layout, &inventory, &display))
+            .collect();
+
+        // 按一段时间生成时注明报告期间，未设置起止时间的一端取实际的采样时间
+        let period = match request.range.reading_id {
+            Some(_) => None,
+            None => {
+                let from = request.range.from.clone().unwrap_or_else(|| inspections[0].sampled_at.clone());
+                let to = request
+                    .range
+                    .to
+                    .clone()
+                    .unwrap_or_else(|| inspections[inspections.len() - 1].sampled_at.clone());
+                Some(format!("{} ~ {}", from, to))
+            }
+        };
+        let now = Local::now();
+        Ok(ReportContext {
+            generated_at: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+            report_date: now.format("%Y-%m-%d").to_string(),
+            inspector: non_empty(&request.inspector),
+            remarks: non_empty(&request.remarks),
+            period,
+            inspection_count: inspections.len(),
+            inspections,
+        })
+    }
+
+    /// 生成报告
+    ///
+    /// # 返回值
+    ///
+    /// 报告的HTML内容
+    pub fn render(&self, request: &ReportRequest) -> Result<String, ReportError> {
+        self.generate(request).map(|(content, _)| content)
+    }
+
+    /// 生成报告并写入文件
+    ///
+    /// # 参数
+    ///
+    /// * `request` - 生成条件
+    /// * `path` - 文件路径，已存在时覆盖
+    pub fn save(&self, request: &ReportRequest, path: &Path) -> Result<ReportSummary, ReportError> {
+        let (content, inspection_count) = self.generate(request)?;
+        // 先写入临时文件再替换，避免写入中断时留下不完整的报告
+        let temp = path.with_extension("html.tmp");
+        fs::write(&temp, content).map_err(|e| storage_error(&temp, e))?;
+        fs::rename(&temp, path).map_err(|e| storage_error(path, e))?;
+
+        let template = template_name(request).to_string();
+        info!(
+            "{}",
+            tr!(
+                "report.written",
+                template = template,
+                count = inspection_count,
+                path = path.display()
+            )
+        );
+        Ok(ReportSummary {
+            path: path.display().to_string(),
+            template,
+            inspection_count,
+        })
+    }
+
+    /// 按模板渲染报告内容，同时返回包含的检测次数
+    fn generate(&self, request: &ReportRequest) -> Result<(String, usize), ReportError> {
+        let name = template_name(request);
+        let template = self.load_template(name)?;
+        let context = self.context(request)?;
+        let invalid_template = |message: String| ReportError::InvalidTemplate {
+            name: name.to_string(),
+            message,
+        };
+        let value = serde_json::to_value(&context).map_err(|e| invalid_template(e.to_string()))?;
+        let content = template::render(&template, &value).map_err(invalid_template)?;
+        Ok((content, context.inspection_count))
+    }
+
+    /// 读取模板，模板目录中没有标准模板时使用内置模板
+    fn load_template(&self, name: &str) -> Result<String, ReportError> {
+        // 模板名称只能是模板目录中的文件名
+        if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(ReportError::UnknownTemplate { name: name.to_string() });
+        }
+        let path = self.dir.join(format!("{}.{}", name, TEMPLATE_EXTENSION));
+        match fs::read_to_string(&path) {
+            Ok(content) => Ok(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound && name == STANDARD_TEMPLATE => {
+                Ok(STANDARD_TEMPLATE_CONTENT.to_string())
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                warn!("{}", tr!("report.templateMissing", path = path.display()));
+                Err(ReportError::UnknownTemplate { name: name.to_string() })
+            }
+            Err(e) => Err(storage_error(&path, e)),
+        }
+    }
+}
+
+/// 请求使用的模板名称
+fn template_name(request: &ReportRequest) -> &str {
+    request.template.as_deref().unwrap_or(STANDARD_TEMPLATE)
+}
+
+/// 去掉首尾空白，为空时返回None
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// 生成存储错误
+fn storage_error(path: &Path, error: impl std::fmt::Display) -> ReportError {
+    ReportError::Storage {
+        path: path.display().to_string(),
+        message: error.to_string(),
+    }
+}
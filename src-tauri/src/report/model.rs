@@ -0,0 +1,245 @@
+// 检测报告的模板上下文，数值按报告的显示格式预先转换为文本
+use serde::Serialize;
+
+use crate::config::model::{DisplayConfig, TemperatureStyle};
+use crate::export::table::{cells_by_position, fault_list, layer_stats, sensor_cells, status_name, LayerStats};
+use crate::history::HistoryReading;
+use crate::i18n::tr;
+use crate::inventory::InventoryConfig;
+use crate::layout::{GranaryLayout, SensorPosition};
+use crate::protocol::SensorStatus;
+
+/// 数值缺失时显示的文本
+const NO_VALUE: &str = "—";
+
+/// 检测报告的模板上下文
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportContext {
+    /// 生成时间
+    pub generated_at: String,
+    /// 生成日期，用于签字栏
+    pub report_date: String,
+    /// 检测人
+    pub inspector: Option<String>,
+    /// 检测结论或备注
+    pub remarks: Option<String>,
+    /// 报告期间，按一段时间生成时为 "起始 ~ 结束"
+    pub period: Option<String>,
+    /// 包含的检测次数
+    pub inspection_count: usize,
+    /// 每次读取一份检测记录，按采样时间排列
+    pub inspections: Vec<Inspection>,
+}
+
+/// 一次读取的检测记录
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Inspection {
+    /// 历史记录编号
+    pub reading_id: i64,
+    /// 粮仓（仓房标识）
+    pub granary: String,
+    /// 仓房名称，台账中没有该仓房时为粮仓标识
+    pub warehouse_name: String,
+    /// 库点名称
+    pub depot_name: Option<String>,
+    /// 库点地址
+    pub depot_address: Option<String>,
+    /// 储粮品种
+    pub grain_type: Option<String>,
+    /// 仓容（吨）
+    pub capacity_tonnes: Option<String>,
+    /// 分机地址
+    pub sub_device_address: u8,
+    /// 采样时间
+    pub sampled_at: String,
+    /// 仓温（°C）
+    pub indoor_temperature: String,
+    /// 仓湿（%RH）
+    pub indoor_humidity: String,
+    /// 气温（°C）
+    pub outdoor_temperature: String,
+    /// 气湿（%RH）
+    pub outdoor_humidity: String,
+    /// 电缆行数、列数和每根电缆的测温点数量
+    pub cable_rows: u16,
+    pub cable_columns: u16,
+    pub points_per_cable: u16,
+    /// 全仓统计
+    pub overall: LayerSummary,
+    /// 各层统计，从上到下排列
+    pub layers: Vec<LayerSummary>,
+    /// 各层的测温电缆分布图
+    pub diagrams: Vec<Diagram>,
+}
+
+/// 一层或全仓的统计
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayerSummary {
+    /// 名称，如 "第1层"、"全仓"
+    pub label: String,
+    pub valid_count: usize,
+    pub max: String,
+    pub min: String,
+    pub average: String,
+    pub fault_count: usize,
+    /// 异常测温点列表，如 "12(断路), 35(卡滞)"
+    pub faults: String,
+}
+
+/// 一层的测温电缆分布图，按电缆行×列排列温度
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagram {
+    /// 名称，如 "第1层"
+    pub label: String,
+    /// 列标题
+    pub columns: Vec<String>,
+    pub rows: Vec<DiagramRow>,
+}
+
+/// 分布图的一行电缆
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagramRow {
+    /// 行标题
+    pub label: String,
+    pub cells: Vec<DiagramCell>,
+}
+
+/// 分布图中的一个测温点
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagramCell {
+    /// 温度，异常测温点为异常类型，跳过的位置为空
+    pub text: String,
+    /// 背景颜色和文字颜色，按界面显示设置中的温度区间取值，跳过的位置为None
+    pub color: Option<String>,
+    pub text_color: Option<String>,
+}
+
+impl Inspection {
+    /// 整理一次读取的检测记录
+    ///
+    /// # 参数
+    ///
+    /// * `reading` - 带测温点读数的历史记录，已按 `layout` 填写测温点坐标
+    /// * `layout` - 粮仓的测温点布局
+    /// * `inventory` - 站点台账，用于填写仓房和库点信息
+    /// * `display` - 界面显示设置，分布图按其中的温度区间着色
+    pub fn new(
+        reading: &HistoryReading,
+        layout: &GranaryLayout,
+        inventory: &InventoryConfig,
+        display: &DisplayConfig,
+    ) -> Self {
+        let warehouse = inventory.warehouse(&reading.granary);
+        let depot = warehouse.and_then(|warehouse| inventory.depot(&warehouse.depot_id));
+        let cells = sensor_cells(reading);
+        let (overall, layers) = layer_stats(&cells, layout);
+        let positions = cells_by_position(&cells);
+
+        let diagrams = (1..=layout.points_per_cable)
+            .map(|layer| Diagram {
+                label: tr!("export.layer", layer = layer),
+                columns: (1..=layout.cable_columns)
+                    .map(|column| tr!("export.columnLabel", column = column))
+                    .collect(),
+                rows: (1..=layout.cable_rows)
+                    .map(|row| DiagramRow {
+                        label: tr!("export.rowLabel", row = row),
+                        cells: (1..=layout.cable_columns)
+                            .map(|column| {
+                                let position = SensorPosition { layer, row, column };
+                                match positions.get(&position) {
+                                    Some(cell) => match (cell.status, cell.temperature) {
+                                        (SensorStatus::Valid, Some(temperature)) => {
+                                            styled(format!("{:.1}", temperature), range_style(display, temperature))
+                                        }
+                                        _ => styled(status_name(cell.status), &display.invalid),
+                                    },
+                                    None => DiagramCell {
+                                        text: String::new(),
+                                        color: None,
+                                        text_color: None,
+                                    },
+                                }
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let environment = &reading.environment;
+        Inspection {
+            reading_id: reading.id,
+            granary: reading.granary.clone(),
+            warehouse_name: warehouse.map_or_else(|| reading.granary.clone(), |warehouse| warehouse.name.clone()),
+            depot_name: depot.map(|depot| depot.name.clone()),
+            depot_address: depot.and_then(|depot| depot.address.clone()),
+            grain_type: warehouse.and_then(|warehouse| warehouse.grain_type.clone()),
+            capacity_tonnes: warehouse
+                .and_then(|warehouse| warehouse.capacity_tonnes)
+                .map(|capacity| capacity.to_string()),
+            sub_device_address: reading.sub_device_address,
+            sampled_at: reading.sampled_at.clone(),
+            indoor_temperature: temperature_text(environment.indoor_temperature),
+            indoor_humidity: humidity_text(environment.indoor_humidity),
+            outdoor_temperature: temperature_text(environment.outdoor_temperature),
+            outdoor_humidity: humidity_text(environment.outdoor_humidity),
+            cable_rows: layout.cable_rows,
+            cable_columns: layout.cable_columns,
+            points_per_cable: layout.points_per_cable,
+            overall: LayerSummary::new(&overall),
+            layers: layers.iter().map(LayerSummary::new).collect(),
+            diagrams,
+        }
+    }
+}
+
+impl LayerSummary {
+    fn new(stats: &LayerStats) -> Self {
+        LayerSummary {
+            label: match stats.layer {
+                Some(layer) => tr!("export.layer", layer = layer),
+                None => tr!("export.overall"),
+            },
+            valid_count: stats.valid_count,
+            max: temperature_text(stats.max),
+            min: temperature_text(stats.min),
+            average: temperature_text(stats.average),
+            fault_count: stats.faults.len(),
+            faults: fault_list(&stats.faults),
+        }
+    }
+}
+
+/// 温度所在区间的显示样式，不在任何区间内时使用故障样式
+fn range_style(display: &DisplayConfig, temperature: f64) -> &TemperatureStyle {
+    display
+        .temperature_ranges
+        .iter()
+        .find(|range| {
+            range.min.is_none_or(|min| temperature >= min) && range.max.is_none_or(|max| temperature < max)
+        })
+        .map_or(&display.invalid, |range| &range.style)
+}
+
+fn styled(text: String, style: &TemperatureStyle) -> DiagramCell {
+    DiagramCell {
+        text,
+        color: Some(style.color.clone()),
+        text_color: Some(style.text_color.clone()),
+    }
+}
+
+fn temperature_text(value: Option<f64>) -> String {
+    value.map_or_else(|| NO_VALUE.to_string(), |value| format!("{:.1}", value))
+}
+
+fn humidity_text(value: Option<u8>) -> String {
+    value.map_or_else(|| NO_VALUE.to_string(), |value| value.to_string())
+}
@@ -0,0 +1,312 @@
+// 报告模板渲染，支持Mustache模板语法的一个子集
+//
+// * `{{name}}` 输出字段，按HTML转义；`name` 可用 `.` 分隔访问下级字段，单独的 `.` 表示当前值
+// * `{{#name}}...{{/name}}` 字段为数组时逐项输出，为其他非空值时输出一次，为空时不输出
+// * `{{^name}}...{{/name}}` 字段为空时输出
+// * `{{! 注释 }}` 不输出
+//
+// 报告内容都要转义，不支持不转义输出的 `{{{name}}}` 和 `{{&name}}`，遇到时返回错误
+//
+// 字段从当前值开始逐级向外查找，在数组项中也可以使用外层的字段。
+// 空值包括null、false、空字符串和空数组
+use serde_json::Value;
+
+use crate::i18n::tr;
+
+/// 按上下文渲染模板
+///
+/// # 返回值
+///
+/// 渲染结果，模板标签不完整或区块未闭合时返回错误说明
+pub fn render(template: &str, context: &Value) -> Result<String, String> {
+    let mut output = String::with_capacity(template.len());
+    render_into(&mut output, template, &mut vec![context])?;
+    Ok(output)
+}
+
+fn render_into(output: &mut String, template: &str, stack: &mut Vec<&Value>) -> Result<(), String> {
+    let mut rest = template;
+    while let Some((before, tag, after)) = next_tag(rest)? {
+        output.push_str(before);
+        rest = after;
+        if let Some(name) = tag.strip_prefix('#') {
+            let name = name.trim();
+            let (body, after) = split_section(rest, name)?;
+            rest = after;
+            match lookup(stack, name) {
+                Some(Value::Array(items)) => {
+                    for item in items {
+                        stack.push(item);
+                        render_into(output, body, stack)?;
+                        stack.pop();
+                    }
+                }
+                Some(value) if !is_empty(value) => {
+                    stack.push(value);
+                    render_into(output, body, stack)?;
+                    stack.pop();
+                }
+                _ => {}
+            }
+        } else if let Some(name) = tag.strip_prefix('^') {
+            let name = name.trim();
+            let (body, after) = split_section(rest, name)?;
+            rest = after;
+            if lookup(stack, name).is_none_or(is_empty) {
+                render_into(output, body, stack)?;
+            }
+        } else if let Some(name) = tag.strip_prefix('/') {
+            return Err(tr!("report.template.unexpectedClose", name = name.trim()));
+        } else if tag.starts_with(['{', '&']) {
+            return Err(tr!("report.template.unescapedTag", tag = tag));
+        } else if !tag.starts_with('!') {
+            if let Some(value) = lookup(stack, tag) {
+                escape_into(output, &text(value));
+            }
+        }
+    }
+    output.push_str(rest);
+    Ok(())
+}
+
+/// 查找下一个标签
+///
+/// # 返回值
+///
+/// 标签之前的文本、去掉首尾空白的标签内容和标签之后的文本，没有标签时为None
+fn next_tag(template: &str) -> Result<Option<(&str, &str, &str)>, String> {
+    let Some(start) = template.find("{{") else {
+        return Ok(None);
+    };
+    let inner = &template[start + 2..];
+    let end = inner
+        .find("}}")
+        .ok_or_else(|| tr!("report.template.unclosedTag", offset = start))?;
+    Ok(Some((&template[..start], inner[..end].trim(), &inner[end + 2..])))
+}
+
+/// 找到与区块开始标签对应的结束标签，支持同名区块嵌套
+///
+/// # 返回值
+///
+/// 区块内容和结束标签之后的文本
+fn split_section<'t>(template: &'t str, name: &str) -> Result<(&'t str, &'t str), String> {
+    let mut depth = 0usize;
+    let mut offset = 0;
+    while let Some((before, tag, after)) = next_tag(&template[offset..])? {
+        let tag_start = offset + before.len();
+        offset = template.len() - after.len();
+        if let Some(inner) = tag.strip_prefix('#').or_else(|| tag.strip_prefix('^')) {
+            if inner.trim() == name {
+                depth += 1;
+            }
+        } else if let Some(inner) = tag.strip_prefix('/') {
+            if inner.trim() == name {
+                if depth == 0 {
+                    return Ok((&template[..tag_start], after));
+                }
+                depth -= 1;
+            }
+        }
+    }
+    Err(tr!("report.template.unclosedSection", name = name))
+}
+
+/// 从当前值开始逐级向外查找字段
+fn lookup<'a>(stack: &[&'a Value], name: &str) -> Option<&'a Value> {
+    if name == "." {
+        return stack.last().copied();
+    }
+    let mut segments = name.split('.');
+    let first = segments.next()?;
+    let mut value = stack.iter().rev().find_map(|value| value.get(first))?;
+    for segment in segments {
+        value = value.get(segment)?;
+    }
+    Some(value)
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Bool(value) => !value,
+        Value::String(text) => text.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+/// 字段的输出文本，null输出为空，数组和对象按JSON输出
+fn text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn escape_into(output: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            c => output.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::model::{Diagram, DiagramCell, DiagramRow, Inspection, LayerSummary, ReportContext};
+    use serde_json::json;
+
+    #[test]
+    fn renders_fields_and_sections() {
+        let context = json!({
+            "title": "1号仓",
+            "layers": [{"label": "第1层", "max": 18.5}, {"label": "第2层", "max": null}],
+            "remarks": ""
+        });
+        let template = "{{! 注释 }}<h1>{{title}}</h1>{{#layers}}[{{label}}:{{max}}]{{/layers}}\
+                        {{^remarks}}无{{/remarks}}";
+
+        assert_eq!(render(template, &context).unwrap(), "<h1>1号仓</h1>[第1层:18.5][第2层:]无");
+    }
+
+    #[test]
+    fn renders_nested_sections_with_same_name() {
+        let context = json!({"item": {"item": {"name": "内层"}, "name": "外层"}});
+        let template = "{{#item}}{{name}}-{{#item}}{{name}}{{/item}}{{/item}}";
+
+        assert_eq!(render(template, &context).unwrap(), "外层-内层");
+
+        let (body, after) = split_section("a{{#x}}b{{/x}}c{{/x}}d", "x").unwrap();
+        assert_eq!(body, "a{{#x}}b{{/x}}c");
+        assert_eq!(after, "d");
+    }
+
+    #[test]
+    fn looks_up_outer_scopes() {
+        let context = json!({
+            "unit": "°C",
+            "rows": [{"cells": [1, 2]}],
+            "meta": {"depot": {"name": "一库"}}
+        });
+        let template = "{{#rows}}{{#cells}}{{.}}{{unit}} {{/cells}}{{/rows}}{{meta.depot.name}}{{missing}}";
+
+        assert_eq!(render(template, &context).unwrap(), "1°C 2°C 一库");
+    }
+
+    #[test]
+    fn inverted_section_renders_for_empty_values() {
+        let context = json!({"none": null, "no": false, "empty": [], "yes": true});
+        let template = "{{^none}}a{{/none}}{{^no}}b{{/no}}{{^empty}}c{{/empty}}\
+                        {{^missing}}d{{/missing}}{{^yes}}e{{/yes}}";
+
+        assert_eq!(render(template, &context).unwrap(), "abcd");
+    }
+
+    #[test]
+    fn escapes_html() {
+        let context = json!({"remarks": "<b>\"A&B's\"</b>"});
+
+        assert_eq!(
+            render("{{remarks}}", &context).unwrap(),
+            "&lt;b&gt;&quot;A&amp;B&#39;s&quot;&lt;/b&gt;"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        let context = json!({"name": "x"});
+
+        assert!(render("{{name", &context).is_err());
+        assert!(render("{{#name}}没有结束", &context).is_err());
+        assert!(render("{{/name}}", &context).is_err());
+        assert!(render("{{{name}}}", &context).is_err());
+        assert!(render("{{&name}}", &context).is_err());
+    }
+
+    fn summary(label: &str) -> LayerSummary {
+        LayerSummary {
+            label: label.to_string(),
+            valid_count: 3,
+            max: "18.5".to_string(),
+            min: "16.0".to_string(),
+            average: "17.1".to_string(),
+            fault_count: 1,
+            faults: "#4 断路".to_string(),
+        }
+    }
+
+    fn cell(text: &str, color: Option<&str>) -> DiagramCell {
+        DiagramCell {
+            text: text.to_string(),
+            color: color.map(str::to_string),
+            text_color: color.map(|_| "#000000".to_string()),
+        }
+    }
+
+    #[test]
+    fn renders_standard_template() {
+        let inspection = Inspection {
+            reading_id: 12,
+            granary: "P1".to_string(),
+            warehouse_name: "1号仓".to_string(),
+            depot_name: Some("一库".to_string()),
+            depot_address: Some("粮库路1号".to_string()),
+            grain_type: Some("小麦".to_string()),
+            capacity_tonnes: Some("5000".to_string()),
+            sub_device_address: 1,
+            sampled_at: "2024-05-01 08:00:00".to_string(),
+            indoor_temperature: "20.2".to_string(),
+            indoor_humidity: "59".to_string(),
+            outdoor_temperature: "25.0".to_string(),
+            outdoor_humidity: "25".to_string(),
+            cable_rows: 2,
+            cable_columns: 2,
+            points_per_cable: 1,
+            overall: summary("全仓"),
+            layers: vec![summary("第1层")],
+            diagrams: vec![Diagram {
+                label: "第1层".to_string(),
+                columns: vec!["1".to_string(), "2".to_string()],
+                rows: vec![
+                    DiagramRow {
+                        label: "1".to_string(),
+                        cells: vec![cell("18.5", Some("#FFD700")), cell("16.0", None)],
+                    },
+                    DiagramRow {
+                        label: "2".to_string(),
+                        cells: vec![cell("16.8", None), cell("断路", Some("#CFCFCF"))],
+                    },
+                ],
+            }],
+        };
+        let context = ReportContext {
+            generated_at: "2024-05-01 09:30:00".to_string(),
+            report_date: "2024-05-01".to_string(),
+            inspector: Some("张三".to_string()),
+            remarks: Some("粮情正常".to_string()),
+            period: None,
+            inspection_count: 1,
+            inspections: vec![inspection],
+        };
+
+        let template = include_str!("templates/inspection.html");
+        let html = render(template, &serde_json::to_value(&context).unwrap()).unwrap();
+
+        assert!(!html.contains("{{"));
+        assert_eq!(html.matches("<section class=\"page\">").count(), 1);
+        assert!(html.contains("<th>仓房</th><td>1号仓（P1）</td>"));
+        assert!(html.contains("<td>2行×2列×1层</td>"));
+        assert!(html.contains("style=\"background-color: #FFD700; color: #000000\">18.5</td>"));
+        assert!(html.contains("<td>16.0</td>"));
+        assert!(html.contains("<span class=\"line\">张三</span>"));
+        assert!(!html.contains("报告期间"));
+    }
+}
@@ -0,0 +1,49 @@
+// 定时轮询错误定义
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::i18n::tr;
+
+/// 定时轮询任务的校验、执行或存储失败的原因
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum SchedulerError {
+    /// 时刻不是有效的 "HH:MM" 格式
+    InvalidTime { value: String },
+    /// 星期不在1-7范围内
+    InvalidWeekday { value: u8 },
+    /// 执行间隔短于允许的最小值
+    IntervalTooShort { seconds: u64, minimum: u64 },
+    /// 每日计划没有设置任何时刻
+    EmptySchedule,
+    /// 任务没有轮询目标
+    NoTargets,
+    /// 任务不存在
+    JobNotFound { id: String },
+    /// 任务的上一次执行尚未结束
+    AlreadyRunning { id: String },
+    /// 读写任务文件或执行记录失败
+    Storage { path: String, message: String },
+}
+
+impl fmt::Display for SchedulerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            SchedulerError::InvalidTime { value } => tr!("scheduler.invalidTime", value = value),
+            SchedulerError::InvalidWeekday { value } => tr!("scheduler.invalidWeekday", value = value),
+            SchedulerError::IntervalTooShort { seconds, minimum } => {
+                tr!("scheduler.intervalTooShort", seconds = seconds, minimum = minimum)
+            }
+            SchedulerError::EmptySchedule => tr!("scheduler.emptySchedule"),
+            SchedulerError::NoTargets => tr!("scheduler.noTargets"),
+            SchedulerError::JobNotFound { id } => tr!("scheduler.jobNotFound", id = id),
+            SchedulerError::AlreadyRunning { id } => tr!("scheduler.alreadyRunning", id = id),
+            SchedulerError::Storage { path, message } => {
+                tr!("scheduler.storage", path = path, error = message)
+            }
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for SchedulerError {}
@@ -0,0 +1,177 @@
+// 定时轮询任务定义与触发时间计算
+use chrono::{DateTime, Datelike, Duration, Local, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use super::SchedulerError;
+use crate::network::{BatchOptions, BatchSummary, NetworkError, PollTarget};
+
+/// 固定间隔计划允许的最小间隔（秒）
+pub const MIN_INTERVAL_SECS: u64 = 60;
+
+/// 任务的执行计划
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum Schedule {
+    /// 固定间隔执行，首次在任务启用一个间隔后执行
+    Interval { seconds: u64 },
+    /// 每天在指定时刻（"HH:MM"）执行，`weekdays` 不为空时只在列出的星期执行（1为星期一，7为星期日）
+    Daily {
+        times: Vec<String>,
+        #[serde(default)]
+        weekdays: Vec<u8>,
+    },
+}
+
+impl Schedule {
+    /// 校验计划参数
+    pub fn validate(&self) -> Result<(), SchedulerError> {
+        match self {
+            Schedule::Interval { seconds } => {
+                if *seconds < MIN_INTERVAL_SECS {
+                    return Err(SchedulerError::IntervalTooShort {
+                        seconds: *seconds,
+                        minimum: MIN_INTERVAL_SECS,
+                    });
+                }
+            }
+            Schedule::Daily { times, weekdays } => {
+                if times.is_empty() {
+                    return Err(SchedulerError::EmptySchedule);
+                }
+                parse_times(times)?;
+                if let Some(value) = weekdays.iter().find(|day| !(1..=7).contains(*day)) {
+                    return Err(SchedulerError::InvalidWeekday { value: *value });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 计算晚于 `after` 的下一次执行时间，计划无效时返回None
+    ///
+    /// 每日计划遇到夏令时跳过的时刻时顺延到下一个可用时刻
+    pub fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            Schedule::Interval { seconds } => {
+                let seconds = i64::try_from(*seconds).ok()?;
+                after.checked_add_signed(Duration::seconds(seconds))
+            }
+            Schedule::Daily { times, weekdays } => {
+                let times = parse_times(times).ok()?;
+                // 最多向后查找一周零一天，覆盖只在某个星期执行的计划
+                (0..=7)
+                    .filter_map(|offset| after.date_naive().checked_add_signed(Duration::days(offset)))
+                    .filter(|date| {
+                        weekdays.is_empty() || weekdays.contains(&(date.weekday().number_from_monday() as u8))
+                    })
+                    .flat_map(|date| times.iter().map(move |time| date.and_time(*time)))
+                    .filter_map(|naive| Local.from_local_datetime(&naive).earliest())
+                    .find(|candidate| *candidate > after)
+            }
+        }
+    }
+}
+
+/// 解析并排序每日计划的时刻
+fn parse_times(times: &[String]) -> Result<Vec<NaiveTime>, SchedulerError> {
+    let mut parsed = times
+        .iter()
+        .map(|value| {
+            NaiveTime::parse_from_str(value.trim(), "%H:%M").map_err(|_| SchedulerError::InvalidTime {
+                value: value.clone(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    parsed.sort();
+    parsed.dedup();
+    Ok(parsed)
+}
+
+/// 定时轮询任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollJob {
+    /// 任务标识，新建任务时留空由后端生成
+    #[serde(default)]
+    pub id: String,
+    /// 任务名称
+    pub name: String,
+    /// 是否启用
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 执行计划
+    pub schedule: Schedule,
+    /// 轮询目标
+    pub targets: Vec<PollTarget>,
+    /// 并发上限、超时与补发参数
+    #[serde(default)]
+    pub options: BatchOptions,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl PollJob {
+    /// 校验任务参数
+    pub fn validate(&self) -> Result<(), SchedulerError> {
+        if self.targets.is_empty() {
+            return Err(SchedulerError::NoTargets);
+        }
+        self.schedule.validate()
+    }
+}
+
+/// 任务及其运行状态
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobStatus {
+    #[serde(flatten)]
+    pub job: PollJob,
+    /// 下一次执行时间（RFC 3339），停用的任务为null
+    pub next_run_at: Option<String>,
+    /// 是否正在执行
+    pub running: bool,
+}
+
+/// 执行的触发方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RunTrigger {
+    /// 按计划自动执行
+    Scheduled,
+    /// 手动立即执行
+    Manual,
+}
+
+/// 执行中失败的目标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetFailure {
+    /// 目标在任务中的序号
+    pub index: usize,
+    /// 链路描述
+    pub target: String,
+    /// 分机地址
+    pub sub_device_address: u8,
+    /// 失败原因
+    pub error: NetworkError,
+}
+
+/// 一次执行的结果记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobRun {
+    /// 任务标识
+    pub job_id: String,
+    /// 执行时的任务名称
+    pub job_name: String,
+    /// 触发方式
+    pub trigger: RunTrigger,
+    /// 开始时间（RFC 3339）
+    pub started_at: String,
+    /// 执行汇总
+    pub summary: BatchSummary,
+    /// 失败的目标
+    pub failures: Vec<TargetFailure>,
+}
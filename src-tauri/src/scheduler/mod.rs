@@ -0,0 +1,461 @@
+// 定时轮询模块，按计划在后台自动读取粮情，窗口最小化时照常运行
+use chrono::{DateTime, Local};
+use log::{error, info, warn};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+
+use crate::alarm::AlarmEngine;
+use crate::history::HistoryStore;
+use crate::i18n::tr;
+use crate::inventory::Inventory;
+use crate::layout::Layouts;
+use crate::network::{batch, DeviceServer, RequestControl, TargetResult};
+
+// 导出错误定义
+pub mod error;
+// 导出任务定义模块
+pub mod job;
+// 导出任务存储模块
+pub mod store;
+
+pub use error::SchedulerError;
+pub use job::{JobRun, JobStatus, PollJob, RunTrigger, TargetFailure};
+use store::JobStore;
+
+/// 调度循环两次检查之间的最长间隔，系统时间被调整或休眠唤醒后最多延迟这么久
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// 定时轮询事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum SchedulerEvent {
+    /// 任务开始执行
+    RunStarted {
+        job_id: String,
+        job_name: String,
+        trigger: RunTrigger,
+    },
+    /// 任务中的一个目标完成
+    TargetCompleted { job_id: String, result: Box<TargetResult> },
+    /// 任务执行结束
+    RunFinished { run: JobRun },
+}
+
+/// 定时轮询事件回调
+type Listener = Arc<dyn Fn(&SchedulerEvent) + Send + Sync>;
+
+/// 调度器共享状态
+struct Shared {
+    store: JobStore,
+    server: DeviceServer,
+    history: HistoryStore,
+    alarms: AlarmEngine,
+    layouts: Layouts,
+    inventory: Inventory,
+    jobs: Mutex<Vec<PollJob>>,
+    /// 各启用任务的下一次执行时间
+    next_due: Mutex<HashMap<String, DateTime<Local>>>,
+    /// 正在执行的任务及其取消令牌
+    running: Mutex<HashMap<String, CancellationToken>>,
+    wake: Notify,
+    started: AtomicBool,
+    listener: Listener,
+}
+
+/// 定时轮询调度器，保存在Tauri托管状态中
+///
+/// 任务定义保存在数据目录下，应用启动后按计划执行；应用关闭期间错过的执行不会补做
+#[derive(Clone)]
+pub struct Scheduler {
+    shared: Arc<Shared>,
+}
+
+impl Scheduler {
+    /// 创建调度器并读取已保存的任务，任务开始、目标完成和任务结束时调用 `listener`
+    ///
+    /// # 参数
+    ///
+    /// * `dir` - 任务定义和执行记录的保存目录
+    /// * `server` - 接入服务，读取接入设备时使用
+    /// * `history` - 历史数据库，保存每个目标的读取结果
+    /// * `alarms` - 告警引擎，判定保存的读取结果
+    /// * `layouts` - 测温点布局，为读取结果填写测温点坐标
+    /// * `inventory` - 站点台账，未指定粮仓的目标按采集器归属到仓房
+    /// * `listener` - 定时轮询事件回调
+    pub fn with_listener<F>(
+        dir: &Path,
+        server: DeviceServer,
+        history: HistoryStore,
+        alarms: AlarmEngine,
+        layouts: Layouts,
+        inventory: Inventory,
+        listener: F,
+    ) -> Self
+    where
+        F: Fn(&SchedulerEvent) + Send + Sync + 'static,
+    {
+        let store = JobStore::new(dir);
+        let jobs = store.load_jobs().unwrap_or_else(|e| {
+            error!("{}", tr!("scheduler.loadFailed", error = e));
+            Vec::new()
+        });
+        info!("{}", tr!("scheduler.loaded", count = jobs.len()));
+        Scheduler {
+            shared: Arc::new(Shared {
+                store,
+                server,
+                history,
+                alarms,
+                layouts,
+                inventory,
+                jobs: Mutex::new(jobs),
+                next_due: Mutex::new(HashMap::new()),
+                running: Mutex::new(HashMap::new()),
+                wake: Notify::new(),
+                started: AtomicBool::new(false),
+                listener: Arc::new(listener),
+            }),
+        }
+    }
+
+    /// 启动后台调度循环，重复调用无效
+    pub fn start(&self) {
+        if self.shared.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        info!("{}", tr!("scheduler.loopStarted"));
+        tauri::async_runtime::spawn(run_loop(self.shared.clone()));
+    }
+
+    /// 列出全部任务及其下一次执行时间
+    pub fn jobs(&self) -> Vec<JobStatus> {
+        let jobs = lock(&self.shared.jobs).clone();
+        let next_due = lock(&self.shared.next_due).clone();
+        let running = lock(&self.shared.running);
+        jobs.into_iter()
+            .map(|job| {
+                // 调度循环尚未计算过的任务按当前时间推算
+                let next_run_at = if job.enabled {
+                    next_due
+                        .get(&job.id)
+                        .copied()
+                        .or_else(|| job.schedule.next_after(Local::now()))
+                        .map(|due| due.to_rfc3339())
+                } else {
+                    None
+                };
+                JobStatus {
+                    running: running.contains_key(&job.id),
+                    next_run_at,
+                    job,
+                }
+            })
+            .collect()
+    }
+
+    /// 新建或更新任务，`id` 为空或不存在时新建
+    ///
+    /// # 返回值
+    ///
+    /// 保存后的任务（新建时带生成的标识）
+    pub fn save_job(&self, mut job: PollJob) -> Result<PollJob, SchedulerError> {
+        job.validate()?;
+        let mut jobs = lock(&self.shared.jobs);
+        if job.id.trim().is_empty() {
+            job.id = generate_job_id(&jobs);
+        }
+        let mut updated = jobs.clone();
+        match updated.iter_mut().find(|existing| existing.id == job.id) {
+            Some(existing) => *existing = job.clone(),
+            None => updated.push(job.clone()),
+        }
+        self.shared.store.save_jobs(&updated)?;
+        *jobs = updated;
+        drop(jobs);
+
+        // 计划或启用状态可能已变化，由调度循环重新计算下一次执行时间
+        lock(&self.shared.next_due).remove(&job.id);
+        if !job.enabled {
+            self.cancel_run(&job.id);
+        }
+        self.shared.wake.notify_one();
+        info!("{}", tr!("scheduler.jobSaved", name = job.name, id = job.id));
+        Ok(job)
+    }
+
+    /// 删除任务，正在执行时一并取消
+    ///
+    /// # 返回值
+    ///
+    /// 任务存在时返回true
+    pub fn delete_job(&self, id: &str) -> Result<bool, SchedulerError> {
+        let mut jobs = lock(&self.shared.jobs);
+        if !jobs.iter().any(|job| job.id == id) {
+            return Ok(false);
+        }
+        let remaining: Vec<PollJob> = jobs.iter().filter(|job| job.id != id).cloned().collect();
+        self.shared.store.save_jobs(&remaining)?;
+        *jobs = remaining;
+        drop(jobs);
+
+        lock(&self.shared.next_due).remove(id);
+        self.cancel_run(id);
+        self.shared.wake.notify_one();
+        info!("{}", tr!("scheduler.jobDeleted", id = id));
+        Ok(true)
+    }
+
+    /// 用导入的任务替换全部任务，被移除或停用的任务正在执行时一并取消
+    ///
+    /// 标识为空的任务按新建处理并生成标识
+    pub fn replace_jobs(&self, mut jobs: Vec<PollJob>) -> Result<Vec<PollJob>, SchedulerError> {
+        for job in &jobs {
+            job.validate()?;
+        }
+        let mut current = lock(&self.shared.jobs);
+        for index in 0..jobs.len() {
+            if jobs[index].id.trim().is_empty() {
+                jobs[index].id = generate_job_id(&jobs);
+            }
+        }
+        self.shared.store.save_jobs(&jobs)?;
+        let stopped: Vec<String> = current
+            .iter()
+            .filter(|job| !jobs.iter().any(|kept| kept.id == job.id && kept.enabled))
+            .map(|job| job.id.clone())
+            .collect();
+        *current = jobs.clone();
+        drop(current);
+
+        // 全部任务的计划都可能已变化，由调度循环重新计算下一次执行时间
+        lock(&self.shared.next_due).clear();
+        for id in &stopped {
+            self.cancel_run(id);
+        }
+        self.shared.wake.notify_one();
+        info!("{}", tr!("scheduler.jobsReplaced", count = jobs.len()));
+        Ok(jobs)
+    }
+
+    /// 立即执行一次任务，不影响计划中的下一次执行时间
+    pub fn run_now(&self, id: &str) -> Result<(), SchedulerError> {
+        let job = lock(&self.shared.jobs)
+            .iter()
+            .find(|job| job.id == id)
+            .cloned()
+            .ok_or_else(|| SchedulerError::JobNotFound { id: id.to_string() })?;
+        spawn_run(&self.shared, job, RunTrigger::Manual)
+    }
+
+    /// 取消正在执行的任务
+    ///
+    /// # 返回值
+    ///
+    /// 任务正在执行时返回true
+    pub fn cancel_run(&self, id: &str) -> bool {
+        match lock(&self.shared.running).get(id) {
+            Some(token) => {
+                info!("{}", tr!("scheduler.cancelling", id = id));
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 读取执行记录，按时间从新到旧排列
+    pub fn runs(&self, job_id: Option<&str>, limit: usize) -> Result<Vec<JobRun>, SchedulerError> {
+        self.shared.store.load_runs(job_id, limit)
+    }
+}
+
+/// 调度循环：执行到期的任务，然后等待到最近的执行时间或任务变化
+async fn run_loop(shared: Arc<Shared>) {
+    loop {
+        let now = Local::now();
+        for job in take_due_jobs(&shared, now) {
+            if let Err(e) = spawn_run(&shared, job, RunTrigger::Scheduled) {
+                warn!("{}", tr!("scheduler.warning", error = e));
+            }
+        }
+
+        let wait = lock(&shared.next_due)
+            .values()
+            .min()
+            .map(|due| (*due - now).to_std().unwrap_or_default())
+            .map_or(MAX_SLEEP, |wait| wait.min(MAX_SLEEP));
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = shared.wake.notified() => {}
+        }
+    }
+}
+
+/// 取出到期的启用任务，并推算它们的下一次执行时间
+///
+/// 因休眠等原因错过多次执行时只执行一次，下一次执行时间从当前时间重新推算
+fn take_due_jobs(shared: &Shared, now: DateTime<Local>) -> Vec<PollJob> {
+    let jobs = lock(&shared.jobs);
+    let mut next_due = lock(&shared.next_due);
+    next_due.retain(|id, _| jobs.iter().any(|job| job.enabled && &job.id == id));
+
+    let mut due_jobs = Vec::new();
+    for job in jobs.iter().filter(|job| job.enabled) {
+        let due = match next_due.get(&job.id) {
+            Some(due) => *due,
+            None => {
+                if let Some(first) = job.schedule.next_after(now) {
+                    next_due.insert(job.id.clone(), first);
+                }
+                continue;
+            }
+        };
+        if due > now {
+            continue;
+        }
+        due_jobs.push(job.clone());
+        let next = job
+            .schedule
+            .next_after(due)
+            .filter(|next| *next > now)
+            .or_else(|| job.schedule.next_after(now));
+        match next {
+            Some(next) => {
+                next_due.insert(job.id.clone(), next);
+            }
+            None => {
+                next_due.remove(&job.id);
+            }
+        }
+    }
+    due_jobs
+}
+
+/// 在后台执行一次任务，执行结束后保存执行记录
+fn spawn_run(shared: &Arc<Shared>, job: PollJob, trigger: RunTrigger) -> Result<(), SchedulerError> {
+    let token = CancellationToken::new();
+    {
+        let mut running = lock(&shared.running);
+        if running.contains_key(&job.id) {
+            return Err(SchedulerError::AlreadyRunning { id: job.id });
+        }
+        running.insert(job.id.clone(), token.clone());
+    }
+
+    let shared = shared.clone();
+    tauri::async_runtime::spawn(async move {
+        let started_at = Local::now();
+        info!(
+            "{}",
+            tr!(
+                "scheduler.jobStarted",
+                name = job.name,
+                id = job.id,
+                trigger = format!("{:?}", trigger),
+                count = job.targets.len()
+            )
+        );
+        (shared.listener)(&SchedulerEvent::RunStarted {
+            job_id: job.id.clone(),
+            job_name: job.name.clone(),
+            trigger,
+        });
+
+        let failures = Arc::new(Mutex::new(Vec::new()));
+        let collected = failures.clone();
+        let listener = shared.listener.clone();
+        let history = shared.history.clone();
+        let alarms = shared.alarms.clone();
+        let layouts = shared.layouts.clone();
+        let job_id = job.id.clone();
+        let control = RequestControl::new(token);
+        // 每次执行时按当前台账归属，台账修改后无需重新保存任务
+        let mut targets = job.targets.clone();
+        for target in &mut targets {
+            shared.inventory.attribute(target);
+        }
+        let summary = batch::poll_batch(targets, &job.options, &shared.server, &control, move |result| {
+            if let Some(error) = &result.error {
+                lock(&collected).push(TargetFailure {
+                    index: result.index,
+                    target: result.target.link.describe(),
+                    sub_device_address: result.target.sub_device_address,
+                    error: error.clone(),
+                });
+            }
+            layouts.locate_target(result);
+            match history.record_target(result) {
+                Ok(Some(id)) => {
+                    if let Err(e) = alarms.evaluate(id) {
+                        error!("{}", tr!("scheduler.alarmFailed", id = job_id, error = e));
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => error!("{}", tr!("scheduler.historyFailed", id = job_id, error = e)),
+            }
+            listener(&SchedulerEvent::TargetCompleted {
+                job_id: job_id.clone(),
+                result: Box::new(result.clone()),
+            });
+        })
+        .await;
+
+        let mut failures = std::mem::take(&mut *lock(&failures));
+        failures.sort_by_key(|failure| failure.index);
+        let run = JobRun {
+            job_id: job.id.clone(),
+            job_name: job.name.clone(),
+            trigger,
+            started_at: started_at.to_rfc3339(),
+            summary,
+            failures,
+        };
+        // 执行记录保存在文件中，读写是阻塞操作，不能占住异步工作线程
+        let saving = shared.clone();
+        let record = run.clone();
+        match tokio::task::spawn_blocking(move || saving.store.append_run(&record)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => error!("{}", tr!("scheduler.runSaveFailed", id = job.id, error = e)),
+            Err(e) => error!("{}", tr!("scheduler.runSaveFailed", id = job.id, error = e)),
+        }
+        lock(&shared.running).remove(&job.id);
+        info!(
+            "{}",
+            tr!(
+                "scheduler.jobFinished",
+                name = job.name,
+                succeeded = run.summary.succeeded,
+                failed = run.summary.failed,
+                elapsed = run.summary.elapsed_ms
+            )
+        );
+        (shared.listener)(&SchedulerEvent::RunFinished { run });
+    });
+    Ok(())
+}
+
+/// 生成不与现有任务重复的任务标识
+fn generate_job_id(jobs: &[PollJob]) -> String {
+    let base = format!("job-{}", Local::now().format("%Y%m%d%H%M%S"));
+    let mut id = base.clone();
+    let mut suffix = 1;
+    while jobs.iter().any(|job| job.id == id) {
+        suffix += 1;
+        id = format!("{}-{}", base, suffix);
+    }
+    id
+}
+
+/// 获取锁，锁中毒时继续使用内部数据
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
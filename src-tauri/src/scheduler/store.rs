@@ -0,0 +1,131 @@
+// 定时轮询任务与执行记录的文件存储
+use log::{info, warn};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::job::{JobRun, PollJob};
+use super::SchedulerError;
+use crate::i18n::tr;
+
+/// 任务定义文件名
+const JOBS_FILE: &str = "jobs.json";
+/// 执行记录文件名，每行一条JSON记录
+const RUNS_FILE: &str = "runs.jsonl";
+/// 保留的执行记录条数
+const MAX_RUN_RECORDS: usize = 1000;
+
+/// 定时轮询的文件存储，任务定义和执行记录保存在同一目录下
+pub struct JobStore {
+    dir: PathBuf,
+}
+
+impl JobStore {
+    /// 使用指定目录创建存储，目录在首次写入时创建
+    pub fn new(dir: &Path) -> Self {
+        JobStore { dir: dir.to_path_buf() }
+    }
+
+    /// 读取任务定义，文件不存在时返回空列表
+    ///
+    /// 文件损坏时改名为 `jobs.json.broken` 保留，避免之后保存任务时覆盖
+    pub fn load_jobs(&self) -> Result<Vec<PollJob>, SchedulerError> {
+        let path = self.dir.join(JOBS_FILE);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(storage_error(&path, e)),
+        };
+        match serde_json::from_str(&content) {
+            Ok(jobs) => Ok(jobs),
+            Err(e) => {
+                let backup = self.dir.join(format!("{}.broken", JOBS_FILE));
+                warn!(
+                    "{}",
+                    tr!(
+                        "scheduler.fileBackedUp",
+                        path = path.display(),
+                        error = e,
+                        backup = backup.display()
+                    )
+                );
+                fs::rename(&path, &backup).map_err(|e| storage_error(&path, e))?;
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    /// 保存全部任务定义
+    pub fn save_jobs(&self, jobs: &[PollJob]) -> Result<(), SchedulerError> {
+        let content = serde_json::to_string_pretty(jobs).map_err(|e| storage_error(&self.dir.join(JOBS_FILE), e))?;
+        self.write_file(JOBS_FILE, &content)?;
+        info!("{}", tr!("scheduler.jobsSaved", count = jobs.len()));
+        Ok(())
+    }
+
+    /// 追加一条执行记录，超出保留条数时删除最早的记录
+    pub fn append_run(&self, run: &JobRun) -> Result<(), SchedulerError> {
+        let path = self.dir.join(RUNS_FILE);
+        let mut lines = self.read_run_lines()?;
+        lines.push(serde_json::to_string(run).map_err(|e| storage_error(&path, e))?);
+        let start = lines.len().saturating_sub(MAX_RUN_RECORDS);
+        let mut content = lines[start..].join("\n");
+        content.push('\n');
+        self.write_file(RUNS_FILE, &content)
+    }
+
+    /// 读取执行记录，按时间从新到旧排列
+    ///
+    /// # 参数
+    ///
+    /// * `job_id` - 只返回该任务的记录，为None时返回全部任务的记录
+    /// * `limit` - 最多返回的条数
+    pub fn load_runs(&self, job_id: Option<&str>, limit: usize) -> Result<Vec<JobRun>, SchedulerError> {
+        let runs = self
+            .read_run_lines()?
+            .iter()
+            .rev()
+            .filter_map(|line| match serde_json::from_str::<JobRun>(line) {
+                Ok(run) => Some(run),
+                Err(e) => {
+                    warn!("{}", tr!("scheduler.runSkipped", error = e));
+                    None
+                }
+            })
+            .filter(|run| job_id.is_none_or(|id| run.job_id == id))
+            .take(limit)
+            .collect();
+        Ok(runs)
+    }
+
+    /// 读取执行记录文件的非空行
+    fn read_run_lines(&self) -> Result<Vec<String>, SchedulerError> {
+        let path = self.dir.join(RUNS_FILE);
+        match fs::read_to_string(&path) {
+            Ok(content) => Ok(content
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(str::to_string)
+                .collect()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(storage_error(&path, e)),
+        }
+    }
+
+    /// 先写入临时文件再替换，避免写入中断时损坏原文件
+    fn write_file(&self, name: &str, content: &str) -> Result<(), SchedulerError> {
+        let path = self.dir.join(name);
+        let temp = self.dir.join(format!("{}.tmp", name));
+        fs::create_dir_all(&self.dir).map_err(|e| storage_error(&self.dir, e))?;
+        fs::write(&temp, content).map_err(|e| storage_error(&temp, e))?;
+        fs::rename(&temp, &path).map_err(|e| storage_error(&path, e))
+    }
+}
+
+/// 生成存储错误
+fn storage_error(path: &Path, error: impl std::fmt::Display) -> SchedulerError {
+    SchedulerError::Storage {
+        path: path.display().to_string(),
+        message: error.to_string(),
+    }
+}
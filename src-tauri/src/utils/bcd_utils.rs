@@ -0,0 +1,35 @@
+// BCD编码工具模块
+
+/// 将BCD编码的字节转换为十进制数值
+///
+/// # 参数
+///
+/// * `bcd` - BCD编码的字节，如 0x23 表示 23
+///
+/// # 返回值
+///
+/// 成功时返回十进制数值，字节中任意半字节大于9时返回None
+pub fn bcd_to_decimal(bcd: u8) -> Option<u8> {
+    let high = bcd >> 4;
+    let low = bcd & 0x0F;
+    if high > 9 || low > 9 {
+        return None;
+    }
+    Some(high * 10 + low)
+}
+
+/// 将十进制数值编码为BCD字节
+///
+/// # 参数
+///
+/// * `value` - 0-99之间的十进制数值
+///
+/// # 返回值
+///
+/// 成功时返回BCD编码的字节，数值超过99时返回None
+pub fn decimal_to_bcd(value: u8) -> Option<u8> {
+    if value > 99 {
+        return None;
+    }
+    Some(((value / 10) << 4) | (value % 10))
+}
@@ -2,3 +2,5 @@
 
 // 导出hex_utils模块
 pub mod hex_utils;
+// 导出bcd_utils模块
+pub mod bcd_utils;
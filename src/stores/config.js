@@ -1,27 +1,104 @@
 import { defineStore } from 'pinia'
+import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
 import { DEFAULT_CONFIG } from '../utils/dataSample'
 
+// 旧版本保存在LocalStorage中的配置键，首次加载时迁移到后端
+const LEGACY_STORAGE_KEY = 'appConfig'
+// 后端配置变化事件
+const CONFIG_EVENT = 'app-config'
+
+// 后端以null表示不限的温度上下限，界面使用正负无穷大
+const toRanges = (ranges) => ranges.map(range => ({
+  ...range,
+  min: range.min ?? -Infinity,
+  max: range.max ?? Infinity
+}))
+
+const fromRanges = (ranges) => ranges.map(range => ({
+  ...range,
+  min: Number.isFinite(range.min) ? range.min : null,
+  max: Number.isFinite(range.max) ? range.max : null
+}))
+
+// 后端配置转换为界面使用的平铺格式
+const toViewConfig = (backend) => ({
+  ...DEFAULT_CONFIG,
+  defaultIp: backend.network.defaultIp,
+  defaultPort: backend.network.defaultPort,
+  defaultSubDeviceAddr: backend.network.defaultSubDeviceAddress,
+  timeout: backend.network.timeoutMs,
+  defaultLayers: backend.layouts.default.pointsPerCable,
+  defaultRows: backend.layouts.default.cableRows,
+  defaultColumns: backend.layouts.default.cableColumns,
+  defaultSendData: backend.network.defaultSendData || DEFAULT_CONFIG.defaultSendData,
+  sampleResponseData: backend.display.sampleResponseData || DEFAULT_CONFIG.sampleResponseData,
+  temperatureConfig: {
+    ranges: toRanges(backend.display.temperatureRanges),
+    invalid: backend.display.invalid
+  }
+})
+
+// 界面配置合并回后端配置，保留界面不编辑的字段（如按粮仓单独设置的布局）
+const toBackendConfig = (backend, view) => ({
+  ...backend,
+  network: {
+    ...backend.network,
+    defaultIp: view.defaultIp,
+    defaultPort: view.defaultPort,
+    defaultSubDeviceAddress: view.defaultSubDeviceAddr,
+    timeoutMs: view.timeout,
+    defaultSendData: view.defaultSendData
+  },
+  layouts: {
+    ...backend.layouts,
+    default: {
+      ...backend.layouts.default,
+      pointsPerCable: view.defaultLayers,
+      cableRows: view.defaultRows,
+      cableColumns: view.defaultColumns
+    }
+  },
+  display: {
+    ...backend.display,
+    temperatureRanges: fromRanges(view.temperatureConfig.ranges),
+    invalid: view.temperatureConfig.invalid,
+    sampleResponseData: view.sampleResponseData
+  }
+})
+
 export const useConfigStore = defineStore('config', {
   state: () => ({
-    config: { ...DEFAULT_CONFIG }
+    config: { ...DEFAULT_CONFIG },
+    // 后端保存的完整配置
+    backend: null,
+    // 是否已监听后端配置变化
+    listening: false
   }),
-  
+
   actions: {
     updateConfig(newConfig) {
       this.config = { ...newConfig }
     },
-    
-    loadConfig() {
+
+    applyBackendConfig(backend) {
+      this.backend = backend
+      this.config = toViewConfig(backend)
+    },
+
+    async loadConfig() {
       try {
-        const savedConfig = localStorage.getItem('appConfig')
+        // 旧版本保存在LocalStorage中的配置由后端按旧格式升级后保存，之后不再使用LocalStorage
+        const savedConfig = localStorage.getItem(LEGACY_STORAGE_KEY)
         if (savedConfig) {
-          const parsedConfig = JSON.parse(savedConfig)
-          if (typeof parsedConfig === 'object' && parsedConfig !== null) {
-            this.config = {
-              ...DEFAULT_CONFIG,
-              ...parsedConfig
-            }
-          }
+          await invoke('save_app_config', { document: JSON.parse(savedConfig) })
+          localStorage.removeItem(LEGACY_STORAGE_KEY)
+        }
+        this.applyBackendConfig(await invoke('get_app_config'))
+
+        if (!this.listening) {
+          this.listening = true
+          await listen(CONFIG_EVENT, (event) => this.applyBackendConfig(event.payload.config))
         }
       } catch (e) {
         console.error('加载配置失败:', e)
@@ -29,13 +106,17 @@ export const useConfigStore = defineStore('config', {
       }
     },
 
-    saveConfig(configToSave) {
+    async saveConfig(configToSave) {
       try {
-        localStorage.setItem('appConfig', JSON.stringify(configToSave))
-        this.config = { ...configToSave }
+        const backend = this.backend ?? await invoke('get_app_config')
+        const saved = await invoke('save_app_config', {
+          document: toBackendConfig(backend, configToSave)
+        })
+        this.applyBackendConfig(saved)
       } catch (e) {
         console.error('保存配置失败:', e)
+        throw e
       }
     }
   }
-})
\ No newline at end of file
+})
@@ -0,0 +1,29 @@
+import { invoke } from '@tauri-apps/api/core';
+import { save } from '@tauri-apps/plugin-dialog';
+
+/**
+ * 导出文件格式及其文件对话框过滤条件
+ */
+const EXPORT_FILTERS = {
+  csv: { name: 'CSV', extensions: ['csv'] },
+  xlsx: { name: 'Excel', extensions: ['xlsx'] }
+};
+
+/**
+ * 选择保存路径并导出历史读数
+ * @param {Object} query 导出范围，readingId 为单次读取，或 granary、subDeviceAddress、from、to
+ * @param {'csv'|'xlsx'} format 文件格式
+ * @param {string} defaultName 默认文件名（不含扩展名）
+ * @returns {Promise<Object|null>} 导出结果，取消选择时为null
+ */
+export const exportReadings = async (query, format, defaultName) => {
+  const filter = EXPORT_FILTERS[format];
+  const path = await save({
+    defaultPath: `${defaultName}.${filter.extensions[0]}`,
+    filters: [filter]
+  });
+  if (!path) {
+    return null;
+  }
+  return invoke('export_readings', { query, format, path });
+};
@@ -0,0 +1,20 @@
+import { invoke } from '@tauri-apps/api/core';
+import { save } from '@tauri-apps/plugin-dialog';
+
+/**
+ * 选择保存路径并生成粮情检测报告，保存后用系统默认浏览器打开，在浏览器中打印或另存为PDF
+ * @param {Object} request 生成条件，readingId 为单次读取，或 granary、subDeviceAddress、from、to；
+ *   可选 inspector（检测人）、remarks（检测结论）、template（模板名称，默认为标准模板）
+ * @param {string} defaultName 默认文件名（不含扩展名）
+ * @returns {Promise<Object|null>} 生成结果，取消选择时为null
+ */
+export const saveInspectionReport = async (request, defaultName) => {
+  const path = await save({
+    defaultPath: `${defaultName}.html`,
+    filters: [{ name: 'HTML', extensions: ['html'] }]
+  });
+  if (!path) {
+    return null;
+  }
+  return invoke('save_inspection_report', { request, path, open: true });
+};